@@ -1,7 +1,14 @@
 use crate::asana::Asana;
 use crate::config::Config;
-use crate::events::network::{Event as NetworkEvent, Handler as NetworkEventHandler};
+use crate::events::network::{
+    Event as NetworkEvent, Handler as NetworkEventHandler, TICK_RATE_IN_SECS,
+};
 use crate::events::terminal::Handler as TerminalEventHandler;
+use crate::history::History;
+use crate::inbox::Inbox;
+use crate::journal::Journal;
+use crate::logger;
+use crate::notify::Notified;
 use crate::state::State;
 use anyhow::{anyhow, Result};
 use crossterm::{
@@ -10,14 +17,20 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use log::*;
-use std::io::{self, stdout};
+use std::io::{self, stdout, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::RecvTimeoutError;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tui::{
     backend::{Backend, CrosstermBackend},
     Terminal,
 };
-use tui_logger::{init_logger, set_default_level};
+
+/// Specifying the terminal bell raised for notifications.
+///
+const BELL: &[u8] = b"\x07";
 
 pub type NetworkEventSender = std::sync::mpsc::Sender<NetworkEvent>;
 type NetworkEventReceiver = std::sync::mpsc::Receiver<NetworkEvent>;
@@ -34,27 +47,66 @@ impl App {
     /// the result of the application execution.
     ///
     pub async fn start(config: Config) -> Result<()> {
-        init_logger(LevelFilter::Info).unwrap();
-        set_default_level(LevelFilter::Trace);
+        let directory = config
+            .directory()
+            .ok_or(anyhow!("Failed to retrieve configuration directory"))?;
+        let log_file_path = logger::init(directory)?;
 
         info!("Starting application...");
         let (tx, rx) = std::sync::mpsc::channel::<NetworkEvent>();
+        let journal = Journal::load(directory)?;
+        let history = History::load(directory)?;
+        let inbox = Inbox::load(directory)?;
+        let notified = Notified::load(directory)?;
         let mut app = App {
             access_token: config
                 .access_token
+                .clone()
                 .ok_or(anyhow!("Failed to retrieve access token"))?,
-            state: Arc::new(Mutex::new(State::new(tx.clone()))),
+            state: Arc::new(Mutex::new(State::new(tx.clone(), journal))),
         };
-        app.start_network(rx)?;
+        app.state
+            .lock()
+            .await
+            .set_keymap(config.keymap.clone())
+            .set_history(history)
+            .set_inbox_reads(inbox)
+            .set_color_support(config.color_support)
+            .set_theme(config.theme.clone())
+            .set_panels(config.panels)
+            .set_notifications(config.notifications.clone())
+            .set_notified(notified)
+            .set_my_teams_only(config.my_teams_only)
+            .set_export_directory(config.export_directory.to_owned())
+            .set_log_file_path(log_file_path.to_owned());
+        if config.refresh_interval_in_secs > 0 {
+            app.state
+                .lock()
+                .await
+                .set_refresh_interval(Some(chrono::Duration::seconds(
+                    config.refresh_interval_in_secs as i64,
+                )));
+        }
+        app.start_network(rx, log_file_path)?;
         app.start_ui(tx).await?;
 
+        let panels = *app.state.lock().await.get_panels();
+        if panels != config.panels {
+            config.save_panels(&panels)?;
+        }
+
         info!("Exiting application...");
         Ok(())
     }
 
-    /// Start a separate thread for asynchronous state mutations.
+    /// Start a separate thread for asynchronous state mutations, rotating the
+    /// log file between events once it grows too large.
     ///
-    fn start_network(&self, net_receiver: NetworkEventReceiver) -> Result<()> {
+    fn start_network(
+        &self,
+        net_receiver: NetworkEventReceiver,
+        log_file_path: PathBuf,
+    ) -> Result<()> {
         debug!("Creating new thread for asynchronous networking...");
         let cloned_state = Arc::clone(&self.state);
         let access_token = self.access_token.to_owned();
@@ -67,7 +119,18 @@ impl App {
                     let mut asana = Asana::new(&access_token);
                     let mut network_event_handler =
                         NetworkEventHandler::new(&cloned_state, &mut asana);
-                    while let Ok(network_event) = net_receiver.recv() {
+                    let tick_rate = Duration::from_secs(TICK_RATE_IN_SECS);
+                    loop {
+                        let network_event = match net_receiver.recv_timeout(tick_rate) {
+                            Ok(network_event) => network_event,
+                            Err(RecvTimeoutError::Timeout) => NetworkEvent::Tick,
+                            Err(RecvTimeoutError::Disconnected) => break,
+                        };
+                        if let NetworkEvent::Tick = network_event {
+                            if let Err(e) = logger::maintain(&log_file_path) {
+                                error!("Failed to rotate log file: {}", e);
+                            }
+                        }
                         match network_event_handler.handle(network_event).await {
                             Ok(_) => (),
                             Err(e) => error!("Failed to handle network event: {}", e),
@@ -100,6 +163,10 @@ impl App {
                 state.set_terminal_size(size);
             };
             terminal.draw(|frame| crate::ui::render(frame, &state))?;
+            if state.take_bell() {
+                terminal.backend_mut().write_all(BELL)?;
+                Backend::flush(terminal.backend_mut())?;
+            }
             if !terminal_event_handler.handle_next(&mut state)? {
                 debug!("Received application exit request.");
                 break;
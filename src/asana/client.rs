@@ -1,9 +1,18 @@
 use super::models::*;
+use super::SyncExpired;
 use anyhow::Result;
-use reqwest::{Method, Response};
+use reqwest::{Method, Response, StatusCode};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// Specify how long to wait for a response before considering Asana
+/// unreachable.
+///
+const REQUEST_TIMEOUT_IN_SECS: u64 = 30;
 
 /// Makes requests to Asana and tries to conform response data to given model.
 ///
+#[derive(Clone)]
 pub struct Client {
     access_token: String,
     base_url: String,
@@ -19,7 +28,10 @@ impl Client {
             access_token: access_token.to_owned(),
             base_url: base_url.to_owned(),
             endpoint: String::from(""),
-            http_client: reqwest::Client::builder().build().unwrap(),
+            http_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_IN_SECS))
+                .build()
+                .unwrap(),
         }
     }
 
@@ -27,7 +39,19 @@ impl Client {
     ///
     pub async fn get<T: Model>(&mut self, gid: &str) -> Result<T> {
         let model: Wrapper<T> = self
-            .call::<T>(Method::GET, Some(gid), None)
+            .call::<T>(Method::GET, Some(gid), None, None)
+            .await?
+            .json()
+            .await?;
+        Ok(model.data)
+    }
+
+    /// Return model data for the single entity at the endpoint, such as a
+    /// relation of another entity, or error.
+    ///
+    pub async fn find<T: Model>(&mut self, params: Vec<(&str, &str)>) -> Result<T> {
+        let model: Wrapper<T> = self
+            .call::<T>(Method::GET, None, Some(params), None)
             .await?
             .json()
             .await?;
@@ -39,7 +63,7 @@ impl Client {
     #[allow(dead_code)]
     pub async fn list<T: Model>(&mut self, params: Option<Vec<(&str, &str)>>) -> Result<Vec<T>> {
         let model: ListWrapper<T> = self
-            .call::<T>(Method::GET, None, params)
+            .call::<T>(Method::GET, None, params, None)
             .await?
             .json()
             .await?;
@@ -47,21 +71,121 @@ impl Client {
         Ok(model.data)
     }
 
+    /// Return vector of model data matching the search parameters within the
+    /// workspace or error.
+    ///
+    pub async fn search<T: Model>(
+        &mut self,
+        workspace_gid: &str,
+        params: Vec<(&str, &str)>,
+    ) -> Result<Vec<T>> {
+        self.endpoint = format!("workspaces/{}/", workspace_gid);
+        let model: ListWrapper<T> = self
+            .call::<T>(Method::GET, Some("search"), Some(params), None)
+            .await?
+            .json()
+            .await?;
+        Ok(model.data)
+    }
+
+    /// Create entity with the given data and return its model data or error.
+    ///
+    pub async fn create<T: Model>(&mut self, data: Value) -> Result<T> {
+        let model: Wrapper<T> = self
+            .call::<T>(Method::POST, None, None, Some(json!({ "data": data })))
+            .await?
+            .json()
+            .await?;
+        Ok(model.data)
+    }
+
+    /// Update entity with GID using the given data and return its model data
+    /// or error.
+    ///
+    pub async fn update<T: Model>(&mut self, gid: &str, data: Value) -> Result<T> {
+        let model: Wrapper<T> = self
+            .call::<T>(Method::PUT, Some(gid), None, Some(json!({ "data": data })))
+            .await?
+            .json()
+            .await?;
+        Ok(model.data)
+    }
+
+    /// Perform the named action on the entity with GID using the given data
+    /// or return error.
+    ///
+    pub async fn action<T: Model>(&mut self, gid: &str, action: &str, data: Value) -> Result<()> {
+        self.call::<T>(
+            Method::POST,
+            Some(&format!("{}/{}", gid, action)),
+            None,
+            Some(json!({ "data": data })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Delete entity with GID or return error.
+    ///
+    pub async fn delete<T: Model>(&mut self, gid: &str) -> Result<()> {
+        self.call::<T>(Method::DELETE, Some(gid), None, None)
+            .await?;
+        Ok(())
+    }
+
+    /// Return events for the resource since the sync token or a
+    /// `SyncExpired` error carrying a fresh token.
+    ///
+    pub async fn events<T: Model>(
+        &mut self,
+        resource_gid: &str,
+        sync: Option<&str>,
+    ) -> Result<EventsWrapper<T>> {
+        let mut params = vec![("resource", resource_gid)];
+        if let Some(sync) = sync {
+            params.push(("sync", sync));
+        }
+        let response = self
+            .request::<T>(Method::GET, None, Some(params), None)
+            .await?;
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            let data: SyncWrapper = response.json().await?;
+            return Err(SyncExpired { sync: data.sync }.into());
+        }
+        Ok(response.error_for_status()?.json().await?)
+    }
+
     /// Prepare endpoint for relational model data.
     ///
-    #[allow(dead_code)]
     pub fn from<T: Model>(&mut self, relational_gid: &str) -> &mut Client {
         self.endpoint = format!("{}/{}/", T::endpoint(), relational_gid);
         self
     }
 
-    /// Make request and return response with model data or error.
+    /// Make request and return response with model data or error, including
+    /// for unsuccessful statuses.
     ///
     async fn call<T: Model>(
         &mut self,
         method: Method,
         gid: Option<&str>,
         params: Option<Vec<(&str, &str)>>,
+        body: Option<Value>,
+    ) -> Result<Response> {
+        Ok(self
+            .request::<T>(method, gid, params, body)
+            .await?
+            .error_for_status()?)
+    }
+
+    /// Make request and return response regardless of status or error.
+    ///
+    async fn request<T: Model>(
+        &mut self,
+        method: Method,
+        gid: Option<&str>,
+        params: Option<Vec<(&str, &str)>>,
+        body: Option<Value>,
     ) -> Result<Response> {
         // Add both relational and main endpoints, and entity gid if supplied
         let uri = format!("{}{}/", self.endpoint, T::endpoint());
@@ -91,12 +215,14 @@ impl Client {
         }
         let request_url = format!("{}/{}", &self.base_url, uri);
 
-        // Make request
-        Ok(self
+        // Make request, attaching body if supplied
+        let mut request = self
             .http_client
             .request(method, &request_url)
-            .header("Authorization", format!("Bearer {}", &self.access_token))
-            .send()
-            .await?)
+            .header("Authorization", format!("Bearer {}", &self.access_token));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        Ok(request.send().await?)
     }
 }
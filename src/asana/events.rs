@@ -0,0 +1,95 @@
+use super::models::Model;
+use serde::Deserialize;
+use std::fmt;
+
+/// Indicates the sync token was missing or has expired, carrying a fresh
+/// token to sync from once the resource has been fully refreshed.
+///
+#[derive(Debug)]
+pub struct SyncExpired {
+    pub sync: String,
+}
+
+impl fmt::Display for SyncExpired {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sync token missing or expired")
+    }
+}
+
+impl std::error::Error for SyncExpired {}
+
+/// Specifying the different task changes reported by Asana.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaskEvent {
+    Added(String),
+    Changed(String),
+    Removed(String),
+}
+
+impl TaskEvent {
+    /// Return the GID of the changed task.
+    ///
+    pub fn task_gid(&self) -> &str {
+        match self {
+            TaskEvent::Added(gid) | TaskEvent::Changed(gid) | TaskEvent::Removed(gid) => gid,
+        }
+    }
+}
+
+/// Defines a batch of task changes and the token to resume syncing from.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskEvents {
+    pub events: Vec<TaskEvent>,
+    pub sync: String,
+    pub has_more: bool,
+}
+
+/// Defines the compact resource attached to an event.
+///
+#[derive(Deserialize, Debug)]
+pub(crate) struct EventResourceModel {
+    pub gid: String,
+    pub resource_type: String,
+}
+
+/// Defines an event as returned by Asana.
+///
+#[derive(Deserialize, Debug)]
+pub(crate) struct EventModel {
+    pub action: String,
+    pub resource: EventResourceModel,
+    pub parent: Option<EventResourceModel>,
+}
+
+impl Model for EventModel {
+    fn endpoint() -> String {
+        String::from("events")
+    }
+    fn field_names() -> &'static [&'static str] {
+        &["action", "resource", "parent"]
+    }
+    fn opt_strings() -> Vec<String> {
+        vec![]
+    }
+}
+
+impl EventModel {
+    /// Return the task change described by the event or None if the event
+    /// does not concern a task.
+    ///
+    pub fn task_event(self) -> Option<TaskEvent> {
+        if self.resource.resource_type != "task" {
+            return None;
+        }
+        let from_project = matches!(&self.parent, Some(p) if p.resource_type == "project");
+        let gid = self.resource.gid;
+        match self.action.as_str() {
+            "added" | "undeleted" if from_project => Some(TaskEvent::Added(gid)),
+            "removed" if from_project => Some(TaskEvent::Removed(gid)),
+            "deleted" => Some(TaskEvent::Removed(gid)),
+            _ => Some(TaskEvent::Changed(gid)),
+        }
+    }
+}
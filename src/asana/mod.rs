@@ -1,18 +1,121 @@
 mod client;
+mod events;
 mod models;
+mod mutation;
 mod resource;
 
+pub use events::*;
+pub use mutation::*;
 pub use resource::*;
 
 use crate::model;
 use anyhow::Result;
 use chrono::prelude::*;
 use client::Client;
+use events::EventModel;
 use log::*;
+use serde_json::json;
+
+/// Specifying how many recently modified tasks are searched for stories
+/// shown in the inbox.
+///
+const INBOX_TASK_LIMIT: usize = 30;
+
+model!(AssigneeModel "assignee" {
+    name: String,
+    email: Option<String>,
+});
+model!(DependencyModel "dependencies" {
+    name: String,
+    completed: bool,
+});
+model!(TaskModel "tasks" {
+    name: String,
+    completed: bool,
+    start_on: Option<String>,
+    due_on: Option<String>,
+    modified_at: Option<String>,
+    assignee: Option<AssigneeModel>,
+    memberships: Option<Vec<MembershipModel>>,
+    permalink_url: Option<String>,
+    dependencies: Option<Vec<DependencyModel>>,
+    assignee_section: Option<CompactModel>,
+} AssigneeModel, DependencyModel);
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct MembershipModel {
+    project: Option<CompactModel>,
+    section: Option<CompactModel>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct CompactModel {
+    gid: String,
+    name: Option<String>,
+}
+
+impl From<TaskModel> for Task {
+    fn from(t: TaskModel) -> Self {
+        Task {
+            gid: t.gid,
+            name: t.name,
+            completed: t.completed,
+            start_on: t.start_on,
+            due_on: t.due_on,
+            modified_at: t.modified_at,
+            assignee: t.assignee.map(|a| User {
+                gid: a.gid,
+                name: a.name,
+                email: a.email.unwrap_or_default(),
+            }),
+            memberships: t
+                .memberships
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|m| match (m.project, m.section) {
+                    (Some(project), Some(section)) => Some(Membership {
+                        project_gid: project.gid,
+                        section: Section {
+                            gid: section.gid,
+                            name: section.name.unwrap_or_default(),
+                        },
+                    }),
+                    _ => None,
+                })
+                .collect(),
+            assignee_section: t.assignee_section.map(|s| Section {
+                gid: s.gid,
+                name: s.name.unwrap_or_default(),
+            }),
+            permalink_url: t.permalink_url,
+            dependencies: t
+                .dependencies
+                .unwrap_or_default()
+                .into_iter()
+                .map(|d| RelatedTask {
+                    gid: d.gid,
+                    name: d.name,
+                    completed: d.completed,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Returns true if the error was caused by failing to reach Asana rather than
+/// by Asana rejecting the request.
+///
+pub fn is_network_error(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<reqwest::Error>() {
+        Some(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+        None => false,
+    }
+}
 
 /// Responsible for asynchronous interaction with the Asana API including
 /// transformation of response data into explicitly-defined types.
 ///
+#[derive(Clone)]
 pub struct Asana {
     client: Client,
 }
@@ -36,7 +139,10 @@ impl Asana {
     pub async fn me(&mut self) -> Result<(User, Vec<Workspace>)> {
         debug!("Requesting authenticated user details...");
 
-        model!(WorkspaceModel "workspaces" { name: String });
+        model!(WorkspaceModel "workspaces" {
+            name: String,
+            is_organization: Option<bool>,
+        });
         model!(UserModel "users" {
             email: String,
             name: String,
@@ -56,6 +162,7 @@ impl Asana {
                 .map(|w| Workspace {
                     gid: w.gid,
                     name: w.name,
+                    is_organization: w.is_organization.unwrap_or_default(),
                 })
                 .collect(),
         ))
@@ -66,7 +173,11 @@ impl Asana {
     pub async fn projects(&mut self, workspace_gid: &str) -> Result<Vec<Project>> {
         debug!("Requesting projects for workspace GID {}...", workspace_gid);
 
-        model!(ProjectModel "projects" { name: String });
+        model!(ProjectModel "projects" {
+            name: String,
+            permalink_url: Option<String>,
+            team: Option<CompactModel>,
+        });
 
         let data: Vec<ProjectModel> = self
             .client
@@ -78,41 +189,147 @@ impl Asana {
             .map(|p| Project {
                 gid: p.gid,
                 name: p.name,
+                permalink_url: p.permalink_url,
+                team: p.team.map(|t| Team {
+                    gid: t.gid,
+                    name: t.name.unwrap_or_default(),
+                }),
             })
             .collect())
     }
 
-    /// Returns a vector of tasks for the project.
+    /// Returns a vector of teams in the organization.
     ///
-    pub async fn tasks(&mut self, project_gid: &str) -> Result<Vec<Task>> {
-        debug!("Requesting tasks for project GID {}...", project_gid);
+    pub async fn teams(&mut self, workspace_gid: &str) -> Result<Vec<Team>> {
+        debug!("Requesting teams for organization GID {}...", workspace_gid);
 
-        model!(TaskModel "tasks" { name: String });
+        model!(OrganizationModel "organizations" { name: String });
+        model!(TeamModel "teams" { name: String });
 
-        let data: Vec<TaskModel> = self
+        let data: Vec<TeamModel> = self
             .client
-            .list::<TaskModel>(Some(vec![("project", project_gid)]))
+            .from::<OrganizationModel>(workspace_gid)
+            .list::<TeamModel>(None)
+            .await?;
+
+        Ok(data
+            .into_iter()
+            .map(|t| Team {
+                gid: t.gid,
+                name: t.name,
+            })
+            .collect())
+    }
+
+    /// Returns a vector of teams in the organization of which the user is a
+    /// member.
+    ///
+    pub async fn user_teams(&mut self, user_gid: &str, workspace_gid: &str) -> Result<Vec<Team>> {
+        debug!(
+            "Requesting teams for user GID {} and organization GID {}...",
+            user_gid, workspace_gid
+        );
+
+        model!(UserModel "users" { name: String });
+        model!(TeamModel "teams" { name: String });
+
+        let data: Vec<TeamModel> = self
+            .client
+            .from::<UserModel>(user_gid)
+            .list::<TeamModel>(Some(vec![("organization", workspace_gid)]))
+            .await?;
+
+        Ok(data
+            .into_iter()
+            .map(|t| Team {
+                gid: t.gid,
+                name: t.name,
+            })
+            .collect())
+    }
+
+    /// Returns a vector of tags for the workspace.
+    ///
+    pub async fn tags(&mut self, workspace_gid: &str) -> Result<Vec<Tag>> {
+        debug!("Requesting tags for workspace GID {}...", workspace_gid);
+
+        model!(TagModel "tags" { name: String });
+
+        let data: Vec<TagModel> = self
+            .client
+            .list::<TagModel>(Some(vec![("workspace", workspace_gid)]))
             .await?;
 
         Ok(data
             .into_iter()
-            .map(|t| Task {
+            .map(|t| Tag {
                 gid: t.gid,
                 name: t.name,
             })
             .collect())
     }
 
+    /// Returns a vector of sections for the project.
+    ///
+    pub async fn sections(&mut self, project_gid: &str) -> Result<Vec<Section>> {
+        debug!("Requesting sections for project GID {}...", project_gid);
+
+        model!(ProjectModel "projects" { name: String });
+        model!(SectionModel "sections" { name: String });
+
+        let data: Vec<SectionModel> = self
+            .client
+            .from::<ProjectModel>(project_gid)
+            .list::<SectionModel>(None)
+            .await?;
+
+        Ok(data
+            .into_iter()
+            .map(|s| Section {
+                gid: s.gid,
+                name: s.name,
+            })
+            .collect())
+    }
+
+    /// Returns a vector of tasks for the section.
+    ///
+    pub async fn section_tasks(&mut self, section_gid: &str) -> Result<Vec<Task>> {
+        debug!("Requesting tasks for section GID {}...", section_gid);
+
+        let data: Vec<TaskModel> = self
+            .client
+            .list::<TaskModel>(Some(vec![("section", section_gid)]))
+            .await?;
+
+        Ok(data.into_iter().map(Task::from).collect())
+    }
+
+    /// Returns a vector of tasks for the project.
+    ///
+    pub async fn tasks(&mut self, project_gid: &str) -> Result<Vec<Task>> {
+        debug!("Requesting tasks for project GID {}...", project_gid);
+
+        let data: Vec<TaskModel> = self
+            .client
+            .list::<TaskModel>(Some(vec![("project", project_gid)]))
+            .await?;
+
+        Ok(data.into_iter().map(Task::from).collect())
+    }
+
     /// Returns a vector of incomplete tasks assigned to the user.
     ///
-    pub async fn my_tasks(&mut self, user_gid: &str, workspace_gid: &str) -> Result<Vec<Task>> {
+    pub async fn assigned_tasks(
+        &mut self,
+        user_gid: &str,
+        workspace_gid: &str,
+    ) -> Result<Vec<Task>> {
         debug!(
             "Requesting tasks for user GID {} and workspace GID {}...",
             user_gid, workspace_gid
         );
 
-        model!(TaskModel "tasks" { name: String });
-
         let data: Vec<TaskModel> = self
             .client
             .list::<TaskModel>(Some(vec![
@@ -125,13 +342,367 @@ impl Asana {
             ]))
             .await?;
 
-        Ok(data
+        Ok(data.into_iter().map(Task::from).collect())
+    }
+
+    /// Returns the user's task list within the workspace.
+    ///
+    pub async fn user_task_list(
+        &mut self,
+        user_gid: &str,
+        workspace_gid: &str,
+    ) -> Result<UserTaskList> {
+        debug!(
+            "Requesting task list for user GID {} and workspace GID {}...",
+            user_gid, workspace_gid
+        );
+
+        model!(UserModel "users" { name: String });
+        model!(UserTaskListModel "user_task_list" { name: String });
+
+        let data: UserTaskListModel = self
+            .client
+            .from::<UserModel>(user_gid)
+            .find::<UserTaskListModel>(vec![("workspace", workspace_gid)])
+            .await?;
+
+        Ok(UserTaskList {
+            gid: data.gid,
+            name: data.name,
+        })
+    }
+
+    /// Returns a vector of incomplete tasks in the user task list.
+    ///
+    pub async fn my_tasks(&mut self, user_task_list_gid: &str) -> Result<Vec<Task>> {
+        debug!(
+            "Requesting tasks for user task list GID {}...",
+            user_task_list_gid
+        );
+
+        model!(UserTaskListModel "user_task_lists" { name: String });
+
+        let data: Vec<TaskModel> = self
+            .client
+            .from::<UserTaskListModel>(user_task_list_gid)
+            .list::<TaskModel>(Some(vec![(
+                "completed_since",
+                &Utc::now().format("%Y-%m-%dT%H:%M:%S%.fZ").to_string(),
+            )]))
+            .await?;
+
+        Ok(data.into_iter().map(Task::from).collect())
+    }
+
+    /// Returns the stories since the given time concerning the user on tasks
+    /// assigned to or followed by them, newest first, along with those tasks.
+    /// Followed tasks are skipped if the workspace does not support search.
+    ///
+    pub async fn inbox(
+        &mut self,
+        user_gid: &str,
+        workspace_gid: &str,
+        since: DateTime<Utc>,
+    ) -> Result<(Vec<Task>, Vec<InboxItem>)> {
+        debug!(
+            "Requesting inbox for user GID {} and workspace GID {}...",
+            user_gid, workspace_gid
+        );
+
+        model!(CreatorModel "created_by" { name: String });
+        model!(StoryModel "stories" {
+            resource_subtype: String,
+            created_at: String,
+            created_by: Option<CreatorModel>,
+            text: Option<String>,
+            html_text: Option<String>,
+        } CreatorModel);
+
+        let modified_since = since.format("%Y-%m-%dT%H:%M:%S%.fZ").to_string();
+        let mut tasks: Vec<Task> = self
+            .client
+            .list::<TaskModel>(Some(vec![
+                ("assignee", user_gid),
+                ("workspace", workspace_gid),
+                ("modified_since", &modified_since),
+            ]))
+            .await?
             .into_iter()
-            .map(|t| Task {
-                gid: t.gid,
-                name: t.name,
-            })
-            .collect())
+            .map(Task::from)
+            .collect();
+        match self
+            .client
+            .search::<TaskModel>(
+                workspace_gid,
+                vec![
+                    ("followers.any", user_gid),
+                    ("modified_at.after", &modified_since),
+                ],
+            )
+            .await
+        {
+            Ok(followed) => {
+                for task in followed.into_iter().map(Task::from) {
+                    if !tasks.iter().any(|t| t.gid == task.gid) {
+                        tasks.push(task);
+                    }
+                }
+            }
+            Err(err) => warn!("Skipping followed tasks in inbox: {}", err),
+        }
+        tasks.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        tasks.truncate(INBOX_TASK_LIMIT);
+
+        let mention = format!("data-asana-gid=\"{}\"", user_gid);
+        let mut items = vec![];
+        for task in tasks.iter() {
+            let stories: Vec<StoryModel> = self
+                .client
+                .from::<TaskModel>(&task.gid)
+                .list::<StoryModel>(None)
+                .await?;
+            let is_assignee = task.assignee.as_ref().map(|a| a.gid.as_str()) == Some(user_gid);
+            items.extend(stories.into_iter().filter_map(|s| {
+                let author = s.created_by.map(|c| User {
+                    gid: c.gid,
+                    name: c.name,
+                    email: String::new(),
+                });
+                let is_recent = DateTime::parse_from_rfc3339(&s.created_at)
+                    .map(|created_at| created_at >= since)
+                    .unwrap_or(false);
+                if !is_recent || author.as_ref().map(|a| a.gid.as_str()) == Some(user_gid) {
+                    return None;
+                }
+                let html_text = s.html_text.unwrap_or_default();
+                let kind = match s.resource_subtype.as_str() {
+                    "comment_added" if html_text.contains(&mention) => InboxKind::Mention,
+                    "comment_added" => InboxKind::Comment,
+                    "assigned" if is_assignee => InboxKind::Assignment,
+                    "marked_complete" => InboxKind::Completion,
+                    _ => return None,
+                };
+                Some(InboxItem {
+                    story_gid: s.gid,
+                    task_gid: task.gid.to_owned(),
+                    kind,
+                    author,
+                    created_at: s.created_at,
+                    text: s.text.unwrap_or_default(),
+                })
+            }));
+        }
+        items.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+        Ok((tasks, items))
+    }
+
+    /// Returns the task changes for the project since the sync token. Fails
+    /// with `SyncExpired` if the token is missing or expired.
+    ///
+    pub async fn project_events(
+        &mut self,
+        project_gid: &str,
+        sync: Option<&str>,
+    ) -> Result<TaskEvents> {
+        debug!("Requesting events for project GID {}...", project_gid);
+
+        let data = self.client.events::<EventModel>(project_gid, sync).await?;
+
+        Ok(TaskEvents {
+            events: data
+                .data
+                .into_iter()
+                .filter_map(EventModel::task_event)
+                .collect(),
+            sync: data.sync,
+            has_more: data.has_more,
+        })
+    }
+
+    /// Creates the task and returns it.
+    ///
+    pub async fn create_task(&mut self, new_task: &NewTask) -> Result<Task> {
+        debug!("Creating task '{}'...", new_task.name);
+
+        let mut data = json!({
+            "name": new_task.name,
+            "workspace": new_task.workspace_gid,
+        });
+        if let Some(notes) = &new_task.notes {
+            data["notes"] = json!(notes);
+        }
+        if let Some(assignee) = &new_task.assignee {
+            data["assignee"] = json!(assignee);
+        }
+        if let Some(due_on) = &new_task.due_on {
+            data["due_on"] = json!(due_on);
+        }
+        if new_task.completed {
+            data["completed"] = json!(true);
+        }
+        match (&new_task.project_gid, &new_task.section_gid) {
+            (Some(project_gid), Some(section_gid)) => {
+                data["memberships"] = json!([{ "project": project_gid, "section": section_gid }]);
+            }
+            (Some(project_gid), None) => {
+                data["projects"] = json!([project_gid]);
+            }
+            _ => (),
+        }
+
+        let task = self.client.create::<TaskModel>(data).await?;
+        Ok(Task::from(task))
+    }
+
+    /// Returns the task with the given GID.
+    ///
+    pub async fn task(&mut self, task_gid: &str) -> Result<Task> {
+        debug!("Requesting task GID {}...", task_gid);
+        let data = self.client.get::<TaskModel>(task_gid).await?;
+        Ok(Task::from(data))
+    }
+
+    /// Returns the notes, followers and comments of the task with the given
+    /// GID.
+    ///
+    pub async fn task_detail(&mut self, task_gid: &str) -> Result<TaskDetail> {
+        debug!("Requesting details for task GID {}...", task_gid);
+
+        model!(FollowerModel "followers" {
+            name: String,
+            email: Option<String>,
+        });
+        model!(DependentModel "dependents" {
+            name: String,
+            completed: bool,
+        });
+        model!(TaskDetailModel "tasks" {
+            html_notes: Option<String>,
+            followers: Option<Vec<FollowerModel>>,
+            dependents: Option<Vec<DependentModel>>,
+        } FollowerModel, DependentModel);
+        model!(CreatorModel "created_by" { name: String });
+        model!(StoryModel "stories" {
+            resource_subtype: String,
+            created_at: String,
+            created_by: Option<CreatorModel>,
+            html_text: Option<String>,
+        } CreatorModel);
+
+        let task = self.client.get::<TaskDetailModel>(task_gid).await?;
+        let stories: Vec<StoryModel> = self
+            .client
+            .from::<TaskModel>(task_gid)
+            .list::<StoryModel>(None)
+            .await?;
+
+        Ok(TaskDetail {
+            task_gid: task.gid,
+            html_notes: task.html_notes.unwrap_or_default(),
+            followers: task
+                .followers
+                .unwrap_or_default()
+                .into_iter()
+                .map(|f| User {
+                    gid: f.gid,
+                    name: f.name,
+                    email: f.email.unwrap_or_default(),
+                })
+                .collect(),
+            dependents: task
+                .dependents
+                .unwrap_or_default()
+                .into_iter()
+                .map(|d| RelatedTask {
+                    gid: d.gid,
+                    name: d.name,
+                    completed: d.completed,
+                })
+                .collect(),
+            comments: stories
+                .into_iter()
+                .filter(|s| s.resource_subtype == "comment_added")
+                .map(|s| Comment {
+                    gid: s.gid,
+                    author: s.created_by.map(|c| User {
+                        gid: c.gid,
+                        name: c.name,
+                        email: String::new(),
+                    }),
+                    created_at: s.created_at,
+                    html_text: s.html_text.unwrap_or_default(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Applies the mutation and returns the updated task, or None if the
+    /// mutation deleted it.
+    ///
+    pub async fn mutate(&mut self, mutation: &Mutation) -> Result<Option<Task>> {
+        debug!("Applying mutation {:?}...", mutation);
+        let task_gid = mutation.task_gid();
+        let data = match mutation {
+            Mutation::Complete { completed, .. } => json!({ "completed": completed }),
+            Mutation::Rename { name, .. } => json!({ "name": name }),
+            Mutation::SetDueDate { due_on, .. } => json!({ "due_on": due_on }),
+            Mutation::Assign { assignee, .. } => json!({ "assignee": assignee }),
+            Mutation::Comment { text, .. } => {
+                model!(StoryModel "stories" { text: String });
+                self.client
+                    .from::<TaskModel>(task_gid)
+                    .create::<StoryModel>(json!({ "text": text }))
+                    .await?;
+                return Ok(Some(self.task(task_gid).await?));
+            }
+            Mutation::MoveSection { section_gid, .. }
+            | Mutation::SetAssigneeSection { section_gid, .. } => {
+                model!(SectionModel "sections" { name: String });
+                self.client
+                    .action::<SectionModel>(section_gid, "addTask", json!({ "task": task_gid }))
+                    .await?;
+                return Ok(Some(self.task(task_gid).await?));
+            }
+            Mutation::AddTag { tag_gid, .. } => {
+                self.client
+                    .action::<TaskModel>(task_gid, "addTag", json!({ "tag": tag_gid }))
+                    .await?;
+                return Ok(Some(self.task(task_gid).await?));
+            }
+            Mutation::RemoveTag { tag_gid, .. } => {
+                self.client
+                    .action::<TaskModel>(task_gid, "removeTag", json!({ "tag": tag_gid }))
+                    .await?;
+                return Ok(Some(self.task(task_gid).await?));
+            }
+            Mutation::AddDependency { dependency_gid, .. } => {
+                self.client
+                    .action::<TaskModel>(
+                        task_gid,
+                        "addDependencies",
+                        json!({ "dependencies": [dependency_gid] }),
+                    )
+                    .await?;
+                return Ok(Some(self.task(task_gid).await?));
+            }
+            Mutation::RemoveDependency { dependency_gid, .. } => {
+                self.client
+                    .action::<TaskModel>(
+                        task_gid,
+                        "removeDependencies",
+                        json!({ "dependencies": [dependency_gid] }),
+                    )
+                    .await?;
+                return Ok(Some(self.task(task_gid).await?));
+            }
+            Mutation::Delete { .. } => {
+                self.client.delete::<TaskModel>(task_gid).await?;
+                return Ok(None);
+            }
+        };
+        let task = self.client.update::<TaskModel>(task_gid, data).await?;
+        Ok(Some(Task::from(task)))
     }
 }
 
@@ -213,11 +784,23 @@ mod tests {
                             "gid": projects[0].gid,
                             "resource_type": "task",
                             "name": projects[0].name,
+                            "permalink_url": projects[0].permalink_url,
+                            "team": projects[0].team.as_ref().map(|t| json!({
+                                "gid": t.gid,
+                                "resource_type": "team",
+                                "name": t.name,
+                            })),
                         },
                         {
                             "gid": projects[1].gid,
                             "resource_type": "task",
                             "name": projects[1].name,
+                            "permalink_url": projects[1].permalink_url,
+                            "team": projects[1].team.as_ref().map(|t| json!({
+                                "gid": t.gid,
+                                "resource_type": "team",
+                                "name": t.name,
+                            })),
                         }
                     ]
                 }));
@@ -227,36 +810,27 @@ mod tests {
         let mut asana = Asana {
             client: Client::new(&token.to_string(), &server.base_url()),
         };
-        asana.projects(&workspace.gid).await?;
+        assert_eq!(asana.projects(&workspace.gid).await?, projects);
         mock.assert_async().await;
         Ok(())
     }
 
     #[tokio::test]
-    async fn tasks_success() -> Result<()> {
+    async fn teams_success() -> Result<()> {
         let token: Uuid = UUIDv4.fake();
-        let project: Project = Faker.fake();
-        let tasks: [Task; 2] = Faker.fake();
+        let workspace: Workspace = Faker.fake();
+        let teams: [Team; 2] = Faker.fake();
 
         let server = MockServer::start();
         let mock = server
             .mock_async(|when, then| {
                 when.method("GET")
-                    .path("/tasks/")
-                    .header("Authorization", &format!("Bearer {}", &token))
-                    .query_param("project", &project.gid);
+                    .path(format!("/organizations/{}/teams/", workspace.gid))
+                    .header("Authorization", &format!("Bearer {}", &token));
                 then.status(200).json_body(json!({
                     "data": [
-                        {
-                            "gid": tasks[0].gid,
-                            "resource_type": "task",
-                            "name": tasks[0].name,
-                        },
-                        {
-                            "gid": tasks[1].gid,
-                            "resource_type": "task",
-                            "name": tasks[1].name,
-                        }
+                        { "gid": teams[0].gid, "resource_type": "team", "name": teams[0].name },
+                        { "gid": teams[1].gid, "resource_type": "team", "name": teams[1].name },
                     ]
                 }));
             })
@@ -265,16 +839,78 @@ mod tests {
         let mut asana = Asana {
             client: Client::new(&token.to_string(), &server.base_url()),
         };
-        asana.tasks(&project.gid).await?;
+        assert_eq!(asana.teams(&workspace.gid).await?, teams);
         mock.assert_async().await;
         Ok(())
     }
 
     #[tokio::test]
-    async fn my_tasks_success() -> Result<()> {
+    async fn user_teams_success() -> Result<()> {
         let token: Uuid = UUIDv4.fake();
         let user: User = Faker.fake();
         let workspace: Workspace = Faker.fake();
+        let team: Team = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/users/{}/teams/", user.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("organization", &workspace.gid);
+                then.status(200).json_body(json!({
+                    "data": [
+                        { "gid": team.gid, "resource_type": "team", "name": team.name },
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        assert_eq!(
+            asana.user_teams(&user.gid, &workspace.gid).await?,
+            vec![team]
+        );
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tags_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let workspace: Workspace = Faker.fake();
+        let tags: [Tag; 2] = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/tags/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("workspace", &workspace.gid);
+                then.status(200).json_body(json!({
+                    "data": [
+                        { "gid": tags[0].gid, "resource_type": "tag", "name": tags[0].name },
+                        { "gid": tags[1].gid, "resource_type": "tag", "name": tags[1].name },
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        assert_eq!(asana.tags(&workspace.gid).await?, tags);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tasks_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let project: Project = Faker.fake();
         let tasks: [Task; 2] = Faker.fake();
 
         let server = MockServer::start();
@@ -283,7 +919,48 @@ mod tests {
                 when.method("GET")
                     .path("/tasks/")
                     .header("Authorization", &format!("Bearer {}", &token))
-                    .query_param("assignee", &user.gid)
+                    .query_param("project", &project.gid);
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "gid": tasks[0].gid,
+                            "resource_type": "task",
+                            "name": tasks[0].name,
+                            "completed": tasks[0].completed,
+                        },
+                        {
+                            "gid": tasks[1].gid,
+                            "resource_type": "task",
+                            "name": tasks[1].name,
+                            "completed": tasks[1].completed,
+                        }
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        asana.tasks(&project.gid).await?;
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn assigned_tasks_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let user: User = Faker.fake();
+        let workspace: Workspace = Faker.fake();
+        let tasks: [Task; 2] = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/tasks/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("assignee", &user.gid)
                     .query_param("workspace", &workspace.gid)
                     .query_param_exists("completed_since");
                 then.status(200).json_body(json!({
@@ -292,11 +969,952 @@ mod tests {
                             "gid": tasks[0].gid,
                             "resource_type": "task",
                             "name": tasks[0].name,
+                            "completed": tasks[0].completed,
+                        },
+                        {
+                            "gid": tasks[1].gid,
+                            "resource_type": "task",
+                            "name": tasks[1].name,
+                            "completed": tasks[1].completed,
+                        }
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        asana.assigned_tasks(&user.gid, &workspace.gid).await?;
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn user_task_list_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let user: User = Faker.fake();
+        let workspace: Workspace = Faker.fake();
+        let user_task_list: UserTaskList = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/users/{}/user_task_list/", user.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("workspace", &workspace.gid);
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": user_task_list.gid,
+                        "resource_type": "user_task_list",
+                        "name": user_task_list.name,
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        assert_eq!(
+            asana.user_task_list(&user.gid, &workspace.gid).await?,
+            user_task_list
+        );
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn my_tasks_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let user_task_list: UserTaskList = Faker.fake();
+        let task: Task = Faker.fake();
+        let section: Section = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/user_task_lists/{}/tasks/", user_task_list.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param_exists("completed_since");
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "gid": task.gid,
+                            "resource_type": "task",
+                            "name": task.name,
+                            "completed": false,
+                            "assignee_section": {
+                                "gid": section.gid,
+                                "name": section.name,
+                            },
+                        }
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let tasks = asana.my_tasks(&user_task_list.gid).await?;
+        mock.assert_async().await;
+        assert_eq!(tasks[0].gid, task.gid);
+        assert_eq!(tasks[0].assignee_section, Some(section));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn task_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let assignee: User = Faker.fake();
+        let membership: Membership = Faker.fake();
+        let task = Task {
+            assignee: Some(assignee.to_owned()),
+            memberships: vec![membership.to_owned()],
+            ..Faker.fake()
+        };
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/tasks/{}", task.gid))
+                    .header("Authorization", &format!("Bearer {}", &token));
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "name": task.name,
+                        "completed": task.completed,
+                        "start_on": task.start_on,
+                        "due_on": task.due_on,
+                        "modified_at": task.modified_at,
+                        "permalink_url": task.permalink_url,
+                        "dependencies": task
+                            .dependencies
+                            .iter()
+                            .map(|d| json!({
+                                "gid": d.gid,
+                                "resource_type": "task",
+                                "name": d.name,
+                                "completed": d.completed,
+                            }))
+                            .collect::<Vec<_>>(),
+                        "assignee_section": task.assignee_section.as_ref().map(|s| json!({
+                            "gid": s.gid,
+                            "resource_type": "section",
+                            "name": s.name,
+                        })),
+                        "assignee": {
+                            "gid": assignee.gid,
+                            "resource_type": "user",
+                            "name": assignee.name,
+                            "email": assignee.email,
+                        },
+                        "memberships": [{
+                            "project": { "gid": membership.project_gid, "resource_type": "project" },
+                            "section": {
+                                "gid": membership.section.gid,
+                                "resource_type": "section",
+                                "name": membership.section.name,
+                            },
+                        }],
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        assert_eq!(asana.task(&task.gid).await?, task);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn task_detail_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let task: Task = Faker.fake();
+        let follower: User = Faker.fake();
+        let dependent: RelatedTask = Faker.fake();
+        let author: User = Faker.fake();
+
+        let server = MockServer::start();
+        let task_mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/tasks/{}", task.gid))
+                    .header("Authorization", &format!("Bearer {}", &token));
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "html_notes": "<body><strong>Notes</strong></body>",
+                        "followers": [{
+                            "gid": follower.gid,
+                            "resource_type": "user",
+                            "name": follower.name,
+                            "email": follower.email,
+                        }],
+                        "dependents": [{
+                            "gid": dependent.gid,
+                            "resource_type": "task",
+                            "name": dependent.name,
+                            "completed": dependent.completed,
+                        }],
+                    }
+                }));
+            })
+            .await;
+        let stories_mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/tasks/{}/stories/", task.gid))
+                    .header("Authorization", &format!("Bearer {}", &token));
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "gid": "1",
+                            "resource_type": "story",
+                            "resource_subtype": "assigned",
+                            "created_at": "2022-01-30T10:00:00.000Z",
+                            "created_by": null,
+                        },
+                        {
+                            "gid": "2",
+                            "resource_type": "story",
+                            "resource_subtype": "comment_added",
+                            "created_at": "2022-01-31T10:00:00.000Z",
+                            "created_by": {
+                                "gid": author.gid,
+                                "resource_type": "user",
+                                "name": author.name,
+                            },
+                            "html_text": "<body>Looks good</body>",
+                        },
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let detail = asana.task_detail(&task.gid).await?;
+        task_mock.assert_async().await;
+        stories_mock.assert_async().await;
+        assert_eq!(detail.html_notes, "<body><strong>Notes</strong></body>");
+        assert_eq!(detail.followers, vec![follower]);
+        assert_eq!(detail.dependents, vec![dependent]);
+        assert_eq!(detail.comments.len(), 1);
+        assert_eq!(
+            detail.comments[0].author.as_ref().unwrap().name,
+            author.name
+        );
+        assert_eq!(detail.comments[0].html_text, "<body>Looks good</body>");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn inbox_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let user: User = Faker.fake();
+        let workspace: Workspace = Faker.fake();
+        let author: User = Faker.fake();
+        let task: Task = Faker.fake();
+        let since = Utc.ymd(2022, 1, 30).and_hms(0, 0, 0);
+
+        let server = MockServer::start();
+        let tasks_mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/tasks/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("assignee", &user.gid)
+                    .query_param("workspace", &workspace.gid);
+                then.status(200).json_body(json!({
+                    "data": [{
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "name": task.name,
+                        "completed": false,
+                        "assignee": {
+                            "gid": user.gid,
+                            "resource_type": "user",
+                            "name": user.name,
+                        },
+                    }]
+                }));
+            })
+            .await;
+        let search_mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/workspaces/{}/tasks/search", workspace.gid))
+                    .query_param("followers.any", &user.gid);
+                then.status(402);
+            })
+            .await;
+        let stories_mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/tasks/{}/stories/", task.gid));
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "gid": "1",
+                            "resource_type": "story",
+                            "resource_subtype": "comment_added",
+                            "created_at": "2022-01-29T10:00:00.000Z",
+                            "created_by": null,
+                            "text": "Too old",
+                        },
+                        {
+                            "gid": "2",
+                            "resource_type": "story",
+                            "resource_subtype": "assigned",
+                            "created_at": "2022-01-30T10:00:00.000Z",
+                            "created_by": null,
+                            "text": "assigned to you",
+                        },
+                        {
+                            "gid": "3",
+                            "resource_type": "story",
+                            "resource_subtype": "comment_added",
+                            "created_at": "2022-01-31T10:00:00.000Z",
+                            "created_by": {
+                                "gid": author.gid,
+                                "resource_type": "user",
+                                "name": author.name,
+                            },
+                            "text": "Thoughts?",
+                            "html_text": format!(
+                                "<body><a data-asana-gid=\"{}\"></a> Thoughts?</body>",
+                                user.gid
+                            ),
+                        },
+                        {
+                            "gid": "4",
+                            "resource_type": "story",
+                            "resource_subtype": "comment_added",
+                            "created_at": "2022-01-31T11:00:00.000Z",
+                            "created_by": {
+                                "gid": user.gid,
+                                "resource_type": "user",
+                                "name": user.name,
+                            },
+                            "text": "Own comment",
+                        },
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let (tasks, items) = asana.inbox(&user.gid, &workspace.gid, since).await?;
+        tasks_mock.assert_async().await;
+        search_mock.assert_async().await;
+        stories_mock.assert_async().await;
+        assert_eq!(tasks.len(), 1);
+        let kinds: Vec<(&str, InboxKind)> = items
+            .iter()
+            .map(|i| (i.story_gid.as_str(), i.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![("3", InboxKind::Mention), ("2", InboxKind::Assignment)]
+        );
+        assert_eq!(items[0].author.as_ref().unwrap().name, author.name);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn inbox_recently_modified() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let user: User = Faker.fake();
+        let workspace: Workspace = Faker.fake();
+        let since = Utc.ymd(2022, 1, 30).and_hms(0, 0, 0);
+        let task = |gid: String, modified_at: String| {
+            json!({
+                "gid": gid,
+                "resource_type": "task",
+                "name": "Task",
+                "completed": false,
+                "modified_at": modified_at,
+            })
+        };
+        let assigned: Vec<serde_json::Value> = (0..INBOX_TASK_LIMIT + 1)
+            .map(|i| {
+                task(
+                    format!("{}", 100 + i),
+                    format!("2022-01-30T01:{:02}:00.000Z", i),
+                )
+            })
+            .collect();
+
+        let server = MockServer::start();
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/tasks/")
+                    .query_param("assignee", &user.gid);
+                then.status(200).json_body(json!({ "data": assigned }));
+            })
+            .await;
+        server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/workspaces/{}/tasks/search", workspace.gid))
+                    .query_param("followers.any", &user.gid);
+                then.status(200).json_body(json!({
+                    "data": [task(String::from("900"), String::from("2022-01-31T00:00:00.000Z"))]
+                }));
+            })
+            .await;
+        let oldest_stories_mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path("/tasks/100/stories/");
+                then.status(200).json_body(json!({ "data": [] }));
+            })
+            .await;
+        for i in 1..=INBOX_TASK_LIMIT {
+            server
+                .mock_async(|when, then| {
+                    when.method("GET")
+                        .path(format!("/tasks/{}/stories/", 100 + i));
+                    then.status(200).json_body(json!({ "data": [] }));
+                })
+                .await;
+        }
+        server
+            .mock_async(|when, then| {
+                when.method("GET").path("/tasks/900/stories/");
+                then.status(200).json_body(json!({
+                    "data": [{
+                        "gid": "1",
+                        "resource_type": "story",
+                        "resource_subtype": "comment_added",
+                        "created_at": "2022-01-31T10:00:00.000Z",
+                        "created_by": null,
+                        "text": "Thoughts?",
+                        "html_text": format!(
+                            "<body><a data-asana-gid=\"{}\"></a> Thoughts?</body>",
+                            user.gid
+                        ),
+                    }]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let (tasks, items) = asana.inbox(&user.gid, &workspace.gid, since).await?;
+        oldest_stories_mock.assert_hits_async(0).await;
+        assert_eq!(tasks.len(), INBOX_TASK_LIMIT);
+        assert_eq!(tasks[0].gid, "900");
+        assert!(!tasks.iter().any(|t| t.gid == "100"));
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].kind, InboxKind::Mention);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_task_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let new_task = NewTask {
+            due_on: Some(String::from("2022-01-31")),
+            ..Faker.fake()
+        };
+        let gid: String = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("POST")
+                    .path("/tasks/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .json_body_partial(
+                        json!({
+                            "data": {
+                                "name": new_task.name,
+                                "workspace": new_task.workspace_gid,
+                                "due_on": "2022-01-31",
+                            }
+                        })
+                        .to_string(),
+                    );
+                then.status(201).json_body(json!({
+                    "data": {
+                        "gid": gid,
+                        "resource_type": "task",
+                        "name": new_task.name,
+                        "completed": false,
+                        "due_on": "2022-01-31",
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let task = asana.create_task(&new_task).await?;
+        assert_eq!(task.gid, gid);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_task_in_section_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let new_task = NewTask {
+            project_gid: Some(Faker.fake()),
+            section_gid: Some(Faker.fake()),
+            ..Faker.fake()
+        };
+        let gid: String = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("POST")
+                    .path("/tasks/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .json_body_partial(
+                        json!({
+                            "data": {
+                                "memberships": [{
+                                    "project": new_task.project_gid,
+                                    "section": new_task.section_gid,
+                                }],
+                            }
+                        })
+                        .to_string(),
+                    );
+                then.status(201).json_body(json!({
+                    "data": {
+                        "gid": gid,
+                        "resource_type": "task",
+                        "name": new_task.name,
+                        "completed": new_task.completed,
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let task = asana.create_task(&new_task).await?;
+        assert_eq!(task.gid, gid);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutate_update_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let task: Task = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("PUT")
+                    .path(format!("/tasks/{}", task.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .json_body(json!({ "data": { "name": task.name } }));
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "name": task.name,
+                        "completed": task.completed,
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let mutation = Mutation::Rename {
+            task_gid: task.gid.to_owned(),
+            name: task.name.to_owned(),
+        };
+        assert_eq!(asana.mutate(&mutation).await?.unwrap().name, task.name);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutate_comment_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let task: Task = Faker.fake();
+        let text: String = Faker.fake();
+
+        let server = MockServer::start();
+        let story_mock = server
+            .mock_async(|when, then| {
+                when.method("POST")
+                    .path(format!("/tasks/{}/stories/", task.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .json_body(json!({ "data": { "text": text } }));
+                then.status(201).json_body(json!({
+                    "data": {
+                        "gid": "1",
+                        "resource_type": "story",
+                        "text": text,
+                    }
+                }));
+            })
+            .await;
+        let task_mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path(format!("/tasks/{}", task.gid));
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "name": task.name,
+                        "completed": task.completed,
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let mutation = Mutation::Comment {
+            task_gid: task.gid.to_owned(),
+            text: text.to_owned(),
+        };
+        asana.mutate(&mutation).await?;
+        story_mock.assert_async().await;
+        task_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutate_move_section_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let task: Task = Faker.fake();
+        let section: Section = Faker.fake();
+
+        let server = MockServer::start();
+        let section_mock = server
+            .mock_async(|when, then| {
+                when.method("POST")
+                    .path(format!("/sections/{}/addTask", section.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .json_body(json!({ "data": { "task": task.gid } }));
+                then.status(200).json_body(json!({ "data": {} }));
+            })
+            .await;
+        let task_mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path(format!("/tasks/{}", task.gid));
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "name": task.name,
+                        "completed": task.completed,
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let mutation = Mutation::MoveSection {
+            task_gid: task.gid.to_owned(),
+            project_gid: Faker.fake(),
+            section_gid: section.gid.to_owned(),
+            section_name: section.name.to_owned(),
+        };
+        assert!(asana.mutate(&mutation).await?.is_some());
+        section_mock.assert_async().await;
+        task_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutate_add_dependency_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let task: Task = Faker.fake();
+        let dependency: RelatedTask = Faker.fake();
+
+        let server = MockServer::start();
+        let dependency_mock = server
+            .mock_async(|when, then| {
+                when.method("POST")
+                    .path(format!("/tasks/{}/addDependencies", task.gid))
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .json_body(json!({ "data": { "dependencies": [dependency.gid] } }));
+                then.status(200).json_body(json!({ "data": {} }));
+            })
+            .await;
+        let task_mock = server
+            .mock_async(|when, then| {
+                when.method("GET").path(format!("/tasks/{}", task.gid));
+                then.status(200).json_body(json!({
+                    "data": {
+                        "gid": task.gid,
+                        "resource_type": "task",
+                        "name": task.name,
+                        "completed": task.completed,
+                        "dependencies": [{
+                            "gid": dependency.gid,
+                            "resource_type": "task",
+                            "name": dependency.name,
+                            "completed": dependency.completed,
+                        }],
+                    }
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let mutation = Mutation::AddDependency {
+            task_gid: task.gid.to_owned(),
+            dependency_gid: dependency.gid.to_owned(),
+            dependency_name: dependency.name.to_owned(),
+        };
+        let updated = asana.mutate(&mutation).await?.unwrap();
+        assert_eq!(updated.dependencies, vec![dependency]);
+        dependency_mock.assert_async().await;
+        task_mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutate_delete_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let task_gid: String = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("DELETE")
+                    .path(format!("/tasks/{}", task_gid))
+                    .header("Authorization", &format!("Bearer {}", &token));
+                then.status(200).json_body(json!({ "data": {} }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let mutation = Mutation::Delete {
+            task_gid: task_gid.to_owned(),
+        };
+        assert!(asana.mutate(&mutation).await?.is_none());
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mutate_unreachable() {
+        let mut asana = Asana {
+            client: Client::new("", "http://127.0.0.1:1"),
+        };
+        let mutation = Mutation::Complete {
+            task_gid: String::from("1"),
+            completed: true,
+        };
+        let err = asana.mutate(&mutation).await.unwrap_err();
+        assert!(is_network_error(&err));
+    }
+
+    #[tokio::test]
+    async fn mutate_malformed_response() {
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("PUT").path("/tasks/1");
+                then.status(200).body("not json");
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new("", &server.base_url()),
+        };
+        let mutation = Mutation::Complete {
+            task_gid: String::from("1"),
+            completed: true,
+        };
+        let err = asana.mutate(&mutation).await.unwrap_err();
+        mock.assert_async().await;
+        assert!(!is_network_error(&err));
+    }
+
+    #[tokio::test]
+    async fn project_events_sync_expired() {
+        let token: Uuid = UUIDv4.fake();
+        let project: Project = Faker.fake();
+        let sync: String = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/events/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("resource", &project.gid);
+                then.status(412).json_body(json!({
+                    "sync": sync,
+                    "errors": [{ "message": "Sync token invalid or too old." }]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let err = asana.project_events(&project.gid, None).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<SyncExpired>().unwrap().sync, sync);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn project_events_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let project: Project = Faker.fake();
+        let tasks: [Task; 3] = Faker.fake();
+        let sync: String = Faker.fake();
+        let next_sync: String = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/events/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("resource", &project.gid)
+                    .query_param("sync", &sync);
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "action": "added",
+                            "resource": { "gid": tasks[0].gid, "resource_type": "task" },
+                            "parent": { "gid": project.gid, "resource_type": "project" },
+                        },
+                        {
+                            "action": "changed",
+                            "resource": { "gid": tasks[1].gid, "resource_type": "task" },
+                            "parent": null,
+                        },
+                        {
+                            "action": "added",
+                            "resource": { "gid": "1", "resource_type": "story" },
+                            "parent": { "gid": tasks[1].gid, "resource_type": "task" },
+                        },
+                        {
+                            "action": "deleted",
+                            "resource": { "gid": tasks[2].gid, "resource_type": "task" },
+                            "parent": null,
+                        },
+                    ],
+                    "sync": next_sync,
+                    "has_more": false,
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        let events = asana.project_events(&project.gid, Some(&sync)).await?;
+        assert_eq!(
+            events,
+            TaskEvents {
+                events: vec![
+                    TaskEvent::Added(tasks[0].gid.to_owned()),
+                    TaskEvent::Changed(tasks[1].gid.to_owned()),
+                    TaskEvent::Removed(tasks[2].gid.to_owned()),
+                ],
+                sync: next_sync,
+                has_more: false,
+            }
+        );
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sections_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let project: Project = Faker.fake();
+        let sections: [Section; 2] = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path(format!("/projects/{}/sections/", project.gid))
+                    .header("Authorization", &format!("Bearer {}", &token));
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "gid": sections[0].gid,
+                            "resource_type": "section",
+                            "name": sections[0].name,
+                        },
+                        {
+                            "gid": sections[1].gid,
+                            "resource_type": "section",
+                            "name": sections[1].name,
+                        }
+                    ]
+                }));
+            })
+            .await;
+
+        let mut asana = Asana {
+            client: Client::new(&token.to_string(), &server.base_url()),
+        };
+        assert_eq!(asana.sections(&project.gid).await?, sections);
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn section_tasks_success() -> Result<()> {
+        let token: Uuid = UUIDv4.fake();
+        let section: Section = Faker.fake();
+        let tasks: [Task; 2] = Faker.fake();
+
+        let server = MockServer::start();
+        let mock = server
+            .mock_async(|when, then| {
+                when.method("GET")
+                    .path("/tasks/")
+                    .header("Authorization", &format!("Bearer {}", &token))
+                    .query_param("section", &section.gid);
+                then.status(200).json_body(json!({
+                    "data": [
+                        {
+                            "gid": tasks[0].gid,
+                            "resource_type": "task",
+                            "name": tasks[0].name,
+                            "completed": tasks[0].completed,
                         },
                         {
                             "gid": tasks[1].gid,
                             "resource_type": "task",
                             "name": tasks[1].name,
+                            "completed": tasks[1].completed,
                         }
                     ]
                 }));
@@ -306,7 +1924,7 @@ mod tests {
         let mut asana = Asana {
             client: Client::new(&token.to_string(), &server.base_url()),
         };
-        asana.my_tasks(&user.gid, &workspace.gid).await?;
+        asana.section_tasks(&section.gid).await?;
         mock.assert_async().await;
         Ok(())
     }
@@ -31,7 +31,7 @@ macro_rules! model {
             extra: std::collections::HashMap<String, serde_json::Value>,
         }
 
-        impl crate::asana::models::Model for $name {
+        impl $crate::asana::models::Model for $name {
             fn endpoint() -> String { $endpoint.to_string() }
             fn opt_strings() -> Vec<String> {
                 vec![$(format!("{}.({})", $include::endpoint(), $include::field_names().join("|"))),*]
@@ -58,3 +58,15 @@ pub(crate) struct Wrapper<T> {
 pub(crate) struct ListWrapper<T> {
     pub data: Vec<T>,
 }
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct EventsWrapper<T> {
+    pub data: Vec<T>,
+    pub sync: String,
+    pub has_more: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct SyncWrapper {
+    pub sync: String,
+}
@@ -0,0 +1,304 @@
+use super::{Membership, RelatedTask, Section, Task};
+use serde::{Deserialize, Serialize};
+
+/// Defines the write operations which can be made against a task.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Mutation {
+    Complete {
+        task_gid: String,
+        completed: bool,
+    },
+    Rename {
+        task_gid: String,
+        name: String,
+    },
+    Comment {
+        task_gid: String,
+        text: String,
+    },
+    SetDueDate {
+        task_gid: String,
+        due_on: Option<String>,
+    },
+    Assign {
+        task_gid: String,
+        assignee: Option<String>,
+    },
+    MoveSection {
+        task_gid: String,
+        project_gid: String,
+        section_gid: String,
+        section_name: String,
+    },
+    SetAssigneeSection {
+        task_gid: String,
+        section_gid: String,
+        section_name: String,
+    },
+    AddTag {
+        task_gid: String,
+        tag_gid: String,
+        tag_name: String,
+    },
+    RemoveTag {
+        task_gid: String,
+        tag_gid: String,
+        tag_name: String,
+    },
+    AddDependency {
+        task_gid: String,
+        dependency_gid: String,
+        dependency_name: String,
+    },
+    RemoveDependency {
+        task_gid: String,
+        dependency_gid: String,
+        dependency_name: String,
+    },
+    Delete {
+        task_gid: String,
+    },
+}
+
+impl Mutation {
+    /// Return the GID of the task the mutation targets.
+    ///
+    pub fn task_gid(&self) -> &str {
+        match self {
+            Mutation::Complete { task_gid, .. }
+            | Mutation::Rename { task_gid, .. }
+            | Mutation::Comment { task_gid, .. }
+            | Mutation::SetDueDate { task_gid, .. }
+            | Mutation::Assign { task_gid, .. }
+            | Mutation::MoveSection { task_gid, .. }
+            | Mutation::SetAssigneeSection { task_gid, .. }
+            | Mutation::AddTag { task_gid, .. }
+            | Mutation::RemoveTag { task_gid, .. }
+            | Mutation::AddDependency { task_gid, .. }
+            | Mutation::RemoveDependency { task_gid, .. }
+            | Mutation::Delete { task_gid } => task_gid,
+        }
+    }
+
+    /// Return a short description of the mutation for display.
+    ///
+    pub fn describe(&self) -> String {
+        match self {
+            Mutation::Complete {
+                completed: true, ..
+            } => String::from("Complete"),
+            Mutation::Complete {
+                completed: false, ..
+            } => String::from("Mark incomplete"),
+            Mutation::Rename { name, .. } => format!("Rename to '{}'", name),
+            Mutation::Comment { text, .. } => format!("Comment '{}'", text),
+            Mutation::SetDueDate {
+                due_on: Some(due_on),
+                ..
+            } => format!("Set due date to {}", due_on),
+            Mutation::SetDueDate { due_on: None, .. } => String::from("Clear due date"),
+            Mutation::Assign {
+                assignee: Some(assignee),
+                ..
+            } => format!("Assign to {}", assignee),
+            Mutation::Assign { assignee: None, .. } => String::from("Unassign"),
+            Mutation::MoveSection { section_name, .. }
+            | Mutation::SetAssigneeSection { section_name, .. } => {
+                format!("Move to section '{}'", section_name)
+            }
+            Mutation::AddTag { tag_name, .. } => format!("Add tag '{}'", tag_name),
+            Mutation::RemoveTag { tag_name, .. } => format!("Remove tag '{}'", tag_name),
+            Mutation::AddDependency {
+                dependency_name, ..
+            } => format!("Mark blocked by '{}'", dependency_name),
+            Mutation::RemoveDependency {
+                dependency_name, ..
+            } => format!("Unmark blocked by '{}'", dependency_name),
+            Mutation::Delete { .. } => String::from("Delete"),
+        }
+    }
+
+    /// Return a sentence describing the mutation once made to the subject,
+    /// such as a quoted task name or a count of tasks.
+    ///
+    pub fn summarize(&self, subject: &str) -> String {
+        match self {
+            Mutation::Complete {
+                completed: true, ..
+            } => format!("Completed {}", subject),
+            Mutation::Complete {
+                completed: false, ..
+            } => format!("Marked {} incomplete", subject),
+            Mutation::Rename { name, .. } => format!("Renamed {} to '{}'", subject, name),
+            Mutation::Comment { .. } => format!("Commented on {}", subject),
+            Mutation::SetDueDate {
+                due_on: Some(due_on),
+                ..
+            } => format!("Set due date of {} to {}", subject, due_on),
+            Mutation::SetDueDate { due_on: None, .. } => {
+                format!("Cleared due date of {}", subject)
+            }
+            Mutation::Assign {
+                assignee: Some(assignee),
+                ..
+            } => format!("Assigned {} to {}", subject, assignee),
+            Mutation::Assign { assignee: None, .. } => format!("Unassigned {}", subject),
+            Mutation::MoveSection { section_name, .. }
+            | Mutation::SetAssigneeSection { section_name, .. } => {
+                format!("Moved {} to section '{}'", subject, section_name)
+            }
+            Mutation::AddTag { tag_name, .. } => format!("Tagged {} '{}'", subject, tag_name),
+            Mutation::RemoveTag { tag_name, .. } => {
+                format!("Removed tag '{}' from {}", tag_name, subject)
+            }
+            Mutation::AddDependency {
+                dependency_name, ..
+            } => format!("Marked {} blocked by '{}'", subject, dependency_name),
+            Mutation::RemoveDependency {
+                dependency_name, ..
+            } => format!(
+                "Marked {} no longer blocked by '{}'",
+                subject, dependency_name
+            ),
+            Mutation::Delete { .. } => format!("Deleted {}", subject),
+        }
+    }
+
+    /// Return the mutation which reverses this one when made to the task in
+    /// its current state, or None if it cannot be reversed.
+    ///
+    pub fn inverse(&self, task: &Task) -> Option<Mutation> {
+        let task_gid = task.gid.to_owned();
+        match self {
+            Mutation::Complete { .. } => Some(Mutation::Complete {
+                task_gid,
+                completed: task.completed,
+            }),
+            Mutation::Rename { .. } => Some(Mutation::Rename {
+                task_gid,
+                name: task.name.to_owned(),
+            }),
+            Mutation::SetDueDate { .. } => Some(Mutation::SetDueDate {
+                task_gid,
+                due_on: task.due_on.to_owned(),
+            }),
+            Mutation::Assign { .. } => Some(Mutation::Assign {
+                task_gid,
+                assignee: task.assignee.as_ref().map(|a| a.gid.to_owned()),
+            }),
+            Mutation::MoveSection { project_gid, .. } => task
+                .memberships
+                .iter()
+                .find(|m| m.project_gid == *project_gid)
+                .map(|m| Mutation::MoveSection {
+                    task_gid,
+                    project_gid: project_gid.to_owned(),
+                    section_gid: m.section.gid.to_owned(),
+                    section_name: m.section.name.to_owned(),
+                }),
+            Mutation::SetAssigneeSection { .. } => {
+                task.assignee_section
+                    .as_ref()
+                    .map(|section| Mutation::SetAssigneeSection {
+                        task_gid,
+                        section_gid: section.gid.to_owned(),
+                        section_name: section.name.to_owned(),
+                    })
+            }
+            Mutation::AddDependency {
+                dependency_gid,
+                dependency_name,
+                ..
+            } => match task.dependencies.iter().any(|d| d.gid == *dependency_gid) {
+                true => None,
+                false => Some(Mutation::RemoveDependency {
+                    task_gid,
+                    dependency_gid: dependency_gid.to_owned(),
+                    dependency_name: dependency_name.to_owned(),
+                }),
+            },
+            Mutation::RemoveDependency {
+                dependency_gid,
+                dependency_name,
+                ..
+            } => match task.dependencies.iter().any(|d| d.gid == *dependency_gid) {
+                true => Some(Mutation::AddDependency {
+                    task_gid,
+                    dependency_gid: dependency_gid.to_owned(),
+                    dependency_name: dependency_name.to_owned(),
+                }),
+                false => None,
+            },
+            // The task's tags aren't loaded, so whether it had the tag before
+            // is unknown and reversing could drop or add one it had all along.
+            Mutation::AddTag { .. }
+            | Mutation::RemoveTag { .. }
+            | Mutation::Comment { .. }
+            | Mutation::Delete { .. } => None,
+        }
+    }
+
+    /// Apply the mutation to a local copy of the task.
+    ///
+    pub fn apply(&self, task: &mut Task) {
+        match self {
+            Mutation::Complete { completed, .. } => task.completed = *completed,
+            Mutation::Rename { name, .. } => task.name = name.to_owned(),
+            Mutation::SetDueDate { due_on, .. } => task.due_on = due_on.to_owned(),
+            Mutation::Assign { assignee: None, .. } => task.assignee = None,
+            Mutation::MoveSection {
+                project_gid,
+                section_gid,
+                section_name,
+                ..
+            } => {
+                task.memberships.retain(|m| m.project_gid != *project_gid);
+                task.memberships.push(Membership {
+                    project_gid: project_gid.to_owned(),
+                    section: Section {
+                        gid: section_gid.to_owned(),
+                        name: section_name.to_owned(),
+                    },
+                });
+            }
+            Mutation::SetAssigneeSection {
+                section_gid,
+                section_name,
+                ..
+            } => {
+                task.assignee_section = Some(Section {
+                    gid: section_gid.to_owned(),
+                    name: section_name.to_owned(),
+                });
+            }
+            Mutation::AddDependency {
+                dependency_gid,
+                dependency_name,
+                ..
+            } => {
+                task.dependencies.retain(|d| d.gid != *dependency_gid);
+                task.dependencies.push(RelatedTask {
+                    gid: dependency_gid.to_owned(),
+                    name: dependency_name.to_owned(),
+                    completed: false,
+                });
+            }
+            Mutation::RemoveDependency { dependency_gid, .. } => {
+                task.dependencies.retain(|d| d.gid != *dependency_gid);
+            }
+            Mutation::Comment { .. }
+            | Mutation::Assign { .. }
+            | Mutation::AddTag { .. }
+            | Mutation::RemoveTag { .. }
+            | Mutation::Delete { .. } => (),
+        }
+    }
+
+    /// Return true if the mutation deletes its task.
+    ///
+    pub fn is_delete(&self) -> bool {
+        matches!(self, Mutation::Delete { .. })
+    }
+}
@@ -1,8 +1,9 @@
 use fake::{Dummy, Fake};
+use serde::Serialize;
 
 /// Defines user data structure.
 ///
-#[derive(Clone, Debug, Dummy, PartialEq)]
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
 pub struct User {
     pub gid: String,
     pub name: String,
@@ -11,24 +12,145 @@ pub struct User {
 
 /// Defines workspace data structure.
 ///
-#[derive(Clone, Debug, Dummy, PartialEq)]
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
 pub struct Workspace {
     pub gid: String,
     pub name: String,
+    pub is_organization: bool,
 }
 
 /// Defines task data structure.
 ///
-#[derive(Clone, Debug, Dummy, PartialEq)]
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
 pub struct Task {
     pub gid: String,
     pub name: String,
+    pub completed: bool,
+    pub start_on: Option<String>,
+    pub due_on: Option<String>,
+    pub modified_at: Option<String>,
+    pub assignee: Option<User>,
+    pub memberships: Vec<Membership>,
+    pub assignee_section: Option<Section>,
+    pub permalink_url: Option<String>,
+    pub dependencies: Vec<RelatedTask>,
+}
+
+/// Defines a task related to another, such as one blocking it.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct RelatedTask {
+    pub gid: String,
+    pub name: String,
+    pub completed: bool,
+}
+
+/// Defines the section a task belongs to within a project.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct Membership {
+    pub project_gid: String,
+    pub section: Section,
+}
+
+/// Defines the notes, followers and comments of a task.
+///
+#[derive(Clone, Debug, Default, Dummy, PartialEq, Serialize)]
+pub struct TaskDetail {
+    pub task_gid: String,
+    pub html_notes: String,
+    pub followers: Vec<User>,
+    pub dependents: Vec<RelatedTask>,
+    pub comments: Vec<Comment>,
+}
+
+/// Defines comment data structure.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct Comment {
+    pub gid: String,
+    pub author: Option<User>,
+    pub created_at: String,
+    pub html_text: String,
+}
+
+/// Defines a recent story concerning the user on a task they are assigned
+/// to or follow.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct InboxItem {
+    pub story_gid: String,
+    pub task_gid: String,
+    pub kind: InboxKind,
+    pub author: Option<User>,
+    pub created_at: String,
+    pub text: String,
+}
+
+/// Specifying the kinds of stories shown in the inbox.
+///
+#[derive(Clone, Copy, Debug, Dummy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxKind {
+    Mention,
+    Assignment,
+    Comment,
+    Completion,
+}
+
+/// Defines data structure for creating a task.
+///
+#[derive(Clone, Debug, Default, Dummy, PartialEq)]
+pub struct NewTask {
+    pub workspace_gid: String,
+    pub project_gid: Option<String>,
+    pub section_gid: Option<String>,
+    pub name: String,
+    pub completed: bool,
+    pub notes: Option<String>,
+    pub assignee: Option<String>,
+    pub due_on: Option<String>,
 }
 
 /// Defines project data structure.
 ///
-#[derive(Clone, Debug, Dummy, PartialEq)]
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
 pub struct Project {
     pub gid: String,
     pub name: String,
+    pub permalink_url: Option<String>,
+    pub team: Option<Team>,
+}
+
+/// Defines team data structure.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct Team {
+    pub gid: String,
+    pub name: String,
+}
+
+/// Defines the data structure of the user's task list, which organises
+/// My Tasks into sections.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct UserTaskList {
+    pub gid: String,
+    pub name: String,
+}
+
+/// Defines tag data structure.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct Tag {
+    pub gid: String,
+    pub name: String,
+}
+
+/// Defines section data structure.
+///
+#[derive(Clone, Debug, Dummy, PartialEq, Serialize)]
+pub struct Section {
+    pub gid: String,
+    pub name: String,
 }
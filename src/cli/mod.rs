@@ -0,0 +1,406 @@
+pub mod output;
+
+use crate::asana::{Asana, Mutation, NewTask, Project, Task, User, Workspace};
+use crate::config::Config;
+use crate::date;
+use crate::export;
+use crate::import::{self, Status as ImportStatus};
+use anyhow::{anyhow, Result};
+use chrono::{Local, Utc};
+use clap::{App as ClapApp, AppSettings, Arg, ArgMatches, SubCommand};
+use output::{Format, Record, FORMATS};
+use std::path::Path;
+
+/// Return the subcommands for non-interactive use from scripts.
+///
+pub fn subcommands() -> Vec<ClapApp<'static, 'static>> {
+    vec![
+        SubCommand::with_name("projects")
+            .about("Manage projects.")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("list")
+                    .about("List projects in the workspace.")
+                    .arg(workspace_arg())
+                    .arg(output_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("export")
+                    .about("Export the tasks in a project grouped by section.")
+                    .arg(
+                        Arg::with_name("project")
+                            .help("GID or name of the project.")
+                            .required(true),
+                    )
+                    .arg(workspace_arg())
+                    .arg(
+                        Arg::with_name("format")
+                            .short("f")
+                            .long("format")
+                            .help("Format of the export.")
+                            .possible_values(export::FORMATS)
+                            .default_value("md")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("file")
+                            .long("file")
+                            .help("Path of the file to write. Defaults to stdout.")
+                            .takes_value(true),
+                    ),
+            ),
+        SubCommand::with_name("tasks")
+            .about("Manage tasks.")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("list")
+                    .about("List incomplete tasks assigned to a user or tasks in a project.")
+                    .arg(workspace_arg())
+                    .arg(project_arg())
+                    .arg(assignee_arg())
+                    .arg(output_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("import")
+                    .about("Create tasks in a project from a CSV file or Markdown checklist.")
+                    .arg(
+                        Arg::with_name("file")
+                            .help("Path of the .csv or .md file to import.")
+                            .required(true),
+                    )
+                    .arg(workspace_arg())
+                    .arg(project_arg().required(true))
+                    .arg(
+                        Arg::with_name("dry-run")
+                            .long("dry-run")
+                            .help("List the tasks which would be created without creating them."),
+                    )
+                    .arg(output_arg()),
+            ),
+        SubCommand::with_name("task")
+            .about("Manage a single task.")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("add")
+                    .about("Create a task.")
+                    .arg(
+                        Arg::with_name("name")
+                            .help("Name of the task.")
+                            .required(true),
+                    )
+                    .arg(workspace_arg())
+                    .arg(project_arg())
+                    .arg(assignee_arg())
+                    .arg(
+                        Arg::with_name("due")
+                            .long("due")
+                            .help("Due date such as 2022-01-31, today, fri or +3d.")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("notes")
+                            .long("notes")
+                            .help("Description of the task.")
+                            .takes_value(true),
+                    )
+                    .arg(output_arg()),
+            )
+            .subcommand(
+                SubCommand::with_name("complete")
+                    .about("Mark a task complete.")
+                    .arg(
+                        Arg::with_name("gid")
+                            .help("GID of the task.")
+                            .required(true),
+                    )
+                    .arg(output_arg()),
+            ),
+    ]
+}
+
+/// Run the subcommand against Asana and print the results to stdout.
+///
+pub async fn run(config: Config, matches: &ArgMatches<'_>) -> Result<()> {
+    let access_token = config
+        .access_token
+        .ok_or(anyhow!("Failed to retrieve access token"))?;
+    let mut asana = Asana::new(&access_token);
+    match matches.subcommand() {
+        ("projects", Some(matches)) => match matches.subcommand() {
+            ("list", Some(matches)) => projects_list(&mut asana, matches).await,
+            ("export", Some(matches)) => projects_export(&mut asana, matches).await,
+            _ => Err(anyhow!("Unknown projects command")),
+        },
+        ("tasks", Some(matches)) => match matches.subcommand() {
+            ("list", Some(matches)) => tasks_list(&mut asana, matches).await,
+            ("import", Some(matches)) => tasks_import(&mut asana, matches).await,
+            _ => Err(anyhow!("Unknown tasks command")),
+        },
+        ("task", Some(matches)) => match matches.subcommand() {
+            ("add", Some(matches)) => task_add(&mut asana, matches).await,
+            ("complete", Some(matches)) => task_complete(&mut asana, matches).await,
+            _ => Err(anyhow!("Unknown task command")),
+        },
+        _ => Err(anyhow!("Unknown command")),
+    }
+}
+
+/// Print the projects in the workspace.
+///
+async fn projects_list(asana: &mut Asana, matches: &ArgMatches<'_>) -> Result<()> {
+    let (_, workspace) = resolve_workspace(asana, matches.value_of("workspace")).await?;
+    let projects = asana.projects(&workspace.gid).await?;
+    print(&projects, matches)
+}
+
+/// Write the tasks in the project grouped by section to the file given or
+/// stdout otherwise.
+///
+async fn projects_export(asana: &mut Asana, matches: &ArgMatches<'_>) -> Result<()> {
+    let (_, workspace) = resolve_workspace(asana, matches.value_of("workspace")).await?;
+    let project = resolve_project(asana, &workspace, matches.value_of("project").unwrap()).await?;
+    let format: export::Format = matches.value_of("format").unwrap_or("md").parse()?;
+    let sections = export::fetch(asana, &project.gid).await?;
+    let output = export::render(&project, &sections, format, Utc::now())?;
+    match matches.value_of("file") {
+        Some(file_path) => std::fs::write(file_path, output)?,
+        None => print!("{}", output),
+    }
+    Ok(())
+}
+
+/// Print the tasks in the project, optionally limited to those assigned to
+/// the given user, or the incomplete tasks assigned to the user otherwise.
+///
+async fn tasks_list(asana: &mut Asana, matches: &ArgMatches<'_>) -> Result<()> {
+    let (user, workspace) = resolve_workspace(asana, matches.value_of("workspace")).await?;
+    let tasks = match matches.value_of("project") {
+        Some(project) => {
+            let project = resolve_project(asana, &workspace, project).await?;
+            let tasks = asana.tasks(&project.gid).await?;
+            match matches.value_of("assignee") {
+                Some(assignee) => tasks
+                    .into_iter()
+                    .filter(|t| is_assigned_to(t, assignee, &user))
+                    .collect(),
+                None => tasks,
+            }
+        }
+        None => {
+            let assignee = match matches.value_of("assignee") {
+                Some("me") | None => user.gid.as_str(),
+                Some(assignee) => assignee,
+            };
+            asana.assigned_tasks(assignee, &workspace.gid).await?
+        }
+    };
+    print(&tasks, matches)
+}
+
+/// Create the tasks read from the file in the project and print the outcome
+/// for each row, or only validate them if a dry run was requested.
+///
+async fn tasks_import(asana: &mut Asana, matches: &ArgMatches<'_>) -> Result<()> {
+    let (_, workspace) = resolve_workspace(asana, matches.value_of("workspace")).await?;
+    let project = resolve_project(asana, &workspace, matches.value_of("project").unwrap()).await?;
+    let file_path = Path::new(matches.value_of("file").unwrap());
+    let mut rows = import::read(file_path, Local::today().naive_local())?;
+    let sections = asana.sections(&project.gid).await?;
+    import::validate(&mut rows, &sections);
+    if !matches.is_present("dry-run") {
+        for row in rows.iter_mut() {
+            if row.status != ImportStatus::Ready {
+                continue;
+            }
+            row.status =
+                match import::create(asana, &workspace.gid, &project.gid, &sections, row).await {
+                    Ok(task) => ImportStatus::Created(task.gid),
+                    Err(err) => ImportStatus::Failed(err.to_string()),
+                };
+        }
+    }
+    print(&rows, matches)?;
+    let skipped = rows
+        .iter()
+        .filter(|r| matches!(r.status, ImportStatus::Invalid(_) | ImportStatus::Failed(_)))
+        .count();
+    match skipped {
+        0 => Ok(()),
+        _ => Err(anyhow!(
+            "Failed to import {} of {} rows",
+            skipped,
+            rows.len()
+        )),
+    }
+}
+
+/// Create a task and print it.
+///
+async fn task_add(asana: &mut Asana, matches: &ArgMatches<'_>) -> Result<()> {
+    let (_, workspace) = resolve_workspace(asana, matches.value_of("workspace")).await?;
+    let project_gid = match matches.value_of("project") {
+        Some(project) => Some(resolve_project(asana, &workspace, project).await?.gid),
+        None => None,
+    };
+    let due_on = match matches.value_of("due") {
+        Some(due) => Some(date::parse(due, Local::today().naive_local())?.to_string()),
+        None => None,
+    };
+    let new_task = NewTask {
+        workspace_gid: workspace.gid,
+        project_gid,
+        section_gid: None,
+        name: matches.value_of("name").unwrap().to_owned(),
+        completed: false,
+        notes: matches.value_of("notes").map(str::to_owned),
+        assignee: matches.value_of("assignee").map(str::to_owned),
+        due_on,
+    };
+    let task = asana.create_task(&new_task).await?;
+    print(&[task], matches)
+}
+
+/// Mark a task complete and print it.
+///
+async fn task_complete(asana: &mut Asana, matches: &ArgMatches<'_>) -> Result<()> {
+    let mutation = Mutation::Complete {
+        task_gid: matches.value_of("gid").unwrap().to_owned(),
+        completed: true,
+    };
+    let tasks: Vec<Task> = asana.mutate(&mutation).await?.into_iter().collect();
+    print(&tasks, matches)
+}
+
+/// Print the records in the output format requested.
+///
+fn print<T: Record>(records: &[T], matches: &ArgMatches<'_>) -> Result<()> {
+    let format: Format = matches.value_of("output").unwrap_or("table").parse()?;
+    print!("{}", output::render(records, format)?);
+    Ok(())
+}
+
+/// Return the current user and the workspace matching the GID or name, or
+/// their first workspace if none was given.
+///
+async fn resolve_workspace(
+    asana: &mut Asana,
+    workspace: Option<&str>,
+) -> Result<(User, Workspace)> {
+    let (user, workspaces) = asana.me().await?;
+    let workspace = match workspace {
+        Some(workspace) => workspaces
+            .into_iter()
+            .find(|w| w.gid == workspace || w.name.eq_ignore_ascii_case(workspace))
+            .ok_or(anyhow!("Failed to find workspace '{}'", workspace))?,
+        None => workspaces
+            .into_iter()
+            .next()
+            .ok_or(anyhow!("Failed to find any workspaces"))?,
+    };
+    Ok((user, workspace))
+}
+
+/// Return the project in the workspace matching the GID or name.
+///
+async fn resolve_project(
+    asana: &mut Asana,
+    workspace: &Workspace,
+    project: &str,
+) -> Result<Project> {
+    let project = project.trim();
+    if project.is_empty() {
+        return Err(anyhow!("Missing project GID or name"));
+    }
+    find_project(asana.projects(&workspace.gid).await?, project)
+        .ok_or(anyhow!("Failed to find project '{}'", project))
+}
+
+/// Return the project whose GID matches if the given text is numeric, or
+/// otherwise whose name matches ignoring case.
+///
+fn find_project(projects: Vec<Project>, project: &str) -> Option<Project> {
+    let by_gid = match project.chars().all(|c| c.is_ascii_digit()) {
+        true => projects.iter().position(|p| p.gid == project),
+        false => None,
+    };
+    let index = by_gid.or_else(|| {
+        projects
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case(project))
+    })?;
+    projects.into_iter().nth(index)
+}
+
+/// Return true if the task is assigned to the user with the given GID or
+/// email, where `me` refers to the current user.
+///
+fn is_assigned_to(task: &Task, assignee: &str, me: &User) -> bool {
+    let assignee = match assignee {
+        "me" => me.gid.as_str(),
+        assignee => assignee,
+    };
+    match &task.assignee {
+        Some(user) => user.gid == assignee || user.email.eq_ignore_ascii_case(assignee),
+        None => false,
+    }
+}
+
+fn workspace_arg() -> Arg<'static, 'static> {
+    Arg::with_name("workspace")
+        .short("w")
+        .long("workspace")
+        .help("GID or name of the workspace. Defaults to the first workspace.")
+        .takes_value(true)
+}
+
+fn project_arg() -> Arg<'static, 'static> {
+    Arg::with_name("project")
+        .short("p")
+        .long("project")
+        .help("GID or name of the project.")
+        .takes_value(true)
+}
+
+fn assignee_arg() -> Arg<'static, 'static> {
+    Arg::with_name("assignee")
+        .short("a")
+        .long("assignee")
+        .help("GID or email of the assignee, or `me`.")
+        .takes_value(true)
+}
+
+fn output_arg() -> Arg<'static, 'static> {
+    Arg::with_name("output")
+        .short("o")
+        .long("output")
+        .help("Format of the output.")
+        .possible_values(FORMATS)
+        .default_value("table")
+        .takes_value(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::{Fake, Faker};
+
+    #[test]
+    fn find_project_by_gid_or_name() {
+        let mut projects = vec![Faker.fake::<Project>(), Faker.fake::<Project>()];
+        projects[0].gid = String::from("1100");
+        projects[1].name = String::from("2022");
+        assert_eq!(
+            find_project(projects.to_owned(), "1100"),
+            Some(projects[0].to_owned())
+        );
+        assert_eq!(
+            find_project(projects.to_owned(), "2022"),
+            Some(projects[1].to_owned())
+        );
+        assert_eq!(
+            find_project(projects.to_owned(), &projects[0].name.to_uppercase()),
+            Some(projects[0].to_owned())
+        );
+        assert_eq!(find_project(projects, "42"), None);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::asana::{Project, Section, Task, Team, User};
+use crate::import::{Row, Status};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::str::FromStr;
+
+/// Specifying the names of the different output formats.
+///
+pub const FORMATS: &[&str] = &["table", "json", "csv", "tsv"];
+
+/// Specifying the different output formats.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(anyhow!("Unknown output format '{}'", s)),
+        }
+    }
+}
+
+/// Defines a resource which can be written as a row of fields. Field names
+/// are the serialized names of the resource, with nested resources
+/// flattened using dots, so every format shares the same schema.
+///
+pub trait Record: Serialize {
+    /// Return a record with every optional resource present, from which
+    /// the field names are found whatever the records hold.
+    ///
+    fn sample() -> Self;
+}
+
+impl Record for Project {
+    fn sample() -> Self {
+        Project {
+            gid: String::new(),
+            name: String::new(),
+            permalink_url: None,
+            team: Some(Team {
+                gid: String::new(),
+                name: String::new(),
+            }),
+        }
+    }
+}
+
+impl Record for Task {
+    fn sample() -> Self {
+        Task {
+            gid: String::new(),
+            name: String::new(),
+            completed: false,
+            start_on: None,
+            due_on: None,
+            modified_at: None,
+            assignee: Some(User {
+                gid: String::new(),
+                name: String::new(),
+                email: String::new(),
+            }),
+            memberships: vec![],
+            assignee_section: Some(Section {
+                gid: String::new(),
+                name: String::new(),
+            }),
+            permalink_url: None,
+            dependencies: vec![],
+        }
+    }
+}
+
+impl Record for Row {
+    fn sample() -> Self {
+        Row {
+            line: 0,
+            name: String::new(),
+            completed: false,
+            assignee: None,
+            due_on: None,
+            section: None,
+            notes: None,
+            status: Status::Invalid(String::new()),
+        }
+    }
+}
+
+/// Return the records written in the given format.
+///
+pub fn render<T: Record>(records: &[T], format: Format) -> Result<String> {
+    let output = match format {
+        Format::Json => format!("{}\n", serde_json::to_string_pretty(records)?),
+        Format::Csv => delimited(records, ",", csv_escape)?,
+        Format::Tsv => delimited(records, "\t", tsv_escape)?,
+        Format::Table => table(records)?,
+    };
+    Ok(output)
+}
+
+/// Return a header line followed by a line per record, with values escaped
+/// and joined by the delimiter.
+///
+fn delimited<T: Record>(
+    records: &[T],
+    delimiter: &str,
+    escape: fn(&str) -> String,
+) -> Result<String> {
+    let (fields, rows) = rows(records)?;
+    let mut lines = vec![fields.join(delimiter)];
+    for row in rows.iter() {
+        let values: Vec<String> = row.iter().map(|v| escape(v)).collect();
+        lines.push(values.join(delimiter));
+    }
+    Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+/// Return the field names of the record type, in the order they are
+/// serialized, along with the values of each record's fields.
+///
+fn rows<T: Record>(records: &[T]) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+    let mut names = vec![];
+    flatten("", &serde_json::to_value(T::sample())?, &mut names);
+    let names: Vec<String> = names.into_iter().map(|(name, _)| name).collect();
+    let mut rows = vec![];
+    for record in records.iter() {
+        let mut fields = vec![];
+        flatten("", &serde_json::to_value(record)?, &mut fields);
+        rows.push(
+            names
+                .iter()
+                .map(|name| {
+                    fields
+                        .iter()
+                        .find(|(n, _)| n == name)
+                        .map(|(_, value)| value.to_owned())
+                        .unwrap_or_default()
+                })
+                .collect(),
+        );
+    }
+    Ok((names, rows))
+}
+
+/// Add the fields of the serialized value to the list, naming those of
+/// nested resources with dots and writing lists as compact JSON.
+///
+fn flatten(name: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+    let text = match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter() {
+                let key = match name.is_empty() {
+                    true => key.to_owned(),
+                    false => format!("{}.{}", name, key),
+                };
+                flatten(&key, value, fields);
+            }
+            return;
+        }
+        Value::Null => String::new(),
+        Value::String(text) => text.to_owned(),
+        Value::Array(values) if values.is_empty() => String::new(),
+        value => value.to_string(),
+    };
+    fields.push((name.to_owned(), text));
+}
+
+/// Return the value quoted if it contains a comma, quote or line break.
+///
+pub fn csv_escape(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_owned(),
+    }
+}
+
+/// Return the value with tabs and line breaks replaced by spaces.
+///
+fn tsv_escape(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// Return the records aligned in columns beneath an uppercase header.
+///
+fn table<T: Record>(records: &[T]) -> Result<String> {
+    let (fields, values) = rows(records)?;
+    let header: Vec<String> = fields.iter().map(|f| f.to_uppercase()).collect();
+    let mut rows = vec![header];
+    for row in values.iter() {
+        rows.push(row.iter().map(|v| tsv_escape(v)).collect());
+    }
+    let widths: Vec<usize> = (0..fields.len())
+        .map(|i| rows.iter().map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(value, width)| format!("{:width$}", value, width = width))
+                .collect();
+            format!("{}\n", cells.join("  ").trim_end())
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asana::{Membership, RelatedTask};
+
+    fn tasks() -> Vec<Task> {
+        vec![
+            Task {
+                gid: String::from("1201"),
+                name: String::from("Draft \"Q1\" plan, v2"),
+                completed: false,
+                start_on: Some(String::from("2022-01-24")),
+                due_on: Some(String::from("2022-01-31")),
+                modified_at: Some(String::from("2022-01-05T12:00:00.000Z")),
+                assignee: Some(User {
+                    gid: String::from("42"),
+                    name: String::from("Alice Example"),
+                    email: String::from("alice@example.com"),
+                }),
+                memberships: vec![Membership {
+                    project_gid: String::from("1100"),
+                    section: Section {
+                        gid: String::from("1110"),
+                        name: String::from("To do"),
+                    },
+                }],
+                assignee_section: Some(Section {
+                    gid: String::from("1001"),
+                    name: String::from("Today"),
+                }),
+                permalink_url: Some(String::from("https://app.asana.com/0/0/1201")),
+                dependencies: vec![RelatedTask {
+                    gid: String::from("1200"),
+                    name: String::from("Kickoff"),
+                    completed: true,
+                }],
+            },
+            Task {
+                gid: String::from("1202"),
+                name: String::from("Review\tcontract"),
+                completed: true,
+                start_on: None,
+                due_on: None,
+                modified_at: None,
+                assignee: None,
+                memberships: vec![],
+                assignee_section: None,
+                permalink_url: None,
+                dependencies: vec![],
+            },
+        ]
+    }
+
+    #[test]
+    fn render_json() -> Result<()> {
+        let output = render(&tasks(), Format::Json)?;
+        assert_eq!(output, include_str!("golden/tasks.json"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_csv() -> Result<()> {
+        let output = render(&tasks(), Format::Csv)?;
+        assert_eq!(output, include_str!("golden/tasks.csv"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_tsv() -> Result<()> {
+        let output = render(&tasks(), Format::Tsv)?;
+        assert_eq!(output, include_str!("golden/tasks.tsv"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_table() -> Result<()> {
+        let output = render(&tasks(), Format::Table)?;
+        assert_eq!(output, include_str!("golden/tasks.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_fields_without_values() -> Result<()> {
+        let unassigned: Vec<Task> = tasks().into_iter().skip(1).collect();
+        let output = render(&unassigned, Format::Csv)?;
+        let header = include_str!("golden/tasks.csv").lines().next();
+        assert_eq!(output.lines().next(), header);
+        Ok(())
+    }
+
+    #[test]
+    fn render_row_status() -> Result<()> {
+        let mut row = Row::sample();
+        row.line = 2;
+        row.name = String::from("Plan");
+        row.status = Status::Created(String::from("1201"));
+        let output = render(&[row], Format::Csv)?;
+        assert_eq!(
+            output,
+            "line,name,completed,assignee,due_on,section,notes,status,message\n\
+             2,Plan,false,,,,,created,1201\n"
+        );
+        Ok(())
+    }
+}
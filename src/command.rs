@@ -0,0 +1,158 @@
+use anyhow::{anyhow, Error, Result};
+use std::str::FromStr;
+
+/// Specifying the names of the commands, in the order they are completed.
+///
+pub const COMMANDS: [&str; 8] = [
+    "assign",
+    "due",
+    "export",
+    "project",
+    "refresh",
+    "tag",
+    "workspace",
+    "ws",
+];
+
+/// Specifying the different commands entered after `:`.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Project(String),
+    Assign(String),
+    Due(String),
+    Tag(String),
+    Workspace(String),
+    Export(String),
+    Refresh,
+}
+
+impl FromStr for Command {
+    type Err = Error;
+
+    /// Parse a command such as `project Foo` or `due fri`, accepting the
+    /// leading `:` and unambiguous prefixes of command names.
+    ///
+    fn from_str(input: &str) -> Result<Command> {
+        let input = input.trim().trim_start_matches(':');
+        let (name, argument) = match input.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim().to_owned()),
+            None => (input, String::new()),
+        };
+        if name.is_empty() {
+            return Err(anyhow!("Missing command"));
+        }
+        let name = resolve(name)?;
+        let requires_argument = |command: fn(String) -> Command| match argument.is_empty() {
+            true => Err(anyhow!("Command '{}' requires an argument", name)),
+            false => Ok(command(argument.to_owned())),
+        };
+        match name {
+            "project" => requires_argument(Command::Project),
+            "assign" => Ok(Command::Assign(argument.to_owned())),
+            "due" => Ok(Command::Due(argument.to_owned())),
+            "tag" => requires_argument(Command::Tag),
+            "workspace" | "ws" => requires_argument(Command::Workspace),
+            "export" => requires_argument(Command::Export),
+            _ => Ok(Command::Refresh),
+        }
+    }
+}
+
+/// Return the command name equal to the given name or else the only one
+/// starting with it.
+///
+pub fn resolve(name: &str) -> Result<&'static str> {
+    if let Some(command) = COMMANDS.iter().find(|c| **c == name) {
+        return Ok(command);
+    }
+    let matches: Vec<&'static str> = COMMANDS
+        .iter()
+        .filter(|c| c.starts_with(name))
+        .copied()
+        .collect();
+    match matches.as_slice() {
+        [command] => Ok(command),
+        [] => Err(anyhow!("Unknown command '{}'", name)),
+        _ => Err(anyhow!(
+            "Ambiguous command '{}' ({})",
+            name,
+            matches.join(", ")
+        )),
+    }
+}
+
+/// Return the completion of the given prefix against the candidates, being
+/// the candidate itself if only one matches or otherwise the longest prefix
+/// shared by all matches. Matching ignores case.
+///
+pub fn complete(prefix: &str, candidates: &[String]) -> Option<String> {
+    let prefix = prefix.to_lowercase();
+    let matches: Vec<&String> = candidates
+        .iter()
+        .filter(|c| c.to_lowercase().starts_with(&prefix))
+        .collect();
+    let first = matches.first()?;
+    let shared = matches
+        .iter()
+        .skip(1)
+        .fold(first.chars().count(), |len, c| {
+            first
+                .chars()
+                .zip(c.chars())
+                .take(len)
+                .take_while(|(a, b)| a.eq_ignore_ascii_case(b))
+                .count()
+        });
+    Some(first.chars().take(shared).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            ":project Foo Bar".parse::<Command>().unwrap(),
+            Command::Project(String::from("Foo Bar"))
+        );
+        assert_eq!(
+            "assign @alice".parse::<Command>().unwrap(),
+            Command::Assign(String::from("@alice"))
+        );
+        assert_eq!(
+            "ws Acme".parse::<Command>().unwrap(),
+            Command::Workspace(String::from("Acme"))
+        );
+        assert_eq!("ref".parse::<Command>().unwrap(), Command::Refresh);
+        assert_eq!(
+            "due".parse::<Command>().unwrap(),
+            Command::Due(String::new())
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(":".parse::<Command>().is_err());
+        assert!("frobnicate".parse::<Command>().is_err());
+        assert!("project".parse::<Command>().is_err());
+        assert!("w Acme".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn complete_candidates() {
+        let candidates = vec![
+            String::from("Marketing"),
+            String::from("Mobile App"),
+            String::from("Mobile Web"),
+        ];
+        assert_eq!(
+            complete("mar", &candidates),
+            Some(String::from("Marketing"))
+        );
+        assert_eq!(complete("mo", &candidates), Some(String::from("Mobile ")));
+        assert_eq!(complete("", &candidates), Some(String::from("M")));
+        assert_eq!(complete("x", &candidates), None);
+    }
+}
@@ -1,13 +1,20 @@
+use crate::keymap::{Action, Binding, Keymap};
+use crate::notify::Notifications;
+use crate::ui::theme::{ColorSupport, Theme, ThemeColors};
+use crate::ui::Panels;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fs,
     io::{self, stdin, Write},
     path::{Path, PathBuf},
 };
 
 const FILE_NAME: &str = "config.yml";
+const PANELS_FILE_NAME: &str = "panels.yml";
 const DEFAULT_DIRECTORY_PATH: &str = ".config/asana-tui";
+const DEFAULT_REFRESH_INTERVAL_IN_SECS: u64 = 300;
 const AUTHORIZATION_INSTRUCTIONS: &[&str] = &[
     "Visit the Asana Developer App Console at `https://app.asana.com/`",
     "Log in with the account you want to authorize",
@@ -19,6 +26,14 @@ const AUTHORIZATION_INSTRUCTIONS: &[&str] = &[
 ///
 pub struct Config {
     pub access_token: Option<String>,
+    pub refresh_interval_in_secs: u64,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub color_support: ColorSupport,
+    pub panels: Panels,
+    pub notifications: Notifications,
+    pub my_teams_only: bool,
+    pub export_directory: PathBuf,
     file_path: Option<PathBuf>,
 }
 
@@ -27,6 +42,24 @@ pub struct Config {
 #[derive(Serialize, Deserialize)]
 struct FileSpec {
     pub access_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys: Option<HashMap<Action, Binding>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme_colors: Option<ThemeColors>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_support: Option<ColorSupport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panels: Option<Panels>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notifications: Option<Notifications>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub my_teams_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_directory: Option<PathBuf>,
 }
 
 impl Config {
@@ -36,6 +69,14 @@ impl Config {
         Config {
             file_path: None,
             access_token: None,
+            refresh_interval_in_secs: DEFAULT_REFRESH_INTERVAL_IN_SECS,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            color_support: ColorSupport::detect(),
+            panels: Panels::default(),
+            notifications: Notifications::default(),
+            my_teams_only: false,
+            export_directory: Config::default_export_directory(),
         }
     }
 
@@ -62,9 +103,40 @@ impl Config {
 
         // If file exists, try to extract token
         if file_path.exists() {
-            let contents = fs::read_to_string(&file_path)?;
+            let contents = fs::read_to_string(file_path)?;
             let data: FileSpec = serde_yaml::from_str(&contents)?;
             self.access_token = Some(data.access_token);
+            if let Some(refresh_interval) = data.refresh_interval {
+                self.refresh_interval_in_secs = refresh_interval;
+            }
+            if let Some(keys) = data.keys {
+                self.keymap = Keymap::with_overrides(&keys)?;
+            }
+            if let Some(theme) = data.theme {
+                self.theme = Theme::named(&theme)?;
+            }
+            if let Some(theme_colors) = data.theme_colors {
+                self.theme = self.theme.clone().with_colors(&theme_colors)?;
+            }
+            if let Some(color_support) = data.color_support {
+                self.color_support = color_support;
+            }
+            if let Some(panels) = data.panels {
+                self.panels = panels;
+            }
+            if let Some(notifications) = data.notifications {
+                self.notifications = notifications;
+            }
+            if let Some(my_teams_only) = data.my_teams_only {
+                self.my_teams_only = my_teams_only;
+            }
+            if let Some(export_directory) = data.export_directory {
+                self.export_directory = export_directory;
+            }
+            let panels_path = dir_path.join(Path::new(PANELS_FILE_NAME));
+            if panels_path.exists() {
+                self.panels = serde_yaml::from_str(&fs::read_to_string(panels_path)?)?;
+            }
         }
         // Otherwise authorize with user and create file
         else {
@@ -75,12 +147,42 @@ impl Config {
         Ok(())
     }
 
+    /// Return the path to the directory containing the configuration file.
+    ///
+    pub fn directory(&self) -> Option<&Path> {
+        self.file_path.as_ref().and_then(|path| path.parent())
+    }
+
+    /// Write the panel sizes to their own file in the configuration
+    /// directory, leaving the configuration file as the user wrote it.
+    ///
+    pub fn save_panels(&self, panels: &Panels) -> Result<()> {
+        let dir_path = match self.directory() {
+            Some(dir_path) => dir_path,
+            None => return Ok(()),
+        };
+        fs::write(
+            dir_path.join(Path::new(PANELS_FILE_NAME)),
+            serde_yaml::to_string(panels)?,
+        )?;
+        Ok(())
+    }
+
     /// Attempt to serialize the configuration data and write it to the disk,
     /// returning any unrecoverable errors.
     ///
     fn create_file(&self) -> Result<()> {
         let data = FileSpec {
             access_token: self.access_token.clone().unwrap(),
+            refresh_interval: None,
+            keys: None,
+            theme: None,
+            theme_colors: None,
+            color_support: None,
+            panels: None,
+            notifications: None,
+            my_teams_only: None,
+            export_directory: None,
         };
         let content = serde_yaml::to_string(&data)?;
         let file_path = self.file_path.as_ref().unwrap();
@@ -123,4 +225,13 @@ impl Config {
             None => Err(anyhow!("Failed to find $HOME directory")),
         }
     }
+
+    /// Returns the directory projects are exported to by default, being the
+    /// user's downloads directory if any and otherwise their home directory.
+    ///
+    fn default_export_directory() -> PathBuf {
+        dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
 }
@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Parse a due date relative to today. Accepts ISO dates (`2022-01-31`),
+/// `today`, `tomorrow`, weekday names (`fri`, `friday`) for their next
+/// occurrence including today, and offsets such as `+3d` or `+2w`.
+///
+pub fn parse(input: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    match input.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" | "tom" => return Ok(today + Duration::days(1)),
+        _ => (),
+    }
+    if let Ok(weekday) = input.parse::<Weekday>() {
+        let days_ahead =
+            (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        return Ok(today + Duration::days(days_ahead as i64));
+    }
+    if let Some(offset) = input.strip_prefix('+') {
+        let (count, unit) = offset.split_at(offset.len().saturating_sub(1));
+        if let Ok(count) = count.parse::<i64>() {
+            match unit {
+                "d" => return Ok(today + Duration::days(count)),
+                "w" => return Ok(today + Duration::weeks(count)),
+                _ => (),
+            }
+        }
+    }
+    Err(anyhow!("Failed to parse date '{}'", input))
+}
+
+/// Return the date the given number of months later, or earlier if
+/// negative, keeping the day within the length of the resulting month.
+///
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let index = date.year() * 12 + date.month0() as i32 + months;
+    let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+    (1..=date.day())
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .unwrap_or(date)
+}
+
+/// Return the Monday starting the week of the given date.
+///
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // A Wednesday
+        NaiveDate::from_ymd(2022, 1, 5)
+    }
+
+    #[test]
+    fn parse_iso() {
+        assert_eq!(
+            parse("2022-02-01", today()).unwrap(),
+            NaiveDate::from_ymd(2022, 2, 1)
+        );
+    }
+
+    #[test]
+    fn parse_relative_words() {
+        assert_eq!(parse("today", today()).unwrap(), today());
+        assert_eq!(
+            parse("Tomorrow", today()).unwrap(),
+            NaiveDate::from_ymd(2022, 1, 6)
+        );
+    }
+
+    #[test]
+    fn parse_weekday() {
+        assert_eq!(
+            parse("fri", today()).unwrap(),
+            NaiveDate::from_ymd(2022, 1, 7)
+        );
+        assert_eq!(parse("wednesday", today()).unwrap(), today());
+        assert_eq!(
+            parse("mon", today()).unwrap(),
+            NaiveDate::from_ymd(2022, 1, 10)
+        );
+    }
+
+    #[test]
+    fn parse_offset() {
+        assert_eq!(
+            parse("+3d", today()).unwrap(),
+            NaiveDate::from_ymd(2022, 1, 8)
+        );
+        assert_eq!(
+            parse("+2w", today()).unwrap(),
+            NaiveDate::from_ymd(2022, 1, 19)
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse("someday", today()).is_err());
+        assert!(parse("+d", today()).is_err());
+    }
+
+    #[test]
+    fn add_months_within_bounds() {
+        assert_eq!(
+            add_months(NaiveDate::from_ymd(2022, 1, 31), 1),
+            NaiveDate::from_ymd(2022, 2, 28)
+        );
+        assert_eq!(
+            add_months(NaiveDate::from_ymd(2022, 1, 5), -1),
+            NaiveDate::from_ymd(2021, 12, 5)
+        );
+        assert_eq!(
+            add_months(NaiveDate::from_ymd(2022, 11, 30), 14),
+            NaiveDate::from_ymd(2024, 1, 30)
+        );
+    }
+
+    #[test]
+    fn week_start_monday() {
+        assert_eq!(week_start(today()), NaiveDate::from_ymd(2022, 1, 3));
+        assert_eq!(
+            week_start(NaiveDate::from_ymd(2022, 1, 3)),
+            NaiveDate::from_ymd(2022, 1, 3)
+        );
+    }
+}
@@ -1,17 +1,45 @@
-use crate::asana::Asana;
-use crate::state::State;
-use anyhow::Result;
+use crate::asana::{is_network_error, Asana, InboxKind, Mutation, SyncExpired, TaskEvent};
+use crate::export::{self, Format as ExportFormat};
+use crate::import::{self, Batch, Status as ImportStatus};
+use crate::notify::{self, Kind as NotificationKind, Notification};
+use crate::state::{State, View};
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Local, Utc};
 use log::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
+
+/// Specify interval in seconds between network ticks while idle.
+///
+pub const TICK_RATE_IN_SECS: u64 = 10;
+
+/// Specify how many mutations of a bulk action are sent to Asana at once.
+///
+const BULK_CONCURRENCY: usize = 4;
+
+/// Specify how many days of stories are shown in the inbox.
+///
+const INBOX_DAYS: i64 = 7;
 
 /// Specify different network event types.
 ///
 #[derive(Debug)]
 pub enum Event {
     Me,
+    Workspace,
     ProjectTasks,
     MyTasks,
+    Inbox,
+    TaskDetail(String),
+    Mutate(Mutation),
+    BulkMutate(Vec<Mutation>),
+    ReplayJournal,
+    Export(ExportFormat),
+    PreviewImport(PathBuf),
+    Import,
+    Tick,
 }
 
 /// Specify struct for managing state with network events.
@@ -19,13 +47,18 @@ pub enum Event {
 pub struct Handler<'a> {
     state: &'a Arc<Mutex<State>>,
     asana: &'a mut Asana,
+    sync_tokens: HashMap<String, String>,
 }
 
 impl<'a> Handler<'a> {
     /// Return new instance with reference to state.
     ///
     pub fn new(state: &'a Arc<Mutex<State>>, asana: &'a mut Asana) -> Self {
-        Handler { state, asana }
+        Handler {
+            state,
+            asana,
+            sync_tokens: HashMap::new(),
+        }
     }
 
     /// Handle network events by type.
@@ -34,8 +67,337 @@ impl<'a> Handler<'a> {
         debug!("Processing network event '{:?}'...", event);
         match event {
             Event::Me => self.me().await?,
+            Event::Workspace => self.workspace().await?,
             Event::ProjectTasks => self.project_tasks().await?,
             Event::MyTasks => self.my_tasks().await?,
+            Event::Inbox => self.inbox().await?,
+            Event::TaskDetail(task_gid) => self.task_detail(&task_gid).await?,
+            Event::Mutate(mutation) => self.mutate(mutation).await?,
+            Event::BulkMutate(mutations) => self.bulk_mutate(mutations).await?,
+            Event::ReplayJournal => self.replay_journal().await?,
+            Event::Export(format) => self.export(format).await?,
+            Event::PreviewImport(file_path) => self.preview_import(file_path).await?,
+            Event::Import => self.import().await?,
+            Event::Tick => self.tick().await?,
+        }
+        Ok(())
+    }
+
+    /// Perform periodic work while no other events are queued.
+    ///
+    async fn tick(&mut self) -> Result<()> {
+        let has_pending = self.state.lock().await.get_journal().has_pending();
+        if has_pending {
+            self.replay_journal().await?;
+        }
+        {
+            let mut state = self.state.lock().await;
+            if state.is_refresh_due() {
+                debug!("Refreshing current view...");
+                state.refresh_current_view();
+                return Ok(());
+            }
+        }
+        self.sync_project_tasks().await?;
+        self.check_notifications().await?;
+        Ok(())
+    }
+
+    /// Look for tasks due soon, new assignments and mentions once the check
+    /// interval has elapsed, raising notifications for those not seen yet.
+    ///
+    async fn check_notifications(&mut self) -> Result<()> {
+        let (since, settings, user_gid, workspace_gid) = {
+            let mut state = self.state.lock().await;
+            let since = match state.start_notification_check() {
+                Some(since) => since,
+                None => return Ok(()),
+            };
+            (
+                since,
+                state.get_notifications().clone(),
+                state.get_user().unwrap().gid.to_owned(),
+                state.get_active_workspace().unwrap().gid.to_owned(),
+            )
+        };
+        debug!("Checking for notifications...");
+        let mut notifications = vec![];
+        if settings.assignments || settings.mentions {
+            let (tasks, items) = self.asana.inbox(&user_gid, &workspace_gid, since).await?;
+            for item in items.iter().rev() {
+                let kind = match item.kind {
+                    InboxKind::Assignment => NotificationKind::Assignment,
+                    InboxKind::Mention => NotificationKind::Mention,
+                    _ => continue,
+                };
+                let task_name = match tasks.iter().find(|t| t.gid == item.task_gid) {
+                    Some(task) => task.name.as_str(),
+                    None => item.task_gid.as_str(),
+                };
+                let author = match &item.author {
+                    Some(author) => author.name.as_str(),
+                    None => "Someone",
+                };
+                let message = match kind {
+                    NotificationKind::Mention => {
+                        format!("{} mentioned you on '{}'", author, task_name)
+                    }
+                    _ => format!("{} assigned you '{}'", author, task_name),
+                };
+                notifications.push(Notification {
+                    kind,
+                    key: item.story_gid.to_owned(),
+                    message,
+                });
+            }
+        }
+        if settings.due_soon {
+            let today = Local::today().naive_local();
+            let tasks = self.asana.assigned_tasks(&user_gid, &workspace_gid).await?;
+            for task in tasks {
+                let due_on = match &task.due_on {
+                    Some(due_on) if notify::is_due_soon(due_on, today) => due_on,
+                    _ => continue,
+                };
+                notifications.push(Notification {
+                    kind: NotificationKind::DueSoon,
+                    key: format!("{}:{}", task.gid, due_on),
+                    message: format!("'{}' is due {}", task.name, due_on),
+                });
+            }
+        }
+        let raised = self.state.lock().await.notify(notifications);
+        for notification in raised.iter() {
+            info!("{}", notification.message);
+        }
+        if let Some(message) = notify::summarize(&raised) {
+            if !settings.command.is_empty() {
+                if let Err(err) = notify::run_command(&settings.command, &message) {
+                    error!("Failed to run notification command: {}", err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the mutation, queueing it in the journal if Asana is unreachable
+    /// or earlier mutations are still awaiting replay.
+    ///
+    async fn mutate(&mut self, mutation: Mutation) -> Result<()> {
+        let has_pending = self.state.lock().await.get_journal().has_pending();
+        if !has_pending {
+            info!("Saving change '{}'...", mutation.describe());
+            match self.asana.mutate(&mutation).await {
+                Ok(task) => {
+                    let detail_gid = {
+                        let mut state = self.state.lock().await;
+                        state
+                            .replace_task(mutation.task_gid(), task)
+                            .set_offline(false);
+                        match &mutation {
+                            Mutation::Comment { task_gid, .. } => Some(task_gid.to_owned()),
+                            Mutation::AddDependency { dependency_gid, .. }
+                            | Mutation::RemoveDependency { dependency_gid, .. } => {
+                                Some(dependency_gid.to_owned())
+                            }
+                            _ => None,
+                        }
+                        .filter(|gid| state.shows_task_detail(gid))
+                    };
+                    info!("Saved change '{}'.", mutation.describe());
+                    if let Some(task_gid) = detail_gid {
+                        self.task_detail(&task_gid).await?;
+                    }
+                    return Ok(());
+                }
+                Err(err) if is_network_error(&err) => {
+                    warn!("Failed to reach Asana: {}", err);
+                }
+                Err(err) => {
+                    self.restore_task(mutation.task_gid()).await;
+                    return Err(err);
+                }
+            }
+        }
+        self.queue(mutation).await
+    }
+
+    /// Apply the mutations concurrently, reporting progress and the result
+    /// for each task. Mutations which cannot reach Asana are queued in the
+    /// journal.
+    ///
+    async fn bulk_mutate(&mut self, mutations: Vec<Mutation>) -> Result<()> {
+        let description = match mutations.first() {
+            Some(mutation) => mutation.describe(),
+            None => return Ok(()),
+        };
+        let has_pending = self.state.lock().await.get_journal().has_pending();
+        if has_pending {
+            for mutation in mutations {
+                self.queue(mutation).await?;
+            }
+            return Ok(());
+        }
+
+        info!(
+            "Saving change '{}' to {} tasks...",
+            description,
+            mutations.len()
+        );
+        let task_names: Vec<String> = {
+            let mut state = self.state.lock().await;
+            state.set_bulk_progress(Some(mutations.len()));
+            mutations
+                .iter()
+                .map(|m| match state.get_task(m.task_gid()) {
+                    Some(task) => task.name.to_owned(),
+                    None => m.task_gid().to_owned(),
+                })
+                .collect()
+        };
+        let semaphore = Arc::new(Semaphore::new(BULK_CONCURRENCY));
+        let handles: Vec<_> = mutations
+            .into_iter()
+            .map(|mutation| {
+                let mut asana = self.asana.clone();
+                let semaphore = Arc::clone(&semaphore);
+                let state = Arc::clone(self.state);
+                tokio::spawn(async move {
+                    let _permit = semaphore.acquire().await;
+                    let result = asana.mutate(&mutation).await;
+                    state.lock().await.advance_bulk_progress();
+                    (mutation, result)
+                })
+            })
+            .collect();
+
+        let (mut saved, mut queued, mut failed) = (0, 0, 0);
+        for (handle, task_name) in handles.into_iter().zip(task_names) {
+            let (mutation, result) = handle.await?;
+            match result {
+                Ok(task) => {
+                    info!("Saved change '{}' to '{}'.", description, task_name);
+                    let mut state = self.state.lock().await;
+                    state
+                        .replace_task(mutation.task_gid(), task)
+                        .set_offline(false);
+                    saved += 1;
+                }
+                Err(err) if is_network_error(&err) => {
+                    warn!("Failed to reach Asana for '{}': {}", task_name, err);
+                    self.queue(mutation).await?;
+                    queued += 1;
+                }
+                Err(err) => {
+                    error!(
+                        "Failed to save change '{}' to '{}': {}",
+                        description, task_name, err
+                    );
+                    self.restore_task(mutation.task_gid()).await;
+                    failed += 1;
+                }
+            }
+        }
+        self.state.lock().await.set_bulk_progress(None);
+        info!(
+            "Saved change '{}' to {} tasks, {} queued, {} failed.",
+            description, saved, queued, failed
+        );
+        Ok(())
+    }
+
+    /// Queue the mutation in the journal for replay once Asana is reachable.
+    ///
+    async fn queue(&mut self, mutation: Mutation) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let modified_at = state
+            .get_task(mutation.task_gid())
+            .and_then(|t| t.modified_at.to_owned());
+        info!("Queueing change '{}' for replay.", mutation.describe());
+        state.set_offline(true);
+        state.get_journal_mut().push(mutation, modified_at)?;
+        Ok(())
+    }
+
+    /// Replace the local copy of the task with the server's after a failed
+    /// mutation.
+    ///
+    async fn restore_task(&mut self, task_gid: &str) {
+        if let Ok(task) = self.asana.task(task_gid).await {
+            let mut state = self.state.lock().await;
+            match state.get_task(task_gid) {
+                Some(_) => state.update_task(task),
+                None => state.add_task(task),
+            };
+        }
+    }
+
+    /// Replay pending journal entries in order, failing those which conflict
+    /// with changes made on the server since they were queued. Stops at the
+    /// first entry which cannot reach Asana.
+    ///
+    async fn replay_journal(&mut self) -> Result<()> {
+        loop {
+            let entry = match self.state.lock().await.get_journal().next_pending() {
+                Some(entry) => entry.to_owned(),
+                None => break,
+            };
+            let task_gid = entry.mutation.task_gid();
+            info!("Replaying change '{}'...", entry.mutation.describe());
+
+            if let Some(modified_at) = &entry.modified_at {
+                match self.asana.task(task_gid).await {
+                    Ok(task) if task.modified_at.as_ref() != Some(modified_at) => {
+                        warn!(
+                            "Skipping conflicting change '{}'.",
+                            entry.mutation.describe()
+                        );
+                        let reason = format!(
+                            "Conflict: task modified on server at {}",
+                            task.modified_at.unwrap_or_default()
+                        );
+                        let mut state = self.state.lock().await;
+                        state
+                            .set_offline(false)
+                            .get_journal_mut()
+                            .fail(entry.id, reason)?;
+                        continue;
+                    }
+                    Ok(_) => (),
+                    Err(err) if is_network_error(&err) => {
+                        debug!("Asana is still unreachable: {}", err);
+                        self.state.lock().await.set_offline(true);
+                        return Ok(());
+                    }
+                    Err(err) => {
+                        let mut state = self.state.lock().await;
+                        state.get_journal_mut().fail(entry.id, err.to_string())?;
+                        continue;
+                    }
+                }
+            }
+
+            match self.asana.mutate(&entry.mutation).await {
+                Ok(task) => {
+                    info!("Replayed change '{}'.", entry.mutation.describe());
+                    let mut state = self.state.lock().await;
+                    let modified_at = task.as_ref().and_then(|t| t.modified_at.to_owned());
+                    state.set_offline(false).replace_task(task_gid, task);
+                    let journal = state.get_journal_mut();
+                    journal.remove(entry.id)?;
+                    journal.rebase(task_gid, modified_at)?;
+                }
+                Err(err) if is_network_error(&err) => {
+                    debug!("Asana is still unreachable: {}", err);
+                    self.state.lock().await.set_offline(true);
+                    return Ok(());
+                }
+                Err(err) => {
+                    error!("Failed to replay change '{}'.", entry.mutation.describe());
+                    let mut state = self.state.lock().await;
+                    state.get_journal_mut().fail(entry.id, err.to_string())?;
+                }
+            }
         }
         Ok(())
     }
@@ -55,15 +417,53 @@ impl<'a> Handler<'a> {
             }
         }
         if !workspaces.is_empty() {
-            info!("Fetching projects for active workspace...");
-            let projects = self.asana.projects(&workspaces[0].gid).await?;
-            let mut state = self.state.lock().await;
-            state.set_projects(projects);
+            self.workspace().await?;
         }
         info!("Loaded initial application data.");
         Ok(())
     }
 
+    /// Update state with teams, projects and tags for the active workspace.
+    ///
+    async fn workspace(&mut self) -> Result<()> {
+        let workspace;
+        let user_gid;
+        let my_teams_only;
+        {
+            let state = self.state.lock().await;
+            workspace = match state.get_active_workspace() {
+                Some(workspace) => workspace.to_owned(),
+                None => {
+                    warn!("Skipping workspace request for unset workspace.");
+                    return Ok(());
+                }
+            };
+            user_gid = state.get_user().map(|u| u.gid.to_owned());
+            my_teams_only = state.is_my_teams_only();
+        }
+        let workspace_gid = workspace.gid;
+        if workspace.is_organization {
+            info!("Fetching teams for active workspace...");
+            let teams = match (my_teams_only, user_gid) {
+                (true, Some(user_gid)) => self.asana.user_teams(&user_gid, &workspace_gid).await,
+                _ => self.asana.teams(&workspace_gid).await,
+            };
+            match teams {
+                Ok(teams) => {
+                    self.state.lock().await.set_teams(teams);
+                }
+                Err(err) => warn!("Skipping teams for active workspace: {}", err),
+            }
+        }
+        info!("Fetching projects for active workspace...");
+        let projects = self.asana.projects(&workspace_gid).await?;
+        self.state.lock().await.set_projects(projects);
+        info!("Fetching tags for active workspace...");
+        let tags = self.asana.tags(&workspace_gid).await?;
+        self.state.lock().await.set_tags(tags);
+        Ok(())
+    }
+
     /// Update state with tasks for project.
     ///
     async fn project_tasks(&mut self) -> Result<()> {
@@ -77,14 +477,17 @@ impl<'a> Handler<'a> {
             project = state.get_project().unwrap().to_owned();
         }
         info!("Fetching tasks for project '{}'...", &project.name);
+        self.reset_sync_token(&project.gid).await;
         let tasks = self.asana.tasks(&project.gid).await?;
+        let sections = self.asana.sections(&project.gid).await?;
         info!("Received tasks for project '{}'.", &project.name);
         let mut state = self.state.lock().await;
-        state.set_tasks(tasks);
+        state.set_tasks(tasks).set_sections(sections);
         Ok(())
     }
 
-    /// Update state with tasks assigned to the user.
+    /// Update state with the user's task list, its sections and the tasks
+    /// in it.
     ///
     async fn my_tasks(&mut self) -> Result<()> {
         info!("Fetching incomplete tasks assigned to user...");
@@ -95,10 +498,272 @@ impl<'a> Handler<'a> {
             user_gid = state.get_user().unwrap().gid.to_owned();
             workspace_gid = state.get_active_workspace().unwrap().gid.to_owned();
         }
-        let my_tasks = self.asana.my_tasks(&user_gid, &workspace_gid).await?;
+        let user_task_list = self.asana.user_task_list(&user_gid, &workspace_gid).await?;
+        let sections = self.asana.sections(&user_task_list.gid).await?;
+        let my_tasks = self.asana.my_tasks(&user_task_list.gid).await?;
         let mut state = self.state.lock().await;
-        state.set_tasks(my_tasks);
+        state.set_user_task_list(user_task_list, sections, my_tasks);
         info!("Received incomplete tasks assigned to user.");
         Ok(())
     }
+
+    /// Fetch the recent stories concerning the user for the inbox.
+    ///
+    async fn inbox(&mut self) -> Result<()> {
+        info!("Fetching inbox...");
+        let user_gid;
+        let workspace_gid;
+        {
+            let state = self.state.lock().await;
+            user_gid = state.get_user().unwrap().gid.to_owned();
+            workspace_gid = state.get_active_workspace().unwrap().gid.to_owned();
+        }
+        let since = Utc::now() - Duration::days(INBOX_DAYS);
+        let (tasks, items) = self.asana.inbox(&user_gid, &workspace_gid, since).await?;
+        let mut state = self.state.lock().await;
+        state.set_inbox(tasks, items);
+        info!("Received inbox.");
+        Ok(())
+    }
+
+    /// Fetch the notes and comments of the task shown in the detail view.
+    ///
+    async fn task_detail(&mut self, task_gid: &str) -> Result<()> {
+        info!("Fetching notes and comments of task...");
+        let task_detail = self.asana.task_detail(task_gid).await?;
+        let mut state = self.state.lock().await;
+        state.set_task_detail(task_detail);
+        info!("Received notes and comments of task.");
+        Ok(())
+    }
+
+    /// Write the current project's tasks grouped by section to a file in the
+    /// export directory.
+    ///
+    async fn export(&mut self, format: ExportFormat) -> Result<()> {
+        let (project, directory) = {
+            let state = self.state.lock().await;
+            match state.get_project() {
+                Some(project) => (project.to_owned(), state.get_export_directory().to_owned()),
+                None => {
+                    warn!("Skipping export of unset project.");
+                    return Ok(());
+                }
+            }
+        };
+        info!("Exporting project '{}'...", &project.name);
+        let sections = export::fetch(self.asana, &project.gid).await?;
+        std::fs::create_dir_all(&directory)?;
+        let file_path = directory.join(export::file_name(&project, format));
+        std::fs::write(
+            &file_path,
+            export::render(&project, &sections, format, Utc::now())?,
+        )?;
+        info!(
+            "Exported project '{}' to {}.",
+            &project.name,
+            file_path.display()
+        );
+        Ok(())
+    }
+
+    /// Read the tasks to import into the current project from the file and
+    /// show them for review, marking those which cannot be created.
+    ///
+    async fn preview_import(&mut self, file_path: PathBuf) -> Result<()> {
+        let project = match self.state.lock().await.get_project() {
+            Some(project) => project.to_owned(),
+            None => {
+                warn!("Skipping import into unset project.");
+                return Ok(());
+            }
+        };
+        info!("Reading tasks to import from {}...", file_path.display());
+        let mut rows = import::read(&file_path, Local::today().naive_local())?;
+        let sections = self.asana.sections(&project.gid).await?;
+        import::validate(&mut rows, &sections);
+        let batch = Batch { file_path, rows };
+        info!(
+            "Read {} tasks to import, {} invalid.",
+            batch.rows.len(),
+            batch.count(|s| matches!(s, ImportStatus::Invalid(_)))
+        );
+        self.state.lock().await.set_import(batch);
+        Ok(())
+    }
+
+    /// Create the tasks in the import preview which are ready, recording the
+    /// outcome for each.
+    ///
+    async fn import(&mut self) -> Result<()> {
+        let (project, workspace_gid, rows) = {
+            let state = self.state.lock().await;
+            let project = state
+                .get_project()
+                .ok_or(anyhow!("Failed to find project to import into"))?
+                .to_owned();
+            let workspace_gid = state
+                .get_active_workspace()
+                .ok_or(anyhow!("Failed to find workspace to import into"))?
+                .gid
+                .to_owned();
+            let rows = match state.get_import() {
+                Some(batch) => batch.rows.to_owned(),
+                None => return Ok(()),
+            };
+            (project, workspace_gid, rows)
+        };
+        info!("Importing tasks into project '{}'...", &project.name);
+        let sections = self.asana.sections(&project.gid).await?;
+        let mut failed = 0;
+        for (i, row) in rows.iter().enumerate() {
+            if row.status != ImportStatus::Ready {
+                continue;
+            }
+            let result =
+                import::create(self.asana, &workspace_gid, &project.gid, &sections, row).await;
+            let mut state = self.state.lock().await;
+            match result {
+                Ok(task) => {
+                    state.set_import_status(i, ImportStatus::Created(task.gid.to_owned()));
+                    if state.get_project().map(|p| &p.gid) == Some(&project.gid) {
+                        state.add_task(task);
+                    }
+                }
+                Err(err) => {
+                    warn!("Failed to import task on line {}: {}", row.line, err);
+                    state.set_import_status(i, ImportStatus::Failed(err.to_string()));
+                    failed += 1;
+                }
+            }
+        }
+        info!(
+            "Imported tasks into project '{}', {} failed.",
+            &project.name, failed
+        );
+        Ok(())
+    }
+
+    /// Request a fresh sync token for the project so that changes made after
+    /// a full refresh can be synced incrementally.
+    ///
+    async fn reset_sync_token(&mut self, project_gid: &str) {
+        self.sync_tokens.remove(project_gid);
+        match self.asana.project_events(project_gid, None).await {
+            Ok(events) => {
+                self.sync_tokens.insert(project_gid.to_owned(), events.sync);
+            }
+            Err(err) => match err.downcast_ref::<SyncExpired>() {
+                Some(expired) => {
+                    self.sync_tokens
+                        .insert(project_gid.to_owned(), expired.sync.to_owned());
+                }
+                None => warn!(
+                    "Failed to start syncing project GID {}: {}",
+                    project_gid, err
+                ),
+            },
+        }
+    }
+
+    /// Apply task changes made since the last sync of the current project,
+    /// fully refreshing its tasks if the sync token has expired.
+    ///
+    async fn sync_project_tasks(&mut self) -> Result<()> {
+        let project;
+        {
+            let state = self.state.lock().await;
+            if *state.current_view() != View::ProjectTasks || state.get_project().is_none() {
+                return Ok(());
+            }
+            project = state.get_project().unwrap().to_owned();
+        }
+        let mut sync = match self.sync_tokens.get(&project.gid) {
+            Some(sync) => sync.to_owned(),
+            None => return Ok(()),
+        };
+        loop {
+            match self.asana.project_events(&project.gid, Some(&sync)).await {
+                Ok(events) => {
+                    self.apply_task_events(&project.gid, events.events).await?;
+                    sync = events.sync;
+                    self.sync_tokens
+                        .insert(project.gid.to_owned(), sync.to_owned());
+                    if !events.has_more {
+                        break;
+                    }
+                }
+                Err(err) => match err.downcast_ref::<SyncExpired>() {
+                    Some(expired) => {
+                        warn!(
+                            "Sync expired for project '{}', refreshing...",
+                            &project.name
+                        );
+                        self.sync_tokens
+                            .insert(project.gid.to_owned(), expired.sync.to_owned());
+                        let tasks = self.asana.tasks(&project.gid).await?;
+                        let mut state = self.state.lock().await;
+                        if state.get_project().map(|p| &p.gid) == Some(&project.gid) {
+                            state.set_tasks(tasks);
+                        }
+                        break;
+                    }
+                    None => return Err(err),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Update state with the task changes if the project is still current.
+    /// Tasks which can no longer be fetched are treated as removed.
+    ///
+    async fn apply_task_events(&mut self, project_gid: &str, events: Vec<TaskEvent>) -> Result<()> {
+        for (i, event) in events.iter().enumerate() {
+            // Only the latest event for each task needs to be applied
+            if events[i + 1..]
+                .iter()
+                .any(|e| e.task_gid() == event.task_gid())
+            {
+                continue;
+            }
+
+            let (task_gid, removed) = match event.to_owned() {
+                TaskEvent::Added(gid) => (gid, false),
+                TaskEvent::Changed(gid) => {
+                    // Changes to tasks which aren't listed can be ignored
+                    if self.state.lock().await.get_task(&gid).is_none() {
+                        continue;
+                    }
+                    (gid, false)
+                }
+                TaskEvent::Removed(gid) => (gid, true),
+            };
+
+            let task = match removed {
+                true => None,
+                false => match self.asana.task(&task_gid).await {
+                    Ok(task) => Some(task),
+                    Err(err) if is_network_error(&err) => return Err(err),
+                    Err(_) => None,
+                },
+            };
+
+            let mut state = self.state.lock().await;
+            if state.get_project().map(|p| p.gid.as_str()) != Some(project_gid) {
+                return Ok(());
+            }
+            match task {
+                Some(task) => {
+                    debug!("Syncing task GID {}...", &task_gid);
+                    state.add_task(task);
+                }
+                None => {
+                    debug!("Syncing removed task GID {}...", &task_gid);
+                    state.remove_task(&task_gid);
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,21 +1,39 @@
-use crate::state::{Focus, Menu, State};
+use crate::keymap::{Action, Key};
+use crate::link;
+use crate::state::{Focus, InputKind, LogDisplay, Menu, State, View};
+use crate::ui::{areas, tab_at};
 use anyhow::Result;
 use crossterm::{
     event,
-    event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
 };
 use log::*;
 use std::{sync::mpsc, thread, time::Duration};
+use tui::layout::Rect;
+use tui_logger::TuiWidgetEvent;
 
 /// Specify terminal event poll rate in milliseconds.
 ///
 const TICK_RATE_IN_MS: u64 = 60;
 
+/// Specify how many percent of the width the sidebar is resized by.
+///
+const SIDEBAR_STEP: i16 = 5;
+
+/// Specify how many columns the timeline scrolls by a page.
+///
+const TIMELINE_PAGE: i32 = 8;
+
 /// Specify different terminal event types.
 ///
 #[derive(Debug)]
 pub enum Event<I> {
     Input(I),
+    Mouse(MouseEvent),
+    Resize,
     Tick,
 }
 
@@ -35,8 +53,10 @@ impl Handler {
         thread::spawn(move || loop {
             let tick_rate = Duration::from_millis(TICK_RATE_IN_MS);
             if event::poll(tick_rate).unwrap() {
-                if let CrosstermEvent::Key(key) = event::read().unwrap() {
-                    tx_clone.send(Event::Input(key)).unwrap();
+                match event::read().unwrap() {
+                    CrosstermEvent::Key(key) => tx_clone.send(Event::Input(key)).unwrap(),
+                    CrosstermEvent::Mouse(mouse) => tx_clone.send(Event::Mouse(mouse)).unwrap(),
+                    CrosstermEvent::Resize(_, _) => tx_clone.send(Event::Resize).unwrap(),
                 }
             }
             tx_clone.send(Event::Tick).unwrap();
@@ -49,107 +69,95 @@ impl Handler {
     ///
     pub fn handle_next(&self, state: &mut State) -> Result<bool> {
         match self.rx.recv()? {
-            Event::Input(event) => match event {
+            Event::Input(event) if state.get_help().is_some() => {
+                return Ok(handle_help_key(state, event));
+            }
+            Event::Input(event) if state.current_input().is_some() => match event {
                 KeyEvent {
                     code: KeyCode::Char('c'),
                     modifiers: KeyModifiers::CONTROL,
-                }
-                | KeyEvent {
-                    code: KeyCode::Char('q'),
-                    ..
                 } => {
                     debug!("Processing exit terminal event '{:?}'...", event);
                     return Ok(false);
                 }
                 KeyEvent {
-                    code: KeyCode::Esc,
-                    modifiers: KeyModifiers::NONE,
+                    code: KeyCode::Esc, ..
                 } => {
-                    if *state.current_focus() == Focus::View {
-                        debug!("Processing view cancel terminal event '{:?}'...", event);
-                        state.focus_menu();
-                    }
+                    debug!("Processing cancel input event '{:?}'...", event);
+                    state.cancel_input();
                 }
                 KeyEvent {
-                    code: KeyCode::Char('h'),
-                    modifiers: KeyModifiers::NONE,
-                } => match state.current_focus() {
-                    Focus::Menu => {
-                        debug!("Processing previous menu event '{:?}'...", event);
-                        state.previous_menu();
-                    }
-                    Focus::View => {}
-                },
+                    code: KeyCode::Enter,
+                    ..
+                } => {
+                    debug!("Processing submit input event '{:?}'...", event);
+                    state.submit_input();
+                }
                 KeyEvent {
-                    code: KeyCode::Char('l'),
-                    modifiers: KeyModifiers::NONE,
-                } => match state.current_focus() {
-                    Focus::Menu => {
-                        debug!("Processing next menu event '{:?}'...", event);
-                        state.next_menu();
-                    }
-                    Focus::View => {}
-                },
+                    code: KeyCode::Backspace,
+                    ..
+                } => {
+                    state.pop_input_char();
+                }
                 KeyEvent {
-                    code: KeyCode::Char('k'),
-                    modifiers: KeyModifiers::NONE,
-                } => match state.current_focus() {
-                    Focus::Menu => {
-                        debug!("Processing previous menu item event '{:?}'...", event);
-                        match state.current_menu() {
-                            Menu::Status => (),
-                            Menu::Shortcuts => {
-                                state.previous_shortcut_index();
-                            }
-                            Menu::TopList => {
-                                state.previous_top_list_index();
-                            }
-                        }
-                    }
-                    Focus::View => {}
-                },
+                    code: KeyCode::Tab, ..
+                } => {
+                    state.complete_input();
+                }
                 KeyEvent {
-                    code: KeyCode::Char('j'),
-                    modifiers: KeyModifiers::NONE,
-                } => match state.current_focus() {
-                    Focus::Menu => {
-                        debug!("Processing next menu item event '{:?}'...", event);
-                        match state.current_menu() {
-                            Menu::Status => (),
-                            Menu::Shortcuts => {
-                                state.next_shortcut_index();
-                            }
-                            Menu::TopList => {
-                                state.next_top_list_index();
-                            }
-                        }
-                    }
-                    Focus::View => {}
-                },
+                    code: KeyCode::F(1),
+                    ..
+                } => {
+                    state.toggle_help();
+                }
                 KeyEvent {
-                    code: KeyCode::Enter,
-                    modifiers: KeyModifiers::NONE,
-                } => match state.current_focus() {
-                    Focus::Menu => {
-                        debug!("Processing select menu item event '{:?}'...", event);
-                        match state.current_menu() {
-                            Menu::Status => {
-                                state.select_status_menu();
-                            }
-                            Menu::Shortcuts => {
-                                state.select_current_shortcut_index();
-                            }
-                            Menu::TopList => {
-                                state.select_current_top_list_index();
-                            }
-                        }
-                    }
-                    Focus::View => {}
-                },
+                    code: KeyCode::Up, ..
+                } if state.is_picking() => {
+                    state.previous_picker_index();
+                }
+                KeyEvent {
+                    code: KeyCode::Down,
+                    ..
+                } if state.is_picking() => {
+                    state.next_picker_index();
+                }
+                KeyEvent {
+                    code: KeyCode::Up, ..
+                } => {
+                    state.previous_history_input();
+                }
+                KeyEvent {
+                    code: KeyCode::Down,
+                    ..
+                } => {
+                    state.next_history_input();
+                }
+                KeyEvent {
+                    code: KeyCode::Char(c),
+                    ..
+                } => {
+                    state.push_input_char(c);
+                }
                 _ => {
+                    debug!("Skipping processing of input event '{:?}'...", event);
+                }
+            },
+            Event::Input(event) => match state.push_key(Key::from(event)) {
+                Some(action) => {
+                    debug!("Processing {:?} action for event '{:?}'...", action, event);
+                    return Ok(handle_action(state, action));
+                }
+                None => {
                     debug!("Skipping processing of terminal event '{:?}'...", event);
                 }
             },
+            Event::Mouse(event) if state.current_input().is_none() => {
+                handle_mouse(state, event);
+            }
+            Event::Mouse(_) => (),
+            Event::Resize => {
+                debug!("Processing resize terminal event...");
+            }
             Event::Tick => {
                 state.advance_spinner_index();
             }
@@ -157,3 +165,474 @@ impl Handler {
         Ok(true)
     }
 }
+
+/// Apply the given action to the state. Returns false if exit was requested.
+///
+fn handle_action(state: &mut State, action: Action) -> bool {
+    if *state.get_log_display() == LogDisplay::Zoomed {
+        return handle_log_action(state, action);
+    }
+    match (action, *state.current_focus()) {
+        (Action::Quit, _) => return false,
+        (Action::Undo, _) => {
+            state.undo();
+        }
+        (Action::Redo, _) => {
+            state.redo();
+        }
+        (Action::NextTheme, _) => {
+            state.next_theme();
+        }
+        (Action::Help, _) => {
+            state.toggle_help();
+        }
+        (Action::Command, _) => {
+            state.start_input(InputKind::Command);
+        }
+        (Action::Team, _) => {
+            state.start_input(InputKind::Team);
+        }
+        (Action::ShrinkSidebar, _) => {
+            state.set_panels(state.get_panels().resize_sidebar(-SIDEBAR_STEP));
+        }
+        (Action::GrowSidebar, _) => {
+            state.set_panels(state.get_panels().resize_sidebar(SIDEBAR_STEP));
+        }
+        (Action::ShrinkLog, _) => {
+            state.set_panels(state.get_panels().resize_log(-1));
+        }
+        (Action::GrowLog, _) => {
+            state.set_panels(state.get_panels().resize_log(1));
+        }
+        (Action::ToggleLog, _) => {
+            state.toggle_log();
+        }
+        (Action::ZoomLog, _) => {
+            state.toggle_log_zoom();
+        }
+        (Action::Cancel, Focus::View) => match state.current_view() {
+            View::ImportPreview => {
+                state.close_import();
+            }
+            View::TaskDetail => {
+                state.close_task_detail();
+            }
+            View::Calendar => match state.get_calendar() {
+                Some(calendar) if calendar.moving_task_gid.is_some() => {
+                    state.cancel_calendar_move();
+                }
+                _ => {
+                    state.close_calendar();
+                }
+            },
+            View::Timeline => {
+                state.close_timeline();
+            }
+            _ if state.has_marks() => {
+                state.clear_marks();
+            }
+            _ => {
+                state.focus_menu();
+            }
+        },
+        (Action::PreviousMenu, Focus::View) if *state.current_view() == View::Calendar => {
+            state.move_calendar_days(-1);
+        }
+        (Action::NextMenu, Focus::View) if *state.current_view() == View::Calendar => {
+            state.move_calendar_days(1);
+        }
+        (Action::PageUp, Focus::View) if *state.current_view() == View::Calendar => {
+            state.move_calendar_span(false);
+        }
+        (Action::PageDown, Focus::View) if *state.current_view() == View::Calendar => {
+            state.move_calendar_span(true);
+        }
+        (Action::FirstItem, Focus::View) if *state.current_view() == View::Calendar => {
+            state.calendar_today();
+        }
+        (Action::Mark, Focus::View) if *state.current_view() == View::Calendar => {
+            state.toggle_calendar_move();
+        }
+        (Action::PreviousMenu, Focus::View) if *state.current_view() == View::Timeline => {
+            state.scroll_timeline(-1);
+        }
+        (Action::NextMenu, Focus::View) if *state.current_view() == View::Timeline => {
+            state.scroll_timeline(1);
+        }
+        (Action::PageUp, Focus::View) if *state.current_view() == View::Timeline => {
+            state.scroll_timeline(-TIMELINE_PAGE);
+        }
+        (Action::PageDown, Focus::View) if *state.current_view() == View::Timeline => {
+            state.scroll_timeline(TIMELINE_PAGE);
+        }
+        (Action::Timeline, Focus::View) => match state.current_view() {
+            View::Timeline => {
+                state.zoom_timeline();
+            }
+            _ => {
+                state.open_timeline();
+            }
+        },
+        (Action::Calendar, Focus::View) => match state.current_view() {
+            View::Calendar => {
+                state.toggle_calendar_span();
+            }
+            _ => {
+                state.open_calendar();
+            }
+        },
+        (Action::PreviousMenu, Focus::Menu) => {
+            state.previous_menu();
+        }
+        (Action::NextMenu, Focus::Menu) => {
+            state.next_menu();
+        }
+        (Action::NextPanel, _) => {
+            state.next_panel();
+        }
+        (Action::PreviousPanel, _) => {
+            state.previous_panel();
+        }
+        (Action::PreviousItem, Focus::Menu) => match state.current_menu() {
+            Menu::Status => (),
+            Menu::Shortcuts => {
+                state.previous_shortcut_index();
+            }
+            Menu::TopList => {
+                state.previous_top_list_index();
+            }
+        },
+        (Action::PreviousItem, Focus::View) => {
+            state.previous_view_index();
+        }
+        (Action::NextItem, Focus::Menu) => match state.current_menu() {
+            Menu::Status => (),
+            Menu::Shortcuts => {
+                state.next_shortcut_index();
+            }
+            Menu::TopList => {
+                state.next_top_list_index();
+            }
+        },
+        (Action::NextItem, Focus::View) => {
+            state.next_view_index();
+        }
+        (Action::FirstItem, Focus::View) => {
+            state.first_view_index();
+        }
+        (Action::LastItem, Focus::View) => {
+            state.last_view_index();
+        }
+        (Action::Select, Focus::Menu) => match state.current_menu() {
+            Menu::Status => {
+                state.select_status_menu();
+            }
+            Menu::Shortcuts => {
+                state.select_current_shortcut_index();
+            }
+            Menu::TopList => {
+                state.select_current_top_list_index();
+            }
+        },
+        (Action::Select, Focus::View) if *state.current_view() == View::ImportPreview => {
+            state.confirm_import();
+        }
+        (Action::Select, Focus::View)
+            if state.is_task_view()
+                || matches!(
+                    state.current_view(),
+                    View::Inbox | View::Calendar | View::Timeline
+                ) =>
+        {
+            state.open_task_detail();
+        }
+        (Action::Complete, Focus::View) => {
+            state.toggle_current_task_completed();
+        }
+        (Action::Rename, Focus::View) => {
+            state.start_input(InputKind::Rename);
+        }
+        (Action::Comment, Focus::View) => {
+            state.start_input(InputKind::Comment);
+        }
+        (Action::DueDate, Focus::View) => match state.current_view() {
+            View::PendingChanges => {
+                state.discard_current_journal_entry();
+            }
+            _ => {
+                state.start_input(InputKind::DueDate);
+            }
+        },
+        (Action::Assign, Focus::View) => {
+            state.start_input(InputKind::Assign);
+        }
+        (Action::Section, Focus::View) => {
+            state.start_input(InputKind::Section);
+        }
+        (Action::Tag, Focus::View) => {
+            state.start_input(InputKind::Tag);
+        }
+        (Action::Delete, Focus::View) => {
+            state.start_input(InputKind::Delete);
+        }
+        (Action::AddDependency, Focus::View) => {
+            state.start_input(InputKind::AddDependency);
+        }
+        (Action::RemoveDependency, Focus::View) => {
+            state.start_input(InputKind::RemoveDependency);
+        }
+        (Action::Mark, Focus::View) if *state.current_view() == View::Inbox => {
+            state.toggle_current_inbox_item_read();
+        }
+        (Action::Mark, Focus::View) => {
+            state.toggle_current_task_marked();
+        }
+        (Action::VisualMode, Focus::View) => {
+            state.toggle_visual_mode();
+        }
+        (Action::Export, Focus::View) => {
+            state.start_input(InputKind::Export);
+        }
+        (Action::Import, Focus::View) => {
+            state.start_input(InputKind::Import);
+        }
+        (Action::OpenLink, _) => {
+            open_link(state);
+        }
+        (Action::CopyLink, _) => {
+            copy_link(state);
+        }
+        (Action::Refresh, Focus::View) => match state.current_view() {
+            View::PendingChanges => {
+                state.retry_current_journal_entry();
+            }
+            _ => {
+                state.refresh_current_view();
+            }
+        },
+        _ => (),
+    }
+    true
+}
+
+/// Open the permalink of the current task or project in the browser.
+///
+fn open_link(state: &mut State) {
+    let message = match state.current_permalink() {
+        Some(url) => match link::open(url) {
+            Ok(()) => format!("Opened {}", url),
+            Err(err) => format!("Failed to open link: {}", err),
+        },
+        None => String::from("No link to open"),
+    };
+    state.set_message(message);
+}
+
+/// Copy the permalink of the current task or project to the clipboard.
+///
+fn copy_link(state: &mut State) {
+    let message = match state.current_permalink() {
+        Some(url) => match link::copy(url) {
+            Ok(()) => format!("Copied {}", url),
+            Err(err) => format!("Failed to copy link: {}", err),
+        },
+        None => String::from("No link to copy"),
+    };
+    state.set_message(message);
+}
+
+/// Apply the given action to the zoomed log, where navigation selects a
+/// target and adjusts the levels shown and recorded for it. Returns false if
+/// exit was requested.
+///
+fn handle_log_action(state: &mut State, action: Action) -> bool {
+    let event = match action {
+        Action::Quit => return false,
+        Action::Undo => {
+            state.undo();
+            return true;
+        }
+        Action::Redo => {
+            state.redo();
+            return true;
+        }
+        Action::NextTheme => {
+            state.next_theme();
+            return true;
+        }
+        Action::Help => {
+            state.toggle_help();
+            return true;
+        }
+        Action::Command => {
+            state.start_input(InputKind::Command);
+            return true;
+        }
+        Action::Search => {
+            state.start_input(InputKind::Search);
+            return true;
+        }
+        Action::ToggleLog => {
+            state.toggle_log();
+            return true;
+        }
+        Action::ZoomLog => {
+            state.toggle_log_zoom();
+            return true;
+        }
+        Action::Cancel => {
+            state
+                .transition_log_widget(TuiWidgetEvent::EscapeKey)
+                .cancel_log();
+            return true;
+        }
+        Action::NextItem => TuiWidgetEvent::DownKey,
+        Action::PreviousItem => TuiWidgetEvent::UpKey,
+        Action::NextMenu => TuiWidgetEvent::RightKey,
+        Action::PreviousMenu => TuiWidgetEvent::LeftKey,
+        Action::GrowLog => TuiWidgetEvent::PlusKey,
+        Action::ShrinkLog => TuiWidgetEvent::MinusKey,
+        Action::Select => TuiWidgetEvent::FocusKey,
+        Action::Mark => TuiWidgetEvent::HideKey,
+        Action::PageUp => TuiWidgetEvent::PrevPageKey,
+        Action::PageDown => TuiWidgetEvent::NextPageKey,
+        _ => return true,
+    };
+    state.transition_log_widget(event);
+    true
+}
+
+/// Apply the given key press to the help overlay, where typing searches
+/// after `/` and other keys scroll or close the overlay. Returns false if
+/// exit was requested.
+///
+fn handle_help_key(state: &mut State, event: KeyEvent) -> bool {
+    if state.get_help().map(|h| h.searching).unwrap_or(false) {
+        match event.code {
+            KeyCode::Esc | KeyCode::Enter => {
+                state.set_help_searching(false);
+            }
+            KeyCode::Backspace => {
+                state.pop_help_char();
+            }
+            KeyCode::Char(c) => {
+                state.push_help_char(c);
+            }
+            _ => (),
+        }
+        return true;
+    }
+    match state.push_key(Key::from(event)) {
+        Some(Action::Quit) => return false,
+        Some(Action::Search) => {
+            debug!("Processing search help event '{:?}'...", event);
+            state.set_help_searching(true);
+        }
+        Some(Action::Help) | Some(Action::Cancel) => {
+            state.toggle_help();
+        }
+        Some(Action::NextItem) => {
+            state.scroll_help(true);
+        }
+        Some(Action::PreviousItem) => {
+            state.scroll_help(false);
+        }
+        _ => (),
+    }
+    true
+}
+
+/// Apply the given mouse event to the panel beneath it, where clicking
+/// focuses the panel and selects the clicked item and scrolling moves
+/// through the panel's items.
+///
+fn handle_mouse(state: &mut State, event: MouseEvent) {
+    if state.get_help().is_some() {
+        match event.kind {
+            MouseEventKind::ScrollDown => state.scroll_help(true),
+            MouseEventKind::ScrollUp => state.scroll_help(false),
+            _ => state,
+        };
+        return;
+    }
+    let areas = areas(*state.get_terminal_size(), state);
+    let (column, row) = (event.column, event.row);
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if contains(areas.tab_bar, column, row) {
+                debug!("Processing click tab event '{:?}'...", event);
+                match tab_at(areas.tab_bar, column) {
+                    Some(0) => state.focus_menu_item(Menu::Status),
+                    Some(1) => state.focus_menu_item(Menu::Shortcuts),
+                    Some(2) => state.focus_menu_item(Menu::TopList),
+                    Some(_) => state.focus_view(),
+                    None => state,
+                };
+            } else if contains(areas.status, column, row) {
+                debug!("Processing click status event '{:?}'...", event);
+                state.focus_menu_item(Menu::Status).select_status_menu();
+            } else if contains(areas.shortcuts, column, row) {
+                debug!("Processing click shortcuts event '{:?}'...", event);
+                state.focus_menu_item(Menu::Shortcuts);
+                if let Some(index) = inner_row(areas.shortcuts, row) {
+                    state.set_shortcut_index(index);
+                    state.select_current_shortcut_index();
+                }
+            } else if contains(areas.top_list, column, row) {
+                debug!("Processing click projects event '{:?}'...", event);
+                state.focus_menu_item(Menu::TopList);
+                if let Some(index) =
+                    inner_row(areas.top_list, row).and_then(|row| state.top_list_index_at_row(row))
+                {
+                    state.set_top_list_index(index);
+                    state.select_current_top_list_index();
+                }
+            } else if contains(areas.main, column, row) {
+                debug!("Processing click view event '{:?}'...", event);
+                state.focus_view();
+                if let Some(index) = inner_row(areas.main, row) {
+                    state
+                        .set_view_index_at_row(index, areas.main.height.saturating_sub(2) as usize);
+                }
+            }
+        }
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            let down = event.kind == MouseEventKind::ScrollDown;
+            if contains(areas.shortcuts, column, row) {
+                match down {
+                    true => state.next_shortcut_index(),
+                    false => state.previous_shortcut_index(),
+                };
+            } else if contains(areas.top_list, column, row) {
+                match down {
+                    true => state.next_top_list_index(),
+                    false => state.previous_top_list_index(),
+                };
+            } else if contains(areas.main, column, row) {
+                match down {
+                    true => state.next_view_index(),
+                    false => state.previous_view_index(),
+                };
+            } else if contains(areas.log, column, row) {
+                state.transition_log_widget(match down {
+                    true => TuiWidgetEvent::NextPageKey,
+                    false => TuiWidgetEvent::PrevPageKey,
+                });
+            }
+        }
+        _ => (),
+    }
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    column >= area.x && column < area.right() && row >= area.y && row < area.bottom()
+}
+
+/// Return the index of the given row within the bordered area, if any.
+///
+fn inner_row(area: Rect, row: u16) -> Option<usize> {
+    match row > area.y && row + 1 < area.bottom() {
+        true => Some((row - area.y - 1) as usize),
+        false => None,
+    }
+}
@@ -0,0 +1,324 @@
+use crate::asana::{Asana, Project, Section, Task};
+use crate::cli::output::{self, Record};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Specifying the names of the different export formats.
+///
+pub const FORMATS: &[&str] = &["md", "csv", "ics"];
+
+/// Specifying the different export formats.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Csv,
+    ICalendar,
+}
+
+impl Format {
+    /// Return the file extension for the format.
+    ///
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Csv => "csv",
+            Format::ICalendar => "ics",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "csv" => Ok(Format::Csv),
+            "ics" | "ical" => Ok(Format::ICalendar),
+            _ => Err(anyhow!("Unknown export format '{}'", s)),
+        }
+    }
+}
+
+/// Houses a task along with the name of its section, written as a row of
+/// the CSV export.
+///
+#[derive(Serialize)]
+struct SectionTask {
+    section: String,
+    #[serde(flatten)]
+    task: Task,
+}
+
+impl Record for SectionTask {
+    fn sample() -> Self {
+        SectionTask {
+            section: String::new(),
+            task: Task::sample(),
+        }
+    }
+}
+
+/// Return the project's sections alongside their tasks.
+///
+pub async fn fetch(asana: &mut Asana, project_gid: &str) -> Result<Vec<(Section, Vec<Task>)>> {
+    let mut sections = vec![];
+    for section in asana.sections(project_gid).await? {
+        let tasks = asana.section_tasks(&section.gid).await?;
+        sections.push((section, tasks));
+    }
+    Ok(sections)
+}
+
+/// Return the project's tasks grouped by section in the given format.
+///
+pub fn render(
+    project: &Project,
+    sections: &[(Section, Vec<Task>)],
+    format: Format,
+    now: DateTime<Utc>,
+) -> Result<String> {
+    let output = match format {
+        Format::Markdown => markdown(project, sections),
+        Format::Csv => csv(sections)?,
+        Format::ICalendar => icalendar(project, sections, now),
+    };
+    Ok(output)
+}
+
+/// Return a file name for exporting the project in the given format, named
+/// after the project's gid if its name has no letters or digits.
+///
+pub fn file_name(project: &Project, format: Format) -> String {
+    let slug: String = project
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug: Vec<&str> = slug.split('-').filter(|s| !s.is_empty()).collect();
+    match slug.is_empty() {
+        true => format!("{}.{}", project.gid, format.extension()),
+        false => format!("{}.{}", slug.join("-"), format.extension()),
+    }
+}
+
+/// Return a checklist with a heading per section.
+///
+fn markdown(project: &Project, sections: &[(Section, Vec<Task>)]) -> String {
+    let mut lines = vec![format!("# {}", project.name)];
+    for (section, tasks) in sections.iter() {
+        lines.push(String::new());
+        lines.push(format!("## {}", section.name));
+        lines.push(String::new());
+        for task in tasks.iter() {
+            let mut line = format!(
+                "- [{}] {}",
+                if task.completed { "x" } else { " " },
+                task.name
+            );
+            if let Some(due_on) = &task.due_on {
+                line.push_str(&format!(" (due {})", due_on));
+            }
+            lines.push(line);
+        }
+    }
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Return a header line followed by a line per task, prefixed with the name
+/// of its section.
+///
+fn csv(sections: &[(Section, Vec<Task>)]) -> Result<String> {
+    let rows: Vec<SectionTask> = sections
+        .iter()
+        .flat_map(|(section, tasks)| {
+            tasks.iter().map(move |task| SectionTask {
+                section: section.name.to_owned(),
+                task: task.to_owned(),
+            })
+        })
+        .collect();
+    output::render(&rows, output::Format::Csv)
+}
+
+/// Return a calendar with a to-do per task and an all-day event on the due
+/// date of each task which has one.
+///
+fn icalendar(project: &Project, sections: &[(Section, Vec<Task>)], now: DateTime<Utc>) -> String {
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!(
+            "PRODID:-//{}//{}//EN",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        ),
+        format!("X-WR-CALNAME:{}", ical_escape(&project.name)),
+    ];
+    for (section, tasks) in sections.iter() {
+        for task in tasks.iter() {
+            let due_on = task
+                .due_on
+                .as_ref()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+            lines.push(String::from("BEGIN:VTODO"));
+            lines.push(format!("UID:{}@asana", task.gid));
+            lines.push(format!("DTSTAMP:{}", stamp));
+            lines.push(format!("SUMMARY:{}", ical_escape(&task.name)));
+            lines.push(format!("CATEGORIES:{}", ical_escape(&section.name)));
+            if let Some(due_on) = due_on {
+                lines.push(format!("DUE;VALUE=DATE:{}", due_on.format("%Y%m%d")));
+            }
+            lines.push(format!(
+                "STATUS:{}",
+                if task.completed {
+                    "COMPLETED"
+                } else {
+                    "NEEDS-ACTION"
+                }
+            ));
+            lines.push(String::from("END:VTODO"));
+            if let Some(due_on) = due_on {
+                lines.push(String::from("BEGIN:VEVENT"));
+                lines.push(format!("UID:{}-due@asana", task.gid));
+                lines.push(format!("DTSTAMP:{}", stamp));
+                lines.push(format!("DTSTART;VALUE=DATE:{}", due_on.format("%Y%m%d")));
+                lines.push(format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (due_on + Duration::days(1)).format("%Y%m%d")
+                ));
+                lines.push(format!("SUMMARY:{}", ical_escape(&task.name)));
+                lines.push(String::from("TRANSP:TRANSPARENT"));
+                lines.push(String::from("END:VEVENT"));
+            }
+        }
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines.iter().map(|line| ical_fold(line)).collect()
+}
+
+/// Return the text with characters special to iCalendar escaped.
+///
+fn ical_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Return the content line folded to 75 octets and terminated by CRLF.
+///
+fn ical_fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn project() -> Project {
+        Project {
+            gid: String::from("1100"),
+            name: String::from("Q1 Launch; Web"),
+            permalink_url: None,
+            team: None,
+        }
+    }
+
+    fn sections() -> Vec<(Section, Vec<Task>)> {
+        let task = |gid: &str, name: &str, completed: bool, due_on: Option<&str>| Task {
+            gid: gid.to_owned(),
+            name: name.to_owned(),
+            completed,
+            start_on: None,
+            due_on: due_on.map(str::to_owned),
+            modified_at: None,
+            assignee: None,
+            memberships: vec![],
+            assignee_section: None,
+            permalink_url: None,
+            dependencies: vec![],
+        };
+        vec![
+            (
+                Section {
+                    gid: String::from("1110"),
+                    name: String::from("To do"),
+                },
+                vec![
+                    task("1201", "Write copy, final", false, Some("2022-01-31")),
+                    task("1202", "Pick hosting", false, None),
+                ],
+            ),
+            (
+                Section {
+                    gid: String::from("1120"),
+                    name: String::from("Done"),
+                },
+                vec![task("1203", "Register domain", true, Some("2022-01-03"))],
+            ),
+        ]
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.ymd(2022, 1, 5).and_hms(12, 0, 0)
+    }
+
+    #[test]
+    fn render_markdown() -> Result<()> {
+        let output = render(&project(), &sections(), Format::Markdown, now())?;
+        assert_eq!(output, include_str!("cli/golden/export.md"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_csv() -> Result<()> {
+        let output = render(&project(), &sections(), Format::Csv, now())?;
+        assert_eq!(output, include_str!("cli/golden/export.csv"));
+        Ok(())
+    }
+
+    #[test]
+    fn render_icalendar() -> Result<()> {
+        let output = render(&project(), &sections(), Format::ICalendar, now())?;
+        assert_eq!(output, include_str!("cli/golden/export.ics"));
+        Ok(())
+    }
+
+    #[test]
+    fn ical_fold_long_lines() {
+        let folded = ical_fold(&"a".repeat(100));
+        assert_eq!(
+            folded,
+            format!("{}\r\n {}\r\n", "a".repeat(75), "a".repeat(25))
+        );
+    }
+
+    #[test]
+    fn file_name() {
+        assert_eq!(
+            super::file_name(&project(), Format::ICalendar),
+            "q1-launch-web.ics"
+        );
+        let mut project = project();
+        project.name = String::from("???");
+        assert_eq!(super::file_name(&project, Format::Markdown), "1100.md");
+    }
+}
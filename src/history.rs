@@ -0,0 +1,82 @@
+use crate::persisted::PersistedList;
+use anyhow::Result;
+use std::path::Path;
+
+const FILE_NAME: &str = "history.yml";
+
+/// Specifying how many commands are remembered.
+///
+const LIMIT: usize = 100;
+
+/// Oversees commands entered in the command line, persisting them to disk so
+/// they can be recalled in later sessions.
+///
+#[derive(Debug)]
+pub struct History {
+    entries: PersistedList,
+}
+
+impl Default for History {
+    fn default() -> History {
+        History {
+            entries: PersistedList::new(LIMIT),
+        }
+    }
+}
+
+impl History {
+    /// Return the history persisted in the given directory or a new empty
+    /// history if none exists yet.
+    ///
+    pub fn load(dir_path: &Path) -> Result<History> {
+        Ok(History {
+            entries: PersistedList::load(dir_path, FILE_NAME, LIMIT)?,
+        })
+    }
+
+    /// Return all commands from oldest to newest.
+    ///
+    pub fn entries(&self) -> &Vec<String> {
+        self.entries.entries()
+    }
+
+    /// Remember the command as the newest, moving it there if it was entered
+    /// before and forgetting the oldest beyond the limit.
+    ///
+    pub fn push(&mut self, command: &str) -> Result<()> {
+        let command = command.trim();
+        if command.is_empty() {
+            return Ok(());
+        }
+        self.entries.remove(command);
+        self.entries.push(command);
+        self.entries.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push() -> Result<()> {
+        let mut history = History::default();
+        history.push("due fri")?;
+        history.push("refresh")?;
+        history.push(" due fri ")?;
+        history.push("")?;
+        assert_eq!(history.entries(), &vec!["refresh", "due fri"]);
+        Ok(())
+    }
+
+    #[test]
+    fn push_forgets_oldest() -> Result<()> {
+        let mut history = History::default();
+        for i in 0..=LIMIT {
+            history.push(&format!("project {}", i))?;
+        }
+        assert_eq!(history.entries().len(), LIMIT);
+        assert_eq!(history.entries()[0], "project 1");
+        Ok(())
+    }
+}
@@ -0,0 +1,466 @@
+use crate::asana::{Asana, NewTask, Section, Task};
+use crate::date;
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Specifying the different import formats.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    /// Return the format matching the extension of the file.
+    ///
+    pub fn from_path(file_path: &Path) -> Result<Format> {
+        let extension = file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(Format::Csv),
+            Some("md") | Some("markdown") => Ok(Format::Markdown),
+            _ => Err(anyhow!(
+                "Unknown import format for '{}', expected .csv or .md",
+                file_path.display()
+            )),
+        }
+    }
+}
+
+/// Specifying the different row statuses.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "message", rename_all = "snake_case")]
+pub enum Status {
+    Ready,
+    Invalid(String),
+    Created(String),
+    Failed(String),
+}
+
+/// Defines a task to be created from a row of the import file.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Row {
+    pub line: usize,
+    pub name: String,
+    pub completed: bool,
+    pub assignee: Option<String>,
+    pub due_on: Option<String>,
+    pub section: Option<String>,
+    pub notes: Option<String>,
+    #[serde(flatten)]
+    pub status: Status,
+}
+
+impl Row {
+    fn new(line: usize, name: &str) -> Row {
+        Row {
+            line,
+            name: name.trim().to_owned(),
+            completed: false,
+            assignee: None,
+            due_on: None,
+            section: None,
+            notes: None,
+            status: match name.trim().is_empty() {
+                true => Status::Invalid(String::from("Missing name")),
+                false => Status::Ready,
+            },
+        }
+    }
+
+    /// Set the due date from user input, marking the row invalid if it cannot
+    /// be parsed.
+    ///
+    fn set_due(&mut self, due: &str, today: NaiveDate) {
+        if due.trim().is_empty() {
+            return;
+        }
+        match date::parse(due, today) {
+            Ok(due_on) => self.due_on = Some(due_on.to_string()),
+            Err(_) => self.invalidate(format!("Invalid due date '{}'", due.trim())),
+        }
+    }
+
+    /// Mark the row invalid for the reason unless it already is.
+    ///
+    fn invalidate(&mut self, reason: String) {
+        if self.status == Status::Ready {
+            self.status = Status::Invalid(reason);
+        }
+    }
+}
+
+/// Houses the rows read from an import file.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Batch {
+    pub file_path: PathBuf,
+    pub rows: Vec<Row>,
+}
+
+impl Batch {
+    /// Return the number of rows with the given status.
+    ///
+    pub fn count(&self, matches: fn(&Status) -> bool) -> usize {
+        self.rows.iter().filter(|r| matches(&r.status)).count()
+    }
+}
+
+/// Return the rows of the file in the format matching its extension.
+///
+pub fn read(file_path: &Path, today: NaiveDate) -> Result<Vec<Row>> {
+    let format = Format::from_path(file_path)?;
+    let text = fs::read_to_string(file_path)?;
+    parse(&text, format, today)
+}
+
+/// Return the rows of the text in the given format, with rows which cannot
+/// be imported marked invalid.
+///
+pub fn parse(text: &str, format: Format, today: NaiveDate) -> Result<Vec<Row>> {
+    match format {
+        Format::Csv => csv(text, today),
+        Format::Markdown => Ok(markdown(text, today)),
+    }
+}
+
+/// Mark rows invalid whose section is not one of the project's sections.
+///
+pub fn validate(rows: &mut [Row], sections: &[Section]) {
+    for row in rows.iter_mut() {
+        if let Some(name) = &row.section {
+            if find_section(sections, name).is_none() {
+                let reason = format!("Unknown section '{}'", name);
+                row.invalidate(reason);
+            }
+        }
+    }
+}
+
+/// Create a task in the project from the row.
+///
+pub async fn create(
+    asana: &mut Asana,
+    workspace_gid: &str,
+    project_gid: &str,
+    sections: &[Section],
+    row: &Row,
+) -> Result<Task> {
+    let section_gid = match &row.section {
+        Some(name) => Some(
+            find_section(sections, name)
+                .ok_or(anyhow!("Unknown section '{}'", name))?
+                .gid
+                .to_owned(),
+        ),
+        None => None,
+    };
+    let new_task = NewTask {
+        workspace_gid: workspace_gid.to_owned(),
+        project_gid: Some(project_gid.to_owned()),
+        section_gid,
+        name: row.name.to_owned(),
+        completed: row.completed,
+        notes: row.notes.to_owned(),
+        assignee: row.assignee.to_owned(),
+        due_on: row.due_on.to_owned(),
+    };
+    asana.create_task(&new_task).await
+}
+
+fn find_section<'a>(sections: &'a [Section], name: &str) -> Option<&'a Section> {
+    sections.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+/// Return a row per record beneath a header naming the columns. Columns
+/// other than name, completed, assignee, due, section and notes are ignored
+/// so that exported files can be imported again.
+///
+fn csv(text: &str, today: NaiveDate) -> Result<Vec<Row>> {
+    let mut records = csv_records(text)?.into_iter();
+    let header: Vec<String> = match records.next() {
+        Some((_, fields)) => fields.iter().map(|f| f.trim().to_lowercase()).collect(),
+        None => return Ok(vec![]),
+    };
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+    let name_column = column(&["name"]).ok_or(anyhow!("Missing 'name' column in header"))?;
+    let completed_column = column(&["completed"]);
+    let assignee_column = column(&["assignee", "assignee_email", "assignee.email", "email"]);
+    let due_column = column(&["due", "due_on", "due date"]);
+    let section_column = column(&["section"]);
+    let notes_column = column(&["notes"]);
+
+    let mut rows = vec![];
+    for (line, fields) in records {
+        if fields.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| fields.get(c))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+        let mut row = Row::new(line, field(Some(name_column)).unwrap_or_default());
+        row.completed = matches!(
+            field(completed_column).map(str::to_lowercase).as_deref(),
+            Some("true" | "yes" | "x" | "1")
+        );
+        row.assignee = field(assignee_column).map(str::to_owned);
+        row.section = field(section_column).map(str::to_owned);
+        row.notes = field(notes_column).map(str::to_owned);
+        if let Some(due) = field(due_column) {
+            row.set_due(due, today);
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Return the fields of each record alongside the line it starts on,
+/// unquoting fields and allowing quoted fields to span lines.
+///
+fn csv_records(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            '\n' if quoted => {
+                line += 1;
+                field.push(c);
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted => (),
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                records.push((start, std::mem::take(&mut fields)));
+                line += 1;
+                start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(anyhow!("Unterminated quote in record on line {}", start));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((start, fields));
+    }
+    Ok(records)
+}
+
+/// Return a row per checklist item, taking the section from the nearest
+/// heading below the title and notes from indented lines beneath the item.
+/// A trailing `(due ...)` sets the due date.
+///
+fn markdown(text: &str, today: NaiveDate) -> Vec<Row> {
+    let mut rows: Vec<Row> = vec![];
+    let mut section: Option<String> = None;
+    let mut in_item = false;
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with("##") {
+            section = Some(line.trim_start_matches('#').trim().to_owned());
+            in_item = false;
+            continue;
+        }
+        let item = line
+            .strip_prefix("- [")
+            .or_else(|| line.strip_prefix("* ["))
+            .and_then(|rest| Some((rest.get(..1)?, rest.get(1..)?.strip_prefix("] ")?)));
+        if let Some((mark, text)) = item {
+            let (name, due) = match text.trim_end().strip_suffix(')') {
+                Some(rest) => match rest.rsplit_once(" (due ") {
+                    Some((name, due)) => (name, Some(due)),
+                    None => (text, None),
+                },
+                None => (text, None),
+            };
+            let mut row = Row::new(i + 1, name);
+            row.completed = mark.eq_ignore_ascii_case("x");
+            row.section = section.to_owned();
+            if let Some(due) = due {
+                row.set_due(due, today);
+            }
+            rows.push(row);
+            in_item = true;
+            continue;
+        }
+        match rows.last_mut() {
+            Some(row) if in_item && line.starts_with([' ', '\t']) => {
+                let notes = row.notes.get_or_insert_with(String::new);
+                if !notes.is_empty() {
+                    notes.push('\n');
+                }
+                notes.push_str(line.trim());
+            }
+            _ => in_item = false,
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // A Wednesday
+        NaiveDate::from_ymd(2022, 1, 5)
+    }
+
+    fn section(name: &str) -> Section {
+        Section {
+            gid: name.to_lowercase(),
+            name: name.to_owned(),
+        }
+    }
+
+    #[test]
+    fn parse_csv() -> Result<()> {
+        let text = "Name,Assignee,Due,Section,Notes\r\n\
+                    \"Write copy, final\",alice@example.com,fri,To do,\"Two\nlines\"\r\n\
+                    Pick hosting,,,,\r\n\
+                    ,,,,\r\n\
+                    ,bob@example.com,,,\r\n\
+                    Register domain,,someday,,\r\n";
+        let rows = parse(text, Format::Csv, today())?;
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[0],
+            Row {
+                line: 2,
+                name: String::from("Write copy, final"),
+                completed: false,
+                assignee: Some(String::from("alice@example.com")),
+                due_on: Some(String::from("2022-01-07")),
+                section: Some(String::from("To do")),
+                notes: Some(String::from("Two\nlines")),
+                status: Status::Ready,
+            }
+        );
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].status, Status::Ready);
+        assert_eq!(rows[2].line, 6);
+        assert_eq!(
+            rows[2].status,
+            Status::Invalid(String::from("Missing name"))
+        );
+        assert_eq!(
+            rows[3].status,
+            Status::Invalid(String::from("Invalid due date 'someday'"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_csv_export() -> Result<()> {
+        let rows = parse(include_str!("cli/golden/export.csv"), Format::Csv, today())?;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].section, Some(String::from("Done")));
+        assert!(rows[2].completed);
+        assert_eq!(rows[2].due_on, Some(String::from("2022-01-03")));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_csv_invalid() {
+        assert!(parse("Title\nWrite copy\n", Format::Csv, today()).is_err());
+        assert!(parse("Name\n\"Write copy\n", Format::Csv, today()).is_err());
+    }
+
+    #[test]
+    fn parse_markdown() -> Result<()> {
+        let text = "# Launch\n\
+                    \n\
+                    - [ ] Pick hosting\n\
+                    \n\
+                    ## To do\n\
+                    \n\
+                    - [ ] Write copy (due +2d)\n  First draft\n  Then review\n\
+                    Not a task\n\
+                    * [x] Register domain\n";
+        let rows = parse(text, Format::Markdown, today())?;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].name, "Pick hosting");
+        assert_eq!(rows[0].section, None);
+        assert_eq!(rows[1].line, 7);
+        assert_eq!(rows[1].name, "Write copy");
+        assert_eq!(rows[1].section, Some(String::from("To do")));
+        assert_eq!(rows[1].due_on, Some(String::from("2022-01-07")));
+        assert_eq!(
+            rows[1].notes,
+            Some(String::from("First draft\nThen review"))
+        );
+        assert!(rows[2].completed);
+        assert_eq!(rows[2].notes, None);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_markdown_export() -> Result<()> {
+        let rows = parse(
+            include_str!("cli/golden/export.md"),
+            Format::Markdown,
+            today(),
+        )?;
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].name, "Write copy, final");
+        assert_eq!(rows[0].due_on, Some(String::from("2022-01-31")));
+        assert_eq!(rows[2].section, Some(String::from("Done")));
+        Ok(())
+    }
+
+    #[test]
+    fn validate_sections() -> Result<()> {
+        let mut rows = parse(
+            "name,section\nWrite copy,to do\nPick hosting,Later\n",
+            Format::Csv,
+            today(),
+        )?;
+        validate(&mut rows, &[section("To do")]);
+        assert_eq!(rows[0].status, Status::Ready);
+        assert_eq!(
+            rows[1].status,
+            Status::Invalid(String::from("Unknown section 'Later'"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(
+            Format::from_path(Path::new("tasks.CSV")).unwrap(),
+            Format::Csv
+        );
+        assert_eq!(
+            Format::from_path(Path::new("tasks.md")).unwrap(),
+            Format::Markdown
+        );
+        assert!(Format::from_path(Path::new("tasks.txt")).is_err());
+    }
+}
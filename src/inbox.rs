@@ -0,0 +1,90 @@
+use crate::persisted::PersistedList;
+use anyhow::Result;
+use std::path::Path;
+
+const FILE_NAME: &str = "inbox.yml";
+
+/// Specifying how many read stories are remembered, well beyond those shown
+/// in the inbox at once.
+///
+const LIMIT: usize = 1000;
+
+/// Oversees which inbox stories were read, persisting them to disk so they
+/// stay read in later sessions.
+///
+#[derive(Debug)]
+pub struct Inbox {
+    read_gids: PersistedList,
+}
+
+impl Default for Inbox {
+    fn default() -> Inbox {
+        Inbox {
+            read_gids: PersistedList::new(LIMIT),
+        }
+    }
+}
+
+impl Inbox {
+    /// Return the read stories persisted in the given directory or a new
+    /// inbox with none read if none exist yet.
+    ///
+    pub fn load(dir_path: &Path) -> Result<Inbox> {
+        Ok(Inbox {
+            read_gids: PersistedList::load(dir_path, FILE_NAME, LIMIT)?,
+        })
+    }
+
+    /// Return true if the story with the given GID was read.
+    ///
+    pub fn is_read(&self, story_gid: &str) -> bool {
+        self.read_gids.contains(story_gid)
+    }
+
+    /// Mark the story with the given GID as read or unread, forgetting the
+    /// oldest read stories beyond the limit.
+    ///
+    pub fn set_read(&mut self, story_gid: &str, read: bool) -> Result<()> {
+        if self.is_read(story_gid) == read {
+            return Ok(());
+        }
+        match read {
+            true => self.read_gids.push(story_gid),
+            false => self.read_gids.remove(story_gid),
+        }
+        self.read_gids.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn set_read() -> Result<()> {
+        let mut inbox = Inbox::default();
+        inbox.set_read("1", true)?;
+        inbox.set_read("2", true)?;
+        inbox.set_read("1", true)?;
+        assert!(inbox.is_read("1"));
+        assert_eq!(inbox.read_gids.entries(), &vec!["1", "2"]);
+        inbox.set_read("1", false)?;
+        assert!(!inbox.is_read("1"));
+        for gid in 0..LIMIT {
+            inbox.set_read(&format!("story {}", gid), true)?;
+        }
+        assert_eq!(inbox.read_gids.entries().len(), LIMIT);
+        assert!(!inbox.is_read("2"));
+        Ok(())
+    }
+
+    #[test]
+    fn load_saved() -> Result<()> {
+        let dir = tempdir()?;
+        let mut inbox = Inbox::load(dir.path())?;
+        inbox.set_read("1", true)?;
+        assert!(Inbox::load(dir.path())?.is_read("1"));
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use crate::asana::{Mutation, Task};
+use anyhow::Result;
+use chrono::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const FILE_NAME: &str = "journal.yml";
+
+/// Specifying the different replay statuses.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pending,
+    Failed(String),
+}
+
+/// Defines a mutation awaiting replay.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub id: u64,
+    pub mutation: Mutation,
+    pub queued_at: String,
+    pub modified_at: Option<String>,
+    pub status: Status,
+}
+
+/// Oversees mutations made while Asana was unreachable, persisting them to
+/// disk until they can be replayed in order.
+///
+#[derive(Debug, Default)]
+pub struct Journal {
+    file_path: Option<PathBuf>,
+    entries: Vec<Entry>,
+}
+
+impl Journal {
+    /// Return the journal persisted in the given directory or a new empty
+    /// journal if none exists yet.
+    ///
+    pub fn load(dir_path: &Path) -> Result<Journal> {
+        let file_path = dir_path.join(Path::new(FILE_NAME));
+        let entries = match file_path.exists() {
+            true => serde_yaml::from_str(&fs::read_to_string(&file_path)?)?,
+            false => vec![],
+        };
+        Ok(Journal {
+            file_path: Some(file_path),
+            entries,
+        })
+    }
+
+    /// Return all entries in replay order.
+    ///
+    pub fn entries(&self) -> &Vec<Entry> {
+        &self.entries
+    }
+
+    /// Queue the mutation alongside the last known modification time of its
+    /// task for conflict detection.
+    ///
+    pub fn push(&mut self, mutation: Mutation, modified_at: Option<String>) -> Result<()> {
+        let id = self.entries.iter().map(|e| e.id).max().unwrap_or(0) + 1;
+        self.entries.push(Entry {
+            id,
+            mutation,
+            queued_at: Utc::now().to_rfc3339(),
+            modified_at,
+            status: Status::Pending,
+        });
+        self.save()
+    }
+
+    /// Return the next entry awaiting replay.
+    ///
+    pub fn next_pending(&self) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.status == Status::Pending)
+    }
+
+    /// Return true if any entry is awaiting replay.
+    ///
+    pub fn has_pending(&self) -> bool {
+        self.next_pending().is_some()
+    }
+
+    /// Return the number of entries which failed to replay.
+    ///
+    pub fn failed_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| matches!(e.status, Status::Failed(_)))
+            .count()
+    }
+
+    /// Return the status of the most recent entry for the task.
+    ///
+    pub fn task_status(&self, task_gid: &str) -> Option<&Status> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.mutation.task_gid() == task_gid)
+            .map(|e| &e.status)
+    }
+
+    /// Return true if a deletion of the task is awaiting replay.
+    ///
+    pub fn is_deleted(&self, task_gid: &str) -> bool {
+        self.entries.iter().any(|e| {
+            e.status == Status::Pending
+                && e.mutation.is_delete()
+                && e.mutation.task_gid() == task_gid
+        })
+    }
+
+    /// Apply the mutations awaiting replay for the task to the local copy in
+    /// order.
+    ///
+    pub fn reapply(&self, task: &mut Task) {
+        let task_gid = task.gid.to_owned();
+        self.entries
+            .iter()
+            .filter(|e| e.status == Status::Pending && e.mutation.task_gid() == task_gid)
+            .for_each(|e| e.mutation.apply(task));
+    }
+
+    /// Remove the entry with the given ID.
+    ///
+    pub fn remove(&mut self, id: u64) -> Result<()> {
+        self.entries.retain(|e| e.id != id);
+        self.save()
+    }
+
+    /// Mark the entry with the given ID as failed for the given reason.
+    ///
+    pub fn fail(&mut self, id: u64, reason: String) -> Result<()> {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+            entry.status = Status::Failed(reason);
+        }
+        self.save()
+    }
+
+    /// Return the failed entry with the given ID to pending, dropping its
+    /// conflict baseline so the replay overwrites the server's changes.
+    ///
+    pub fn retry(&mut self, id: u64) -> Result<()> {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == id) {
+            entry.status = Status::Pending;
+            entry.modified_at = None;
+        }
+        self.save()
+    }
+
+    /// Update the conflict baseline of pending entries for the task after a
+    /// successful replay changed its modification time.
+    ///
+    pub fn rebase(&mut self, task_gid: &str, modified_at: Option<String>) -> Result<()> {
+        self.entries
+            .iter_mut()
+            .filter(|e| e.status == Status::Pending && e.mutation.task_gid() == task_gid)
+            .filter(|e| e.modified_at.is_some())
+            .for_each(|e| e.modified_at = modified_at.to_owned());
+        self.save()
+    }
+
+    /// Write the entries to the disk if the journal has a file path.
+    ///
+    fn save(&self) -> Result<()> {
+        if let Some(file_path) = &self.file_path {
+            fs::write(file_path, serde_yaml::to_string(&self.entries)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::{Fake, Faker};
+    use tempfile::tempdir;
+
+    fn complete(task_gid: &str) -> Mutation {
+        Mutation::Complete {
+            task_gid: task_gid.to_owned(),
+            completed: true,
+        }
+    }
+
+    #[test]
+    fn push() -> Result<()> {
+        let mut journal = Journal::default();
+        journal.push(complete("1"), Some(Faker.fake()))?;
+        journal.push(complete("2"), None)?;
+        assert_eq!(journal.entries.len(), 2);
+        assert_eq!(journal.entries[0].id, 1);
+        assert_eq!(journal.entries[1].id, 2);
+        assert_eq!(journal.entries[1].status, Status::Pending);
+        Ok(())
+    }
+
+    #[test]
+    fn next_pending_skips_failed() -> Result<()> {
+        let mut journal = Journal::default();
+        journal.push(complete("1"), None)?;
+        journal.push(complete("2"), None)?;
+        journal.fail(1, Faker.fake())?;
+        assert_eq!(journal.next_pending().unwrap().id, 2);
+        assert_eq!(journal.failed_count(), 1);
+        journal.fail(2, Faker.fake())?;
+        assert!(!journal.has_pending());
+        Ok(())
+    }
+
+    #[test]
+    fn retry() -> Result<()> {
+        let mut journal = Journal::default();
+        journal.push(complete("1"), Some(Faker.fake()))?;
+        journal.fail(1, Faker.fake())?;
+        journal.retry(1)?;
+        assert_eq!(journal.entries[0].status, Status::Pending);
+        assert_eq!(journal.entries[0].modified_at, None);
+        Ok(())
+    }
+
+    #[test]
+    fn rebase() -> Result<()> {
+        let mut journal = Journal::default();
+        journal.push(complete("1"), Some(Faker.fake()))?;
+        journal.push(complete("2"), Some(Faker.fake()))?;
+        let modified_at: String = Faker.fake();
+        journal.rebase("1", Some(modified_at.to_owned()))?;
+        assert_eq!(journal.entries[0].modified_at, Some(modified_at.to_owned()));
+        assert_ne!(journal.entries[1].modified_at, Some(modified_at));
+        Ok(())
+    }
+
+    #[test]
+    fn is_deleted() -> Result<()> {
+        let mut journal = Journal::default();
+        journal.push(complete("1"), None)?;
+        journal.push(
+            Mutation::Delete {
+                task_gid: String::from("2"),
+            },
+            None,
+        )?;
+        assert!(!journal.is_deleted("1"));
+        assert!(journal.is_deleted("2"));
+        journal.fail(2, Faker.fake())?;
+        assert!(!journal.is_deleted("2"));
+        Ok(())
+    }
+
+    #[test]
+    fn load_persisted() -> Result<()> {
+        let dir = tempdir()?;
+        let mut journal = Journal::load(dir.path())?;
+        journal.push(complete("1"), None)?;
+        let loaded = Journal::load(dir.path())?;
+        assert_eq!(loaded.entries, journal.entries);
+        Ok(())
+    }
+}
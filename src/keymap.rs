@@ -0,0 +1,617 @@
+use anyhow::{anyhow, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Specifying the different actions which can be bound to keys.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Cancel,
+    Select,
+    NextItem,
+    PreviousItem,
+    FirstItem,
+    LastItem,
+    NextMenu,
+    PreviousMenu,
+    NextPanel,
+    PreviousPanel,
+    Undo,
+    Redo,
+    Complete,
+    Rename,
+    Comment,
+    DueDate,
+    Assign,
+    Section,
+    Tag,
+    Delete,
+    Mark,
+    VisualMode,
+    Export,
+    Import,
+    Refresh,
+    NextTheme,
+    Help,
+    Command,
+    ShrinkSidebar,
+    GrowSidebar,
+    ShrinkLog,
+    GrowLog,
+    ToggleLog,
+    ZoomLog,
+    Search,
+    PageUp,
+    PageDown,
+    OpenLink,
+    CopyLink,
+    Calendar,
+    Timeline,
+    AddDependency,
+    RemoveDependency,
+    Team,
+}
+
+/// Specifying the contexts in which actions are available.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Context {
+    Menu,
+    Tasks,
+    PendingChanges,
+    ImportPreview,
+    Welcome,
+    TaskDetail,
+    Inbox,
+    Calendar,
+    Timeline,
+    Log,
+}
+
+/// Specifying the keys used while entering text, which are not rebindable.
+///
+pub const INPUT_BINDINGS: &[(&str, &str)] = &[
+    ("enter", "submit"),
+    ("esc", "cancel"),
+    ("backspace", "delete previous character"),
+    ("tab", "complete command"),
+    ("up, down", "previous or next command"),
+    ("f1", "toggle help"),
+    ("^c", "quit"),
+];
+
+impl Action {
+    /// Return what the action does in the given context, or None if it is
+    /// unavailable there.
+    ///
+    pub fn describe(&self, context: Context) -> Option<&'static str> {
+        use Context::*;
+        let description = match (self, context) {
+            (Action::Quit, _) => "quit",
+            (Action::Undo, _) => "undo last change",
+            (Action::Redo, _) => "redo last undone change",
+            (Action::NextPanel, _) => "focus next panel",
+            (Action::PreviousPanel, _) => "focus previous panel",
+            (Action::NextTheme, _) => "switch theme",
+            (Action::Help, _) => "toggle help",
+            (Action::Command, _) => "enter command",
+            (Action::Team, _) => "pick team of listed projects",
+            (Action::ShrinkSidebar, _) => "narrow sidebar",
+            (Action::GrowSidebar, _) => "widen sidebar",
+            (Action::ToggleLog, _) => "toggle log",
+            (Action::ZoomLog, _) => "zoom log",
+            (Action::NextItem, Log) => "next target",
+            (Action::PreviousItem, Log) => "previous target",
+            (Action::NextMenu, Log) => "show more of target",
+            (Action::PreviousMenu, Log) => "show less of target",
+            (Action::GrowLog, Log) => "record more of target",
+            (Action::ShrinkLog, Log) => "record less of target",
+            (Action::Select, Log) => "show only selected target",
+            (Action::Mark, Log) => "toggle target list",
+            (Action::Search, Log) => "search log",
+            (Action::PageUp, Log) => "previous page",
+            (Action::PageDown, Log) => "next page",
+            (Action::Cancel, Log) => "clear search or leave zoom",
+            (Action::ShrinkLog, _) => "shorten log",
+            (Action::GrowLog, _) => "lengthen log",
+            (Action::NextItem, Welcome) | (Action::PreviousItem, Welcome) => return None,
+            (Action::NextItem, Calendar) => "next task on day",
+            (Action::PreviousItem, Calendar) => "previous task on day",
+            (Action::NextMenu, Calendar) => "next day",
+            (Action::PreviousMenu, Calendar) => "previous day",
+            (Action::PageDown, Calendar) => "next week or month",
+            (Action::PageUp, Calendar) => "previous week or month",
+            (Action::FirstItem, Calendar) => "today",
+            (Action::Mark, Calendar) => "move task to selected day",
+            (Action::Calendar, Calendar) => "switch between week and month",
+            (Action::Calendar, Tasks) => "show calendar",
+            (Action::Cancel, Calendar) => "stop moving or close calendar",
+            (Action::NextMenu, Timeline) => "scroll later",
+            (Action::PreviousMenu, Timeline) => "scroll earlier",
+            (Action::PageDown, Timeline) => "scroll a page later",
+            (Action::PageUp, Timeline) => "scroll a page earlier",
+            (Action::Timeline, Timeline) => "zoom to days, weeks or months",
+            (Action::Timeline, Tasks) => "show project timeline",
+            (Action::Cancel, Timeline) => "close timeline",
+            (Action::NextItem, TaskDetail) => "next comment",
+            (Action::PreviousItem, TaskDetail) => "previous comment",
+            (Action::FirstItem, TaskDetail) => "notes",
+            (Action::LastItem, TaskDetail) => "last comment",
+            (Action::NextItem, _) => "next item",
+            (Action::PreviousItem, _) => "previous item",
+            (Action::NextMenu, Menu) => "next menu",
+            (Action::PreviousMenu, Menu) => "previous menu",
+            (Action::Select, Menu) => "open selected item",
+            (Action::Select, ImportPreview) => "create tasks",
+            (Action::Select, Tasks | Inbox | Calendar | Timeline) => "open task",
+            (Action::Mark, Inbox) => "toggle read",
+            (Action::FirstItem, Tasks | PendingChanges | ImportPreview | Inbox | Timeline) => {
+                "first item"
+            }
+            (Action::LastItem, Tasks | PendingChanges | ImportPreview | Inbox | Timeline) => {
+                "last item"
+            }
+            (Action::Cancel, ImportPreview) => "close preview",
+            (Action::Cancel, Tasks) => "clear marks or focus menu",
+            (Action::Cancel, PendingChanges | Welcome | Inbox) => "focus menu",
+            (Action::Cancel, TaskDetail) => "back to tasks",
+            (Action::DueDate, PendingChanges) => "discard change",
+            (Action::Refresh, PendingChanges) => "retry change",
+            (Action::Complete, Tasks | TaskDetail | Calendar | Timeline) => "toggle completion",
+            (Action::Rename, Tasks | TaskDetail | Calendar | Timeline) => "rename",
+            (Action::Comment, Tasks | TaskDetail | Calendar | Timeline) => "comment",
+            (Action::DueDate, Tasks | TaskDetail | Calendar | Timeline) => "set due date",
+            (Action::Assign, Tasks | TaskDetail | Calendar | Timeline) => "assign",
+            (Action::Section, Tasks | TaskDetail | Calendar | Timeline) => "move to section",
+            (Action::Tag, Tasks | TaskDetail | Calendar | Timeline) => "add tag",
+            (Action::Delete, Tasks | TaskDetail | Calendar | Timeline) => "delete",
+            (Action::AddDependency, Tasks | TaskDetail | Calendar | Timeline) => {
+                "mark blocked by task"
+            }
+            (Action::RemoveDependency, Tasks | TaskDetail | Calendar | Timeline) => {
+                "remove blocking task"
+            }
+            (Action::Mark, Tasks) => "mark task",
+            (Action::VisualMode, Tasks) => "mark range",
+            (Action::Export, Tasks) => "export project",
+            (Action::Import, Tasks) => "import tasks",
+            (Action::Refresh, Tasks | TaskDetail | Inbox | Calendar | Timeline) => "refresh",
+            (Action::OpenLink, Tasks | TaskDetail | Inbox | Calendar | Timeline) => {
+                "open task in browser"
+            }
+            (Action::CopyLink, Tasks | TaskDetail | Inbox | Calendar | Timeline) => {
+                "copy task link"
+            }
+            (Action::OpenLink, Menu) => "open project in browser",
+            (Action::CopyLink, Menu) => "copy project link",
+            _ => return None,
+        };
+        Some(description)
+    }
+}
+
+/// Specifying the default bindings, in the order they are shown in hints.
+///
+const DEFAULT_BINDINGS: &[(&str, Action)] = &[
+    ("j", Action::NextItem),
+    ("k", Action::PreviousItem),
+    ("h", Action::PreviousMenu),
+    ("l", Action::NextMenu),
+    ("down", Action::NextItem),
+    ("up", Action::PreviousItem),
+    ("left", Action::PreviousMenu),
+    ("right", Action::NextMenu),
+    ("tab", Action::NextPanel),
+    ("shift-tab", Action::PreviousPanel),
+    ("g g", Action::FirstItem),
+    ("G", Action::LastItem),
+    ("enter", Action::Select),
+    ("space", Action::Mark),
+    ("V", Action::VisualMode),
+    ("c", Action::Complete),
+    ("e", Action::Rename),
+    ("m", Action::Comment),
+    ("d", Action::DueDate),
+    ("a", Action::Assign),
+    ("s", Action::Section),
+    ("t", Action::Tag),
+    ("D", Action::Delete),
+    ("b", Action::AddDependency),
+    ("B", Action::RemoveDependency),
+    ("x", Action::Export),
+    ("i", Action::Import),
+    ("u", Action::Undo),
+    ("ctrl-r", Action::Redo),
+    ("r", Action::Refresh),
+    ("C", Action::Calendar),
+    ("P", Action::Timeline),
+    ("ctrl-t", Action::Team),
+    ("o", Action::OpenLink),
+    ("y", Action::CopyLink),
+    ("T", Action::NextTheme),
+    ("?", Action::Help),
+    (":", Action::Command),
+    ("<", Action::ShrinkSidebar),
+    (">", Action::GrowSidebar),
+    ("-", Action::ShrinkLog),
+    ("+", Action::GrowLog),
+    ("L", Action::ToggleLog),
+    ("Z", Action::ZoomLog),
+    ("/", Action::Search),
+    ("pageup", Action::PageUp),
+    ("pagedown", Action::PageDown),
+    ("esc", Action::Cancel),
+    ("q", Action::Quit),
+    ("ctrl-c", Action::Quit),
+];
+
+/// Houses a single key press, ignoring shift for characters since it is
+/// already reflected by their case.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Parse a key from its configuration name, e.g. `j`, `G`, `esc` or
+    /// `ctrl-r`.
+    ///
+    pub fn parse(name: &str) -> Result<Key> {
+        let (modifiers, name) = match name.strip_prefix("ctrl-") {
+            Some(name) => (KeyModifiers::CONTROL, name),
+            None => (KeyModifiers::NONE, name),
+        };
+        let code = match name {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "shift-tab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(anyhow!("Failed to parse key '{}'", name)),
+                }
+            }
+        };
+        Ok(Key { code, modifiers })
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Key {
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        Key {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "^")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "shift-tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// Specifying the outcome of looking up a key sequence.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    Action(Action),
+    Partial,
+    Unbound,
+}
+
+/// Specifying the keys bound to an action in the configuration file, either
+/// a single sequence or a list of them.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Binding {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Maps key sequences to actions.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(Vec<Key>, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap {
+            bindings: DEFAULT_BINDINGS
+                .iter()
+                .map(|(keys, action)| (parse_sequence(keys).unwrap(), *action))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Return the default keymap with the bindings for each overridden
+    /// action replaced, along with any default binding of the same keys.
+    /// Sequences are keys separated by spaces, e.g. `g g`. Returns an error
+    /// if two overridden actions are bound to the same keys.
+    ///
+    pub fn with_overrides(overrides: &HashMap<Action, Binding>) -> Result<Keymap> {
+        let mut bindings: Vec<(Vec<Key>, Action)> = vec![];
+        for (action, binding) in overrides {
+            let sequences = match binding {
+                Binding::One(sequence) => vec![sequence.as_str()],
+                Binding::Many(sequences) => sequences.iter().map(|s| s.as_str()).collect(),
+            };
+            for sequence in sequences {
+                let keys = parse_sequence(sequence)?;
+                if let Some((_, other)) = bindings.iter().find(|(k, a)| *k == keys && a != action) {
+                    return Err(anyhow!(
+                        "Keys '{}' are bound to both {} and {}",
+                        sequence,
+                        serde_json::to_string(other)?,
+                        serde_json::to_string(action)?
+                    ));
+                }
+                bindings.push((keys, *action));
+            }
+        }
+        let mut keymap = Keymap::default();
+        keymap.bindings.retain(|(keys, action)| {
+            !overrides.contains_key(action) && !bindings.iter().any(|(k, _)| k == keys)
+        });
+        keymap.bindings.extend(bindings);
+        Ok(keymap)
+    }
+
+    /// Look up the given key sequence, returning its action, whether it is
+    /// the start of a longer sequence, or that nothing is bound to it.
+    ///
+    pub fn resolve(&self, sequence: &[Key]) -> Resolution {
+        if let Some((_, action)) = self.bindings.iter().find(|(keys, _)| keys == sequence) {
+            return Resolution::Action(*action);
+        }
+        match self
+            .bindings
+            .iter()
+            .any(|(keys, _)| keys.starts_with(sequence))
+        {
+            true => Resolution::Partial,
+            false => Resolution::Unbound,
+        }
+    }
+
+    /// Return the first key sequence bound to the given action for display.
+    ///
+    pub fn keys(&self, action: Action) -> Option<String> {
+        self.bindings
+            .iter()
+            .find(|(_, a)| *a == action)
+            .map(|(keys, _)| keys.iter().map(|k| k.to_string()).collect())
+    }
+
+    /// Return hint text describing each group of actions, skipping actions
+    /// which are unbound.
+    ///
+    pub fn hint(&self, groups: &[(&[Action], &str)]) -> String {
+        groups
+            .iter()
+            .filter_map(|(actions, description)| {
+                let keys: Vec<String> = actions.iter().filter_map(|a| self.keys(*a)).collect();
+                match keys.is_empty() {
+                    true => None,
+                    false => Some(format!("{}: {}", keys.join(" "), description)),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Return the keys and description of every action available in the
+    /// given context, in the order they are bound.
+    ///
+    pub fn help(&self, context: Context) -> Vec<(String, &'static str)> {
+        let mut actions: Vec<Action> = vec![];
+        for (_, action) in &self.bindings {
+            if !actions.contains(action) {
+                actions.push(*action);
+            }
+        }
+        actions
+            .into_iter()
+            .filter_map(|action| {
+                let description = action.describe(context)?;
+                let keys: Vec<String> = self
+                    .bindings
+                    .iter()
+                    .filter(|(_, a)| *a == action)
+                    .map(|(keys, _)| keys.iter().map(|k| k.to_string()).collect())
+                    .collect();
+                Some((keys.join(", "), description))
+            })
+            .collect()
+    }
+}
+
+fn parse_sequence(sequence: &str) -> Result<Vec<Key>> {
+    let keys = sequence
+        .split_whitespace()
+        .map(Key::parse)
+        .collect::<Result<Vec<Key>>>()?;
+    match keys.is_empty() {
+        true => Err(anyhow!("Failed to parse empty key sequence")),
+        false => Ok(keys),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> Key {
+        Key {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(Key::parse("j").unwrap(), key('j'));
+        assert_eq!(
+            Key::parse("ctrl-r").unwrap(),
+            Key {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+            }
+        );
+        assert_eq!(Key::parse("space").unwrap(), key(' '));
+        assert_eq!(Key::parse("esc").unwrap().code, KeyCode::Esc);
+        assert!(Key::parse("jk").is_err());
+        assert!(parse_sequence("  ").is_err());
+    }
+
+    #[test]
+    fn key_from_event_ignores_shift() {
+        let event = KeyEvent {
+            code: KeyCode::Char('G'),
+            modifiers: KeyModifiers::SHIFT,
+        };
+        assert_eq!(Key::from(event), key('G'));
+    }
+
+    #[test]
+    fn key_from_event_ignores_shift_for_back_tab() {
+        let event = KeyEvent {
+            code: KeyCode::BackTab,
+            modifiers: KeyModifiers::SHIFT,
+        };
+        assert_eq!(Key::from(event), Key::parse("shift-tab").unwrap());
+        assert_eq!(Key::from(event).to_string(), "shift-tab");
+    }
+
+    #[test]
+    fn resolve_defaults() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.resolve(&[key('j')]),
+            Resolution::Action(Action::NextItem)
+        );
+        assert_eq!(
+            keymap.resolve(&[Key::parse("down").unwrap()]),
+            Resolution::Action(Action::NextItem)
+        );
+        assert_eq!(keymap.resolve(&[key('g')]), Resolution::Partial);
+        assert_eq!(
+            keymap.resolve(&[key('g'), key('g')]),
+            Resolution::Action(Action::FirstItem)
+        );
+        assert_eq!(keymap.resolve(&[key('z')]), Resolution::Unbound);
+    }
+
+    #[test]
+    fn with_overrides() {
+        let overrides = HashMap::from([
+            (
+                Action::NextItem,
+                Binding::Many(vec!["n".into(), "down".into()]),
+            ),
+            (Action::Quit, Binding::One("Z Z".into())),
+        ]);
+        let keymap = Keymap::with_overrides(&overrides).unwrap();
+        assert_eq!(keymap.resolve(&[key('j')]), Resolution::Unbound);
+        assert_eq!(keymap.resolve(&[key('q')]), Resolution::Unbound);
+        assert_eq!(
+            keymap.resolve(&[key('n')]),
+            Resolution::Action(Action::NextItem)
+        );
+        assert_eq!(
+            keymap.resolve(&[key('Z'), key('Z')]),
+            Resolution::Action(Action::Quit)
+        );
+        assert_eq!(keymap.keys(Action::Quit), Some(String::from("ZZ")));
+        let invalid = HashMap::from([(Action::Quit, Binding::One("ctrl-".into()))]);
+        assert!(Keymap::with_overrides(&invalid).is_err());
+    }
+
+    #[test]
+    fn with_overrides_of_default_keys() {
+        let overrides = HashMap::from([(Action::Refresh, Binding::One("j".into()))]);
+        let keymap = Keymap::with_overrides(&overrides).unwrap();
+        assert_eq!(
+            keymap.resolve(&[key('j')]),
+            Resolution::Action(Action::Refresh)
+        );
+        assert_eq!(keymap.keys(Action::NextItem), Some(String::from("down")));
+        let conflicting = HashMap::from([
+            (Action::Refresh, Binding::One("j".into())),
+            (Action::Quit, Binding::One("j".into())),
+        ]);
+        assert!(Keymap::with_overrides(&conflicting).is_err());
+    }
+
+    #[test]
+    fn hint() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.hint(&[
+                (&[Action::NextItem, Action::PreviousItem], "navigate"),
+                (&[Action::Redo], "redo"),
+            ]),
+            "j k: navigate, ^r: redo"
+        );
+    }
+
+    #[test]
+    fn help() {
+        let keymap = Keymap::default();
+        let help = keymap.help(Context::Tasks);
+        assert_eq!(help[0], (String::from("j, down"), "next item"));
+        assert!(help.contains(&(String::from("gg"), "first item")));
+        assert!(help.contains(&(String::from("q, ^c"), "quit")));
+        assert!(!help.iter().any(|(_, d)| *d == "next menu"));
+        let help = keymap.help(Context::PendingChanges);
+        assert!(help.contains(&(String::from("d"), "discard change")));
+        assert!(!help.iter().any(|(_, d)| *d == "rename"));
+        let help = keymap.help(Context::Log);
+        assert!(help.contains(&(String::from("h, left"), "show less of target")));
+        assert!(help.contains(&(String::from("pageup"), "previous page")));
+        assert!(!help.iter().any(|(_, d)| *d == "lengthen log"));
+    }
+}
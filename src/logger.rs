@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Result};
+use log::LevelFilter;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tui_logger::{init_logger, set_default_level, set_log_file};
+
+const FILE_NAME: &str = "asana-tui.log";
+
+/// Specifying the size in bytes beyond which the log file is rotated.
+///
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Specifying how many rotated log files are kept besides the current one.
+///
+const ROTATED_FILE_COUNT: usize = 3;
+
+/// Initialize the logger, writing records to a log file in the given
+/// directory which is rotated once grown too large. Returns the path of the
+/// log file.
+///
+pub fn init(dir_path: &Path) -> Result<PathBuf> {
+    init_logger(LevelFilter::Info).map_err(|e| anyhow!("Failed to initialize logger: {}", e))?;
+    set_default_level(LevelFilter::Trace);
+    let file_path = dir_path.join(Path::new(FILE_NAME));
+    rotate(&file_path)?;
+    set_log_file(&file_path.to_string_lossy())?;
+    Ok(file_path)
+}
+
+/// Rotate the log file if it has grown too large since the last check,
+/// continuing to log into a new file at the same path.
+///
+pub fn maintain(file_path: &Path) -> Result<()> {
+    if rotate(file_path)? {
+        set_log_file(&file_path.to_string_lossy())?;
+    }
+    Ok(())
+}
+
+/// Return the lines of the log file containing the query, ignoring case,
+/// from oldest to newest.
+///
+pub fn search(file_path: &Path, query: &str) -> Result<Vec<String>> {
+    let query = query.to_lowercase();
+    let lines = match file_path.exists() {
+        true => fs::read_to_string(file_path)?
+            .lines()
+            .filter(|line| line.to_lowercase().contains(&query))
+            .map(|line| line.to_owned())
+            .collect(),
+        false => vec![],
+    };
+    Ok(lines)
+}
+
+/// Move the log file to `<file>.1` if it exceeds the maximum size, shifting
+/// older rotations along and discarding the oldest. Returns whether the file
+/// was rotated.
+///
+fn rotate(file_path: &Path) -> Result<bool> {
+    let size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+    if size <= MAX_FILE_SIZE {
+        return Ok(false);
+    }
+    for index in (1..ROTATED_FILE_COUNT).rev() {
+        let rotated_path = rotation(file_path, index);
+        if rotated_path.exists() {
+            fs::rename(&rotated_path, rotation(file_path, index + 1))?;
+        }
+    }
+    fs::rename(file_path, rotation(file_path, 1))?;
+    Ok(true)
+}
+
+fn rotation(file_path: &Path, index: usize) -> PathBuf {
+    let mut file_name = file_path.as_os_str().to_owned();
+    file_name.push(format!(".{}", index));
+    PathBuf::from(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotate_large_file() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join(FILE_NAME);
+        fs::write(&file_path, "small")?;
+        assert!(!rotate(&file_path)?);
+        for index in 0..=ROTATED_FILE_COUNT {
+            fs::write(
+                &file_path,
+                vec![b'a' + index as u8; MAX_FILE_SIZE as usize + 1],
+            )?;
+            assert!(rotate(&file_path)?);
+        }
+        assert!(!file_path.exists());
+        assert_eq!(fs::read(rotation(&file_path, 1))?[0], b'a' + 3);
+        assert_eq!(fs::read(rotation(&file_path, 3))?[0], b'a' + 1);
+        assert!(!rotation(&file_path, 4).exists());
+        Ok(())
+    }
+
+    #[test]
+    fn search_lines() -> Result<()> {
+        let dir = tempdir()?;
+        let file_path = dir.path().join(FILE_NAME);
+        assert!(search(&file_path, "task")?.is_empty());
+        fs::write(
+            &file_path,
+            "INFO:Fetching tasks...\nERROR:Failed to fetch Tasks\nDEBUG:Tick\n",
+        )?;
+        assert_eq!(
+            search(&file_path, "TASK")?,
+            vec!["INFO:Fetching tasks...", "ERROR:Failed to fetch Tasks"]
+        );
+        Ok(())
+    }
+}
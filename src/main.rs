@@ -1,7 +1,20 @@
 mod app;
 mod asana;
+mod cli;
+mod command;
 mod config;
+mod date;
 mod events;
+mod export;
+mod history;
+mod import;
+mod inbox;
+mod journal;
+mod keymap;
+mod link;
+mod logger;
+mod notify;
+mod persisted;
 mod state;
 mod ui;
 
@@ -10,7 +23,8 @@ use app::App;
 use clap::{App as ClapApp, Arg};
 use config::Config;
 
-/// Parse command and start app with corresponding configuration.
+/// Parse command and either run the given subcommand or start app with
+/// corresponding configuration.
 ///
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -24,12 +38,16 @@ async fn main() -> Result<()> {
                 .long("config")
                 .help("Specify path to configuration file.")
                 .takes_value(true),
-        );
+        )
+        .subcommands(cli::subcommands());
 
     let matches = clap_app.get_matches();
     let mut config = Config::new();
     config.load(matches.value_of("config"))?;
 
-    App::start(config).await?;
+    match matches.subcommand() {
+        (_, Some(_)) => cli::run(config, &matches).await?,
+        _ => App::start(config).await?,
+    }
     Ok(())
 }
@@ -0,0 +1,237 @@
+use crate::persisted::PersistedList;
+use anyhow::{anyhow, Result};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+const FILE_NAME: &str = "notified.yml";
+
+/// Specifying the placeholder in command arguments replaced by the
+/// notification message.
+///
+const MESSAGE_PLACEHOLDER: &str = "{message}";
+
+/// Specifying the shortest time between checks, keeping within Asana's rate
+/// limits.
+///
+const MIN_INTERVAL_IN_SECS: u64 = 60;
+
+/// Specifying how many raised notifications are remembered, well beyond
+/// those found by a single check.
+///
+const LIMIT: usize = 1000;
+
+/// Houses the notification settings from the configuration file, with an
+/// opt-in for each kind of notification, all off by default.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Notifications {
+    pub due_soon: bool,
+    pub assignments: bool,
+    pub mentions: bool,
+    pub bell: bool,
+    pub interval: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+}
+
+impl Default for Notifications {
+    fn default() -> Notifications {
+        Notifications {
+            due_soon: false,
+            assignments: false,
+            mentions: false,
+            bell: false,
+            interval: 300,
+            command: vec![],
+        }
+    }
+}
+
+impl Notifications {
+    /// Return true if any kind of notification is enabled.
+    ///
+    pub fn is_enabled(&self) -> bool {
+        self.due_soon || self.assignments || self.mentions
+    }
+
+    /// Return the number of seconds between checks, being at least a minute.
+    ///
+    pub fn interval(&self) -> u64 {
+        self.interval.max(MIN_INTERVAL_IN_SECS)
+    }
+
+    /// Return true if the given kind of notification is enabled.
+    ///
+    pub fn allows(&self, kind: Kind) -> bool {
+        match kind {
+            Kind::DueSoon => self.due_soon,
+            Kind::Assignment => self.assignments,
+            Kind::Mention => self.mentions,
+        }
+    }
+}
+
+/// Specifying the different kinds of notifications.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    DueSoon,
+    Assignment,
+    Mention,
+}
+
+/// Houses a notification, identified by a key so it is raised only once.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: Kind,
+    pub key: String,
+    pub message: String,
+}
+
+/// Oversees which notifications were raised, persisting them to disk so they
+/// are not raised again in later sessions.
+///
+#[derive(Debug)]
+pub struct Notified {
+    keys: PersistedList,
+}
+
+impl Default for Notified {
+    fn default() -> Notified {
+        Notified {
+            keys: PersistedList::new(LIMIT),
+        }
+    }
+}
+
+impl Notified {
+    /// Return the raised notifications persisted in the given directory or
+    /// none if none exist yet.
+    ///
+    pub fn load(dir_path: &Path) -> Result<Notified> {
+        Ok(Notified {
+            keys: PersistedList::load(dir_path, FILE_NAME, LIMIT)?,
+        })
+    }
+
+    /// Record the notification with the given key as raised, forgetting the
+    /// oldest beyond the limit. Returns false if it was raised before.
+    ///
+    pub fn insert(&mut self, key: &str) -> bool {
+        if self.keys.contains(key) {
+            return false;
+        }
+        self.keys.push(key);
+        true
+    }
+
+    /// Write the raised notifications to the disk.
+    ///
+    pub fn save(&self) -> Result<()> {
+        self.keys.save()
+    }
+}
+
+/// Return a single message for the notifications raised together, being the
+/// newest one's message preceded by how many were raised.
+///
+pub fn summarize(notifications: &[Notification]) -> Option<String> {
+    let newest = notifications.last()?;
+    match notifications.len() {
+        1 => Some(newest.message.to_owned()),
+        count => Some(format!(
+            "{} new notifications, latest: {}",
+            count, newest.message
+        )),
+    }
+}
+
+/// Return true if a task due on the given date is due by the end of the day
+/// after today, including overdue tasks.
+///
+pub fn is_due_soon(due_on: &str, today: NaiveDate) -> bool {
+    match NaiveDate::parse_from_str(due_on, "%Y-%m-%d") {
+        Ok(due_on) => due_on <= today + Duration::days(1),
+        Err(_) => false,
+    }
+}
+
+/// Run the configured command for the message without waiting for it,
+/// replacing the message placeholder in its arguments.
+///
+pub fn run_command(command: &[String], message: &str) -> Result<()> {
+    let (program, args) = command
+        .split_first()
+        .ok_or(anyhow!("Missing notification command"))?;
+    let mut child = Command::new(program)
+        .args(
+            args.iter()
+                .map(|arg| arg.replace(MESSAGE_PLACEHOLDER, message)),
+        )
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn due_soon() {
+        let today = NaiveDate::from_ymd(2022, 1, 31);
+        assert!(is_due_soon("2022-01-20", today));
+        assert!(is_due_soon("2022-01-31", today));
+        assert!(is_due_soon("2022-02-01", today));
+        assert!(!is_due_soon("2022-02-02", today));
+        assert!(!is_due_soon("soon", today));
+    }
+
+    #[test]
+    fn parse_settings() {
+        let notifications: Notifications = serde_yaml::from_str(
+            "mentions: true\ninterval: 5\ncommand: [notify-send, '{message}']",
+        )
+        .unwrap();
+        assert!(!notifications.due_soon);
+        assert!(notifications.allows(Kind::Mention));
+        assert_eq!(notifications.interval(), MIN_INTERVAL_IN_SECS);
+        assert_eq!(notifications.command, vec!["notify-send", "{message}"]);
+        assert!(!Notifications::default().is_enabled());
+    }
+
+    #[test]
+    fn load_notified() -> Result<()> {
+        let dir = tempdir()?;
+        let mut notified = Notified::load(dir.path())?;
+        assert!(notified.insert("1"));
+        assert!(!notified.insert("1"));
+        notified.save()?;
+        assert!(!Notified::load(dir.path())?.insert("1"));
+        Ok(())
+    }
+
+    #[test]
+    fn summarize_burst() {
+        let notification = |message: &str| Notification {
+            kind: Kind::Mention,
+            key: message.to_owned(),
+            message: message.to_owned(),
+        };
+        assert_eq!(summarize(&[]), None);
+        assert_eq!(summarize(&[notification("a")]), Some(String::from("a")));
+        assert_eq!(
+            summarize(&[notification("a"), notification("b")]),
+            Some(String::from("2 new notifications, latest: b"))
+        );
+    }
+}
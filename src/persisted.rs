@@ -0,0 +1,100 @@
+use anyhow::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Oversees a list of entries persisted to a file in the configuration
+/// directory, remembering only the newest entries up to a limit.
+///
+#[derive(Debug)]
+pub struct PersistedList {
+    file_path: Option<PathBuf>,
+    limit: usize,
+    entries: Vec<String>,
+}
+
+impl PersistedList {
+    /// Return a new empty list which is not persisted.
+    ///
+    pub fn new(limit: usize) -> PersistedList {
+        PersistedList {
+            file_path: None,
+            limit,
+            entries: vec![],
+        }
+    }
+
+    /// Return the list persisted in the named file of the given directory,
+    /// or a new empty list if the file does not exist yet.
+    ///
+    pub fn load(dir_path: &Path, file_name: &str, limit: usize) -> Result<PersistedList> {
+        let file_path = dir_path.join(Path::new(file_name));
+        let entries = match file_path.exists() {
+            true => serde_yaml::from_str(&fs::read_to_string(&file_path)?)?,
+            false => vec![],
+        };
+        Ok(PersistedList {
+            file_path: Some(file_path),
+            limit,
+            entries,
+        })
+    }
+
+    /// Return all entries from oldest to newest.
+    ///
+    pub fn entries(&self) -> &Vec<String> {
+        &self.entries
+    }
+
+    /// Return true if the entry is listed.
+    ///
+    pub fn contains(&self, entry: &str) -> bool {
+        self.entries.iter().any(|e| e == entry)
+    }
+
+    /// Add the entry as the newest, forgetting the oldest beyond the limit.
+    ///
+    pub fn push(&mut self, entry: &str) {
+        self.entries.push(entry.to_owned());
+        if self.entries.len() > self.limit {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Remove the entry wherever it is listed.
+    ///
+    pub fn remove(&mut self, entry: &str) {
+        self.entries.retain(|e| e != entry);
+    }
+
+    /// Write the entries to the disk if the list has a file path.
+    ///
+    pub fn save(&self) -> Result<()> {
+        if let Some(file_path) = &self.file_path {
+            fs::write(file_path, serde_yaml::to_string(&self.entries)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn load_saved() -> Result<()> {
+        let dir = tempdir()?;
+        let mut list = PersistedList::load(dir.path(), "list.yml", 2)?;
+        list.push("1");
+        list.push("2");
+        list.push("3");
+        list.remove("2");
+        list.save()?;
+        let loaded = PersistedList::load(dir.path(), "list.yml", 2)?;
+        assert_eq!(loaded.entries(), &vec![String::from("3")]);
+        assert!(!loaded.contains("1"));
+        Ok(())
+    }
+}
@@ -1,13 +1,31 @@
 use crate::app::NetworkEventSender;
-use crate::asana::{Project, Task, User, Workspace};
+use crate::asana::{
+    InboxItem, Mutation, Project, RelatedTask, Section, Tag, Task, TaskDetail, Team, User,
+    UserTaskList, Workspace,
+};
+use crate::command::{self, Command, COMMANDS};
+use crate::date;
 use crate::events::network::Event as NetworkEvent;
-use crate::ui::SPINNER_FRAME_COUNT;
+use crate::export;
+use crate::history::History;
+use crate::import::{Batch, Status as ImportStatus};
+use crate::inbox::Inbox;
+use crate::journal::{Entry, Journal};
+use crate::keymap::{Action, Context, Key, Keymap, Resolution, INPUT_BINDINGS};
+use crate::logger;
+use crate::notify::{Notification, Notifications, Notified};
+use crate::ui::theme::{ColorSupport, Theme};
+use crate::ui::{Panels, SPINNER_FRAME_COUNT};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
 use log::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use tui::layout::Rect;
+use tui_logger::{TuiWidgetEvent, TuiWidgetState};
 
 /// Specifying the different foci.
 ///
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Focus {
     Menu,
     View,
@@ -28,14 +46,180 @@ pub enum Menu {
 pub enum View {
     Welcome,
     MyTasks,
+    Inbox,
     RecentlyModified,
     RecentlyCompleted,
     ProjectTasks,
+    PendingChanges,
+    ImportPreview,
+    TaskDetail,
+    Calendar,
+    Timeline,
+}
+
+/// Specifying the different text inputs.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    Rename,
+    Comment,
+    DueDate,
+    Export,
+    Import,
+    Assign,
+    Section,
+    Tag,
+    Delete,
+    Command,
+    Search,
+    AddDependency,
+    RemoveDependency,
+    Team,
+}
+
+impl InputKind {
+    /// Return true if the input picks a task from those matching the text
+    /// entered.
+    ///
+    pub fn picks_task(&self) -> bool {
+        matches!(self, InputKind::AddDependency | InputKind::RemoveDependency)
+    }
+}
+
+/// Houses the text being entered for an input.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    pub kind: InputKind,
+    pub buffer: String,
+}
+
+/// Houses the scroll position and search query of the help overlay.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Help {
+    pub scroll: usize,
+    pub query: String,
+    pub searching: bool,
+}
+
+/// Specifying how the log panel is shown.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogDisplay {
+    Hidden,
+    Normal,
+    Zoomed,
+}
+
+/// Houses the query and matching lines of a search through the log file.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogSearch {
+    pub query: String,
+    pub matches: Vec<String>,
+}
+
+/// Specifying the spans of days shown in the calendar.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalendarSpan {
+    Week,
+    Month,
+}
+
+/// Houses the day selected in the calendar, the task selected on it and the
+/// task being moved to another day, if any.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    pub span: CalendarSpan,
+    pub date: NaiveDate,
+    pub index: usize,
+    pub moving_task_gid: Option<String>,
+}
+
+/// Specifying how long a period each column of the timeline shows.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelineZoom {
+    Day,
+    Week,
+    Month,
+}
+
+impl TimelineZoom {
+    /// Return the first day of the column containing the given date.
+    ///
+    pub fn align(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            TimelineZoom::Day => date,
+            TimelineZoom::Week => date::week_start(date),
+            TimelineZoom::Month => date.with_day(1).unwrap(),
+        }
+    }
+
+    /// Return the date the given number of columns after the given date.
+    ///
+    pub fn offset(&self, date: NaiveDate, columns: i32) -> NaiveDate {
+        match self {
+            TimelineZoom::Day => date + Duration::days(columns as i64),
+            TimelineZoom::Week => date + Duration::weeks(columns as i64),
+            TimelineZoom::Month => date::add_months(date, columns),
+        }
+    }
+
+    /// Return the index of the column containing the given date, counting
+    /// from the column starting on the given start date.
+    ///
+    pub fn column(&self, start: NaiveDate, date: NaiveDate) -> i64 {
+        match self {
+            TimelineZoom::Day => (date - start).num_days(),
+            TimelineZoom::Week => (date - start).num_days().div_euclid(7),
+            TimelineZoom::Month => {
+                let months = |d: NaiveDate| d.year() as i64 * 12 + d.month0() as i64;
+                months(date) - months(start)
+            }
+        }
+    }
+}
+
+/// Houses the zoom level and first column shown by the timeline and the
+/// task selected in it.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timeline {
+    pub zoom: TimelineZoom,
+    pub start: NaiveDate,
+    pub index: usize,
 }
 
 /// Specifying the different shortcuts.
 ///
-pub const SHORTCUTS: [&str; 3] = ["My Tasks", "Recently Modified", "Recently Completed"];
+pub const SHORTCUTS: [&str; 5] = [
+    "My Tasks",
+    "Inbox",
+    "Recently Modified",
+    "Recently Completed",
+    "Pending Changes",
+];
+
+/// Specifying how many changes can be undone.
+///
+const UNDO_LIMIT: usize = 50;
+
+/// Specifying how long a message is shown after an action.
+///
+const MESSAGE_DURATION_IN_SECS: i64 = 5;
+
+/// Houses mutations made together alongside those which reverse them.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Change {
+    summary: String,
+    mutations: Vec<Mutation>,
+    inverses: Vec<Mutation>,
+}
 
 /// Houses data representative of application state.
 ///
@@ -50,10 +234,57 @@ pub struct State {
     current_menu: Menu,
     current_shortcut_index: usize,
     current_top_list_index: usize,
+    current_view_index: usize,
     view_stack: Vec<View>,
     tasks: Vec<Task>,
+    detail_task_gid: Option<String>,
+    task_detail: Option<TaskDetail>,
+    detail_index: usize,
+    calendar: Option<Calendar>,
+    timeline: Option<Timeline>,
+    inbox: Inbox,
+    inbox_items: Vec<InboxItem>,
+    notifications: Notifications,
+    notified: Notified,
+    notification_count: usize,
+    notifications_checked_at: Option<DateTime<Utc>>,
+    bell_pending: bool,
+    workspace_projects: Vec<Project>,
     projects: Vec<Project>,
     project: Option<Project>,
+    teams: Vec<Team>,
+    team: Option<Team>,
+    my_teams_only: bool,
+    sections: Vec<Section>,
+    user_task_list: Option<UserTaskList>,
+    tags: Vec<Tag>,
+    marked_task_gids: Vec<String>,
+    visual_anchor: Option<usize>,
+    bulk_progress: Option<(usize, usize)>,
+    undo_stack: Vec<Change>,
+    redo_stack: Vec<Change>,
+    message: Option<(String, DateTime<Utc>)>,
+    keymap: Keymap,
+    pending_keys: Vec<Key>,
+    help: Option<Help>,
+    theme: Theme,
+    color_support: ColorSupport,
+    panels: Panels,
+    log_display: LogDisplay,
+    log_widget: TuiWidgetState,
+    log_file_path: Option<PathBuf>,
+    export_directory: PathBuf,
+    log_search: Option<LogSearch>,
+    journal: Journal,
+    history: History,
+    history_index: Option<usize>,
+    input: Option<Input>,
+    picker_index: usize,
+    import: Option<Batch>,
+    offline: bool,
+    refresh_interval: Option<Duration>,
+    refresh_requested_at: Option<DateTime<Utc>>,
+    last_updated: Option<DateTime<Utc>>,
 }
 
 /// Defines default application state.
@@ -71,18 +302,66 @@ impl Default for State {
             current_menu: Menu::Shortcuts,
             current_shortcut_index: 0,
             current_top_list_index: 0,
+            current_view_index: 0,
             view_stack: vec![View::Welcome],
             tasks: vec![],
+            detail_task_gid: None,
+            task_detail: None,
+            detail_index: 0,
+            calendar: None,
+            timeline: None,
+            inbox: Inbox::default(),
+            inbox_items: vec![],
+            notifications: Notifications::default(),
+            notified: Notified::default(),
+            notification_count: 0,
+            notifications_checked_at: None,
+            bell_pending: false,
+            workspace_projects: vec![],
             projects: vec![],
             project: None,
+            teams: vec![],
+            team: None,
+            my_teams_only: false,
+            sections: vec![],
+            user_task_list: None,
+            tags: vec![],
+            marked_task_gids: vec![],
+            visual_anchor: None,
+            bulk_progress: None,
+            undo_stack: vec![],
+            redo_stack: vec![],
+            message: None,
+            keymap: Keymap::default(),
+            pending_keys: vec![],
+            help: None,
+            theme: Theme::default(),
+            color_support: ColorSupport::TrueColor,
+            panels: Panels::default(),
+            log_display: LogDisplay::Normal,
+            log_widget: TuiWidgetState::new(),
+            log_file_path: None,
+            export_directory: PathBuf::from("."),
+            log_search: None,
+            journal: Journal::default(),
+            history: History::default(),
+            history_index: None,
+            picker_index: 0,
+            input: None,
+            import: None,
+            offline: false,
+            refresh_interval: None,
+            refresh_requested_at: None,
+            last_updated: None,
         }
     }
 }
 
 impl State {
-    pub fn new(net_sender: NetworkEventSender) -> Self {
+    pub fn new(net_sender: NetworkEventSender, journal: Journal) -> Self {
         State {
             net_sender: Some(net_sender),
+            journal,
             ..State::default()
         }
     }
@@ -134,6 +413,12 @@ impl State {
         self
     }
 
+    /// Returns the terminal size.
+    ///
+    pub fn get_terminal_size(&self) -> &Rect {
+        &self.terminal_size
+    }
+
     /// Advance the spinner index.
     ///
     pub fn advance_spinner_index(&mut self) -> &mut Self {
@@ -198,11 +483,41 @@ impl State {
         self
     }
 
+    /// Focus the given menu.
+    ///
+    pub fn focus_menu_item(&mut self, menu: Menu) -> &mut Self {
+        self.current_menu = menu;
+        self.current_focus = Focus::Menu;
+        self
+    }
+
+    /// Focus the next panel, moving through the menus before the view.
+    ///
+    pub fn next_panel(&mut self) -> &mut Self {
+        match (self.current_focus, &self.current_menu) {
+            (Focus::Menu, Menu::TopList) => self.focus_view(),
+            (Focus::Menu, _) => self.next_menu(),
+            (Focus::View, _) => self.focus_menu_item(Menu::Status),
+        }
+    }
+
+    /// Focus the previous panel, moving through the menus before the view.
+    ///
+    pub fn previous_panel(&mut self) -> &mut Self {
+        match (self.current_focus, &self.current_menu) {
+            (Focus::Menu, Menu::Status) => self.focus_view(),
+            (Focus::Menu, _) => self.previous_menu(),
+            (Focus::View, _) => self.focus_menu_item(Menu::TopList),
+        }
+    }
+
     /// Activate the status menu.
     ///
     pub fn select_status_menu(&mut self) -> &mut Self {
         self.view_stack.clear();
         self.view_stack.push(View::Welcome);
+        self.current_view_index = 0;
+        self.last_updated = None;
         self
     }
 
@@ -212,6 +527,15 @@ impl State {
         &self.current_shortcut_index
     }
 
+    /// Activate the shortcut at the given index if it exists.
+    ///
+    pub fn set_shortcut_index(&mut self, index: usize) -> &mut Self {
+        if index < SHORTCUTS.len() {
+            self.current_shortcut_index = index;
+        }
+        self
+    }
+
     /// Activate the next shortcut.
     ///
     pub fn next_shortcut_index(&mut self) -> &mut Self {
@@ -239,10 +563,18 @@ impl State {
         self.view_stack.clear();
         match SHORTCUTS[self.current_shortcut_index] {
             "My Tasks" => {
+                self.notification_count = 0;
                 self.tasks.clear();
+                self.sections.clear();
                 self.dispatch(NetworkEvent::MyTasks);
                 self.view_stack.push(View::MyTasks);
             }
+            "Inbox" => {
+                self.notification_count = 0;
+                self.inbox_items.clear();
+                self.dispatch(NetworkEvent::Inbox);
+                self.view_stack.push(View::Inbox);
+            }
             "Recently Modified" => {
                 self.tasks.clear();
                 self.view_stack.push(View::RecentlyModified);
@@ -251,12 +583,27 @@ impl State {
                 self.tasks.clear();
                 self.view_stack.push(View::RecentlyCompleted);
             }
+            "Pending Changes" => {
+                self.view_stack.push(View::PendingChanges);
+            }
             _ => (),
         }
+        self.current_view_index = 0;
+        self.last_updated = None;
+        self.clear_marks();
         self.focus_view();
         self
     }
 
+    /// Activate the top list item at the given index if it exists.
+    ///
+    pub fn set_top_list_index(&mut self, index: usize) -> &mut Self {
+        if index < self.projects.len() {
+            self.current_top_list_index = index;
+        }
+        self
+    }
+
     /// Activate the next top list item.
     ///
     pub fn next_top_list_index(&mut self) -> &mut Self {
@@ -293,8 +640,12 @@ impl State {
         self.project = Some(self.projects[self.current_top_list_index].to_owned());
         self.view_stack.clear();
         self.tasks.clear();
+        self.sections.clear();
+        self.clear_marks();
         self.dispatch(NetworkEvent::ProjectTasks);
         self.view_stack.push(View::ProjectTasks);
+        self.current_view_index = 0;
+        self.last_updated = None;
         self.focus_view();
         self
     }
@@ -305,389 +656,4161 @@ impl State {
         self.view_stack.last().unwrap()
     }
 
-    /// Return the list of tasks.
+    /// Return true if the current view lists tasks.
     ///
-    pub fn get_tasks(&self) -> &Vec<Task> {
-        &self.tasks
+    pub fn is_task_view(&self) -> bool {
+        matches!(
+            self.current_view(),
+            View::MyTasks | View::RecentlyModified | View::RecentlyCompleted | View::ProjectTasks
+        )
     }
 
-    /// Set the list of tasks.
+    /// Return the current view item.
     ///
-    pub fn set_tasks(&mut self, tasks: Vec<Task>) -> &mut Self {
-        self.tasks = tasks;
-        self
+    pub fn current_view_index(&self) -> &usize {
+        &self.current_view_index
     }
 
-    /// Return the list of projects.
+    /// Activate the next view item.
     ///
-    pub fn get_projects(&self) -> &Vec<Project> {
-        &self.projects
+    pub fn next_view_index(&mut self) -> &mut Self {
+        let len = self.view_len();
+        let index = self.view_index_mut();
+        *index += 1;
+        if *index >= len {
+            *index = 0;
+        }
+        self
     }
 
-    /// Set the list of projects.
+    /// Activate the previous view item.
     ///
-    pub fn set_projects(&mut self, projects: Vec<Project>) -> &mut Self {
-        self.projects = projects;
+    pub fn previous_view_index(&mut self) -> &mut Self {
+        let len = self.view_len();
+        let index = self.view_index_mut();
+        if *index > 0 {
+            *index -= 1;
+        } else if len > 0 {
+            *index = len - 1;
+        }
         self
     }
 
-    /// Return the current project.
+    /// Activate the view item shown on the given row of a list with the
+    /// given height, accounting for the list scrolling to keep the current
+    /// item visible.
     ///
-    pub fn get_project(&self) -> Option<&Project> {
-        self.project.as_ref()
+    pub fn set_view_index_at_row(&mut self, row: usize, height: usize) -> &mut Self {
+        if matches!(
+            self.current_view(),
+            View::TaskDetail | View::Calendar | View::Timeline
+        ) {
+            return self;
+        }
+        let headings = self.section_headings();
+        if headings.iter().any(Option::is_some) {
+            // Map rows to tasks around the section headings between them.
+            let rows: Vec<Option<usize>> = headings
+                .iter()
+                .enumerate()
+                .flat_map(|(index, heading)| heading.map(|_| None).into_iter().chain([Some(index)]))
+                .collect();
+            let selected = rows
+                .iter()
+                .position(|r| *r == Some(self.current_view_index))
+                .unwrap_or(0);
+            let offset = selected.saturating_sub(height.saturating_sub(1));
+            if let Some(Some(index)) = rows.get(offset + row) {
+                self.current_view_index = *index;
+            }
+            return self;
+        }
+        let offset = self
+            .current_view_index
+            .saturating_sub(height.saturating_sub(1));
+        if offset + row < self.view_len() {
+            self.current_view_index = offset + row;
+        }
+        self
     }
 
-    /// Dispatches an asynchronous network event.
+    /// Activate the first view item.
     ///
-    fn dispatch(&self, event: NetworkEvent) {
-        if let Some(net_sender) = &self.net_sender {
-            if let Err(err) = net_sender.send(event) {
-                error!("Recieved error from network dispatch: {}", err);
-            }
-        }
+    pub fn first_view_index(&mut self) -> &mut Self {
+        *self.view_index_mut() = 0;
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use fake::uuid::UUIDv4;
-    use fake::{Fake, Faker};
-    use uuid::Uuid;
+    /// Activate the last view item.
+    ///
+    pub fn last_view_index(&mut self) -> &mut Self {
+        let len = self.view_len();
+        *self.view_index_mut() = len.saturating_sub(1);
+        self
+    }
 
-    #[test]
-    fn get_user() {
-        let user: User = Faker.fake();
-        let state = State {
-            user: Some(user.to_owned()),
-            ..State::default()
+    /// Open the detail view of the current task, requesting its notes and
+    /// comments.
+    ///
+    pub fn open_task_detail(&mut self) -> &mut Self {
+        if !self.is_task_view()
+            && !matches!(
+                self.current_view(),
+                View::Inbox | View::Calendar | View::Timeline
+            )
+        {
+            return self;
+        }
+        if let Some(item) = self.current_inbox_item() {
+            let story_gid = item.story_gid.to_owned();
+            self.set_inbox_item_read(&story_gid, true);
+        }
+        let task_gid = match self.current_task() {
+            Some(task) => task.gid.to_owned(),
+            None => return self,
         };
-        assert_eq!(user, *state.get_user().unwrap());
+        self.view_stack.push(View::TaskDetail);
+        self.detail_task_gid = Some(task_gid.to_owned());
+        self.task_detail = None;
+        self.detail_index = 0;
+        self.dispatch(NetworkEvent::TaskDetail(task_gid));
+        self
     }
 
-    #[test]
-    fn set_user() {
-        let mut state = State::default();
-        let user: User = Faker.fake();
-        state.set_user(user.to_owned());
-        assert_eq!(user, state.user.unwrap());
+    /// Close the task detail view, returning to the list of tasks.
+    ///
+    pub fn close_task_detail(&mut self) -> &mut Self {
+        if *self.current_view() == View::TaskDetail {
+            self.view_stack.pop();
+            self.detail_task_gid = None;
+            self.task_detail = None;
+        }
+        self
     }
 
-    #[test]
-    fn get_active_workspace() {
-        let workspaces = vec![
-            Faker.fake::<Workspace>(),
-            Faker.fake::<Workspace>(),
-            Faker.fake::<Workspace>(),
-        ];
-        let active_workspace = workspaces[0].to_owned();
-        let state = State {
-            active_workspace_gid: Some(active_workspace.gid.to_owned()),
-            workspaces,
-            ..State::default()
-        };
-        assert_eq!(active_workspace, *state.get_active_workspace().unwrap());
+    /// Return the notes and comments of the task shown in the detail view,
+    /// or None while they are loading.
+    ///
+    pub fn get_task_detail(&self) -> Option<&TaskDetail> {
+        self.task_detail.as_ref()
     }
 
-    #[test]
-    fn set_active_workspace() {
-        let workspace_gid: Uuid = UUIDv4.fake();
-        let mut state = State::default();
-        state.set_active_workspace(workspace_gid.to_string());
-        assert_eq!(
-            workspace_gid.to_string(),
-            state.active_workspace_gid.unwrap()
-        );
+    /// Set the notes and comments of the task shown in the detail view,
+    /// ignoring those of other tasks.
+    ///
+    pub fn set_task_detail(&mut self, task_detail: TaskDetail) -> &mut Self {
+        if self.detail_task_gid.as_ref() == Some(&task_detail.task_gid) {
+            self.detail_index = self.detail_index.min(task_detail.comments.len());
+            self.task_detail = Some(task_detail);
+        }
+        self
     }
 
-    #[test]
-    fn set_workspaces() {
-        let workspaces = vec![
-            Faker.fake::<Workspace>(),
-            Faker.fake::<Workspace>(),
-            Faker.fake::<Workspace>(),
-        ];
-        let mut state = State::default();
-        state.set_workspaces(workspaces.to_owned());
-        assert_eq!(workspaces, state.workspaces);
+    /// Return true if the detail view is open on the task with the given GID.
+    ///
+    pub fn shows_task_detail(&self, task_gid: &str) -> bool {
+        *self.current_view() == View::TaskDetail
+            && self.detail_task_gid.as_deref() == Some(task_gid)
     }
 
-    #[test]
-    fn set_terminal_size() {
-        let mut state = State::default();
-        let size = Rect::new(Faker.fake(), Faker.fake(), Faker.fake(), Faker.fake());
-        state.set_terminal_size(size);
-        assert_eq!(size, state.terminal_size);
+    /// Return the item of the detail view scrolled to, being the notes
+    /// followed by each comment.
+    ///
+    pub fn get_detail_index(&self) -> &usize {
+        &self.detail_index
     }
 
-    #[test]
-    fn advance_spinner_index() {
-        let mut state = State::default();
-        state.advance_spinner_index();
-        assert_eq!(state.spinner_index, 1);
-        for _ in 0..SPINNER_FRAME_COUNT {
-            state.advance_spinner_index();
+    /// Open the calendar of the listed tasks by due date, starting at the
+    /// current task's due date or else today.
+    ///
+    pub fn open_calendar(&mut self) -> &mut Self {
+        if !self.is_task_view() {
+            return self;
+        }
+        let task_gid = self.current_task().map(|t| t.gid.to_owned());
+        let date = self
+            .current_task()
+            .and_then(|t| t.due_on.as_ref())
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .unwrap_or_else(|| Local::today().naive_local());
+        self.clear_marks();
+        self.view_stack.push(View::Calendar);
+        self.calendar = Some(Calendar {
+            span: CalendarSpan::Month,
+            date,
+            index: 0,
+            moving_task_gid: None,
+        });
+        match task_gid {
+            Some(task_gid) => self.select_calendar_task(&task_gid),
+            None => self,
         }
-        assert_eq!(state.spinner_index, 1);
     }
 
-    #[test]
-    fn get_spinner_index() {
-        let state = State {
-            spinner_index: 2,
-            ..State::default()
-        };
-        assert_eq!(*state.get_spinner_index(), 2);
+    /// Close the calendar, returning to the list of tasks.
+    ///
+    pub fn close_calendar(&mut self) -> &mut Self {
+        if *self.current_view() == View::Calendar {
+            self.view_stack.pop();
+            self.calendar = None;
+        }
+        self
     }
 
-    #[test]
-    fn current_focus() {
-        let mut state = State {
-            current_focus: Focus::Menu,
-            ..State::default()
-        };
-        assert_eq!(*state.current_focus(), Focus::Menu);
-        state.current_focus = Focus::View;
-        assert_eq!(*state.current_focus(), Focus::View);
+    /// Return the calendar position while the calendar is open.
+    ///
+    pub fn get_calendar(&self) -> Option<&Calendar> {
+        self.calendar.as_ref()
     }
 
-    #[test]
-    fn focus_menu() {
-        let mut state = State {
-            current_focus: Focus::View,
-            ..State::default()
+    /// Return the tasks due on the given date, showing the task being moved
+    /// on the selected day instead.
+    ///
+    pub fn calendar_tasks(&self, date: NaiveDate) -> Vec<&Task> {
+        let (selected_date, moving_task_gid) = match &self.calendar {
+            Some(calendar) => (Some(calendar.date), calendar.moving_task_gid.as_ref()),
+            None => (None, None),
         };
-        state.focus_menu();
-        assert_eq!(state.current_focus, Focus::Menu);
+        let due_on = date.to_string();
+        self.tasks
+            .iter()
+            .filter(|t| match Some(&t.gid) == moving_task_gid {
+                true => selected_date == Some(date),
+                false => t.due_on.as_ref() == Some(&due_on),
+            })
+            .collect()
     }
 
-    #[test]
-    fn focus_view() {
-        let mut state = State {
-            current_focus: Focus::Menu,
-            ..State::default()
-        };
-        state.focus_view();
-        assert_eq!(state.current_focus, Focus::View);
+    /// Toggle the calendar between showing a week and a month.
+    ///
+    pub fn toggle_calendar_span(&mut self) -> &mut Self {
+        if let Some(calendar) = self.calendar.as_mut() {
+            calendar.span = match calendar.span {
+                CalendarSpan::Week => CalendarSpan::Month,
+                CalendarSpan::Month => CalendarSpan::Week,
+            };
+        }
+        self
     }
 
-    #[test]
-    fn current_menu() {
-        let state = State {
-            current_menu: Menu::Status,
-            ..State::default()
+    /// Select the day the given number of days after the selected one,
+    /// carrying along the task being moved.
+    ///
+    pub fn move_calendar_days(&mut self, days: i64) -> &mut Self {
+        let date = match &self.calendar {
+            Some(calendar) => calendar.date + Duration::days(days),
+            None => return self,
         };
-        assert_eq!(*state.current_menu(), Menu::Status);
+        self.set_calendar_date(date)
     }
 
-    #[test]
-    fn next_menu() {
-        let mut state = State {
-            current_menu: Menu::Status,
-            ..State::default()
+    /// Select the same day in the next week or month shown, or the previous
+    /// one if not forward.
+    ///
+    pub fn move_calendar_span(&mut self, forward: bool) -> &mut Self {
+        let sign = if forward { 1 } else { -1 };
+        let date = match &self.calendar {
+            Some(calendar) => match calendar.span {
+                CalendarSpan::Week => calendar.date + Duration::weeks(sign as i64),
+                CalendarSpan::Month => date::add_months(calendar.date, sign),
+            },
+            None => return self,
         };
+        self.set_calendar_date(date)
+    }
+
+    /// Select today in the calendar.
+    ///
+    pub fn calendar_today(&mut self) -> &mut Self {
+        self.set_calendar_date(Local::today().naive_local())
+    }
+
+    /// Start moving the selected task to another day or, if already moving
+    /// one, set its due date to the selected day.
+    ///
+    pub fn toggle_calendar_move(&mut self) -> &mut Self {
+        let (date, moving_task_gid) = match &self.calendar {
+            Some(calendar) => (calendar.date, calendar.moving_task_gid.to_owned()),
+            None => return self,
+        };
+        let task_gid = match moving_task_gid {
+            Some(task_gid) => task_gid,
+            None => {
+                let task_gid = self.current_task().map(|t| t.gid.to_owned());
+                if let Some(calendar) = self.calendar.as_mut() {
+                    calendar.moving_task_gid = task_gid;
+                }
+                return self;
+            }
+        };
+        if let Some(calendar) = self.calendar.as_mut() {
+            calendar.moving_task_gid = None;
+        }
+        let due_on = date.to_string();
+        let moved = self
+            .get_task(&task_gid)
+            .map(|t| t.due_on.as_ref() != Some(&due_on))
+            .unwrap_or(false);
+        if moved {
+            self.mutate(Mutation::SetDueDate {
+                task_gid: task_gid.to_owned(),
+                due_on: Some(due_on),
+            });
+        }
+        self.select_calendar_task(&task_gid)
+    }
+
+    /// Stop moving a task, leaving its due date unchanged.
+    ///
+    pub fn cancel_calendar_move(&mut self) -> &mut Self {
+        let task_gid = match self.calendar.as_mut() {
+            Some(calendar) => calendar.moving_task_gid.take(),
+            None => None,
+        };
+        if let Some(task_gid) = task_gid {
+            let date = self
+                .get_task(&task_gid)
+                .and_then(|t| t.due_on.as_ref())
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+            if let Some(date) = date {
+                self.set_calendar_date(date).select_calendar_task(&task_gid);
+            }
+        }
+        self
+    }
+
+    /// Open the timeline of the project's tasks by section, starting a
+    /// week before the current task or else today.
+    ///
+    pub fn open_timeline(&mut self) -> &mut Self {
+        if *self.current_view() != View::ProjectTasks {
+            return self;
+        }
+        let task_gid = self.current_task().map(|t| t.gid.to_owned());
+        let date = self
+            .current_task()
+            .and_then(|t| t.start_on.as_ref().or(t.due_on.as_ref()))
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .unwrap_or_else(|| Local::today().naive_local());
+        let zoom = TimelineZoom::Week;
+        self.clear_marks();
+        self.view_stack.push(View::Timeline);
+        let index = self
+            .timeline_tasks()
+            .iter()
+            .position(|t| Some(&t.gid) == task_gid.as_ref())
+            .unwrap_or(0);
+        self.timeline = Some(Timeline {
+            zoom,
+            start: zoom.offset(zoom.align(date), -1),
+            index,
+        });
+        self
+    }
+
+    /// Close the timeline, returning to the list of tasks.
+    ///
+    pub fn close_timeline(&mut self) -> &mut Self {
+        if *self.current_view() == View::Timeline {
+            self.view_stack.pop();
+            self.timeline = None;
+        }
+        self
+    }
+
+    /// Return the zoom level and position while the timeline is open.
+    ///
+    pub fn get_timeline(&self) -> Option<&Timeline> {
+        self.timeline.as_ref()
+    }
+
+    /// Return the names of the project's sections in order, each with its
+    /// tasks, followed by any tasks not in a known section.
+    ///
+    pub fn timeline_sections(&self) -> Vec<(String, Vec<&Task>)> {
+        let project_gid = self.project.as_ref().map(|p| p.gid.as_str());
+        let section_gid = |task: &Task| {
+            task.memberships
+                .iter()
+                .find(|m| Some(m.project_gid.as_str()) == project_gid)
+                .map(|m| m.section.gid.to_owned())
+        };
+        let mut sections: Vec<(String, Vec<&Task>)> = self
+            .sections
+            .iter()
+            .map(|section| {
+                let tasks = self
+                    .tasks
+                    .iter()
+                    .filter(|t| section_gid(t).as_ref() == Some(&section.gid))
+                    .collect();
+                (section.name.to_owned(), tasks)
+            })
+            .collect();
+        let unsectioned: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|t| match section_gid(t) {
+                Some(gid) => !self.sections.iter().any(|s| s.gid == gid),
+                None => true,
+            })
+            .collect();
+        if !unsectioned.is_empty() {
+            sections.push((String::from("No section"), unsectioned));
+        }
+        sections.retain(|(_, tasks)| !tasks.is_empty());
+        sections
+    }
+
+    /// Switch the timeline to the next zoom level, from days to weeks to
+    /// months and back to days.
+    ///
+    pub fn zoom_timeline(&mut self) -> &mut Self {
+        if let Some(timeline) = self.timeline.as_mut() {
+            timeline.zoom = match timeline.zoom {
+                TimelineZoom::Day => TimelineZoom::Week,
+                TimelineZoom::Week => TimelineZoom::Month,
+                TimelineZoom::Month => TimelineZoom::Day,
+            };
+            timeline.start = timeline.zoom.align(timeline.start);
+        }
+        self
+    }
+
+    /// Scroll the timeline by the given number of columns, back in time if
+    /// negative.
+    ///
+    pub fn scroll_timeline(&mut self, columns: i32) -> &mut Self {
+        if let Some(timeline) = self.timeline.as_mut() {
+            timeline.start = timeline.zoom.offset(timeline.start, columns);
+        }
+        self
+    }
+
+    /// Return the names of known users by GID, used to resolve @-mentions.
+    ///
+    pub fn mention_names(&self) -> HashMap<String, String> {
+        let mut users = self.known_users();
+        if let Some(detail) = &self.task_detail {
+            users.extend(detail.followers.iter().cloned());
+            users.extend(detail.comments.iter().filter_map(|c| c.author.clone()));
+        }
+        users.into_iter().map(|u| (u.gid, u.name)).collect()
+    }
+
+    /// Return the task for the current view item if the view lists tasks.
+    ///
+    pub fn current_task(&self) -> Option<&Task> {
+        match self.current_view() {
+            View::TaskDetail => self
+                .detail_task_gid
+                .as_ref()
+                .and_then(|gid| self.get_task(gid)),
+            View::Inbox => self
+                .current_inbox_item()
+                .and_then(|item| self.get_task(&item.task_gid)),
+            View::Calendar => self
+                .calendar
+                .as_ref()
+                .and_then(|c| self.calendar_tasks(c.date).get(c.index).copied()),
+            View::Timeline => self
+                .timeline
+                .as_ref()
+                .and_then(|t| self.timeline_tasks().get(t.index).copied()),
+            _ if self.is_task_view() => self.tasks.get(self.current_view_index),
+            _ => None,
+        }
+    }
+
+    /// Sets which notifications are raised and how.
+    ///
+    pub fn set_notifications(&mut self, notifications: Notifications) -> &mut Self {
+        self.notifications = notifications;
+        self
+    }
+
+    /// Sets the notifications raised in earlier sessions.
+    ///
+    pub fn set_notified(&mut self, notified: Notified) -> &mut Self {
+        self.notified = notified;
+        self
+    }
+
+    /// Return which notifications are raised and how.
+    ///
+    pub fn get_notifications(&self) -> &Notifications {
+        &self.notifications
+    }
+
+    /// Return the time since which to look for notifications if a check is
+    /// due, recording the check. The first check looks back one interval.
+    ///
+    pub fn start_notification_check(&mut self) -> Option<DateTime<Utc>> {
+        if !self.notifications.is_enabled()
+            || self.user.is_none()
+            || self.active_workspace_gid.is_none()
+        {
+            return None;
+        }
+        let now = Utc::now();
+        let interval = Duration::seconds(self.notifications.interval() as i64);
+        let since = match self.notifications_checked_at {
+            Some(checked_at) if now - checked_at < interval => return None,
+            Some(checked_at) => checked_at,
+            None => now - interval,
+        };
+        self.notifications_checked_at = Some(now);
+        Some(since)
+    }
+
+    /// Raise the enabled notifications not raised before, ringing the bell
+    /// if configured and showing the newest as a message. Returns those
+    /// raised.
+    ///
+    pub fn notify(&mut self, notifications: Vec<Notification>) -> Vec<Notification> {
+        let raised: Vec<Notification> = notifications
+            .into_iter()
+            .filter(|n| self.notifications.allows(n.kind))
+            .filter(|n| self.notified.insert(&n.key))
+            .collect();
+        if !raised.is_empty() {
+            if let Err(err) = self.notified.save() {
+                error!("Failed to save raised notifications: {}", err);
+            }
+        }
+        if let Some(notification) = raised.last() {
+            self.notification_count += raised.len();
+            self.bell_pending = self.notifications.bell;
+            self.set_message(notification.message.to_owned());
+        }
+        raised
+    }
+
+    /// Return the number of notifications raised since the inbox or My Tasks
+    /// were last opened.
+    ///
+    pub fn get_notification_count(&self) -> usize {
+        self.notification_count
+    }
+
+    /// Return true once if the bell should ring for new notifications.
+    ///
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell_pending)
+    }
+
+    /// Return the inbox item for the current view item if the view is the
+    /// inbox.
+    ///
+    pub fn current_inbox_item(&self) -> Option<&InboxItem> {
+        match self.current_view() {
+            View::Inbox => self.inbox_items.get(self.current_view_index),
+            _ => None,
+        }
+    }
+
+    /// Return the stories shown in the inbox, newest first.
+    ///
+    pub fn get_inbox_items(&self) -> &Vec<InboxItem> {
+        &self.inbox_items
+    }
+
+    /// Set the stories shown in the inbox along with the tasks they belong
+    /// to, keeping the current view item on the same story where possible.
+    ///
+    pub fn set_inbox(&mut self, tasks: Vec<Task>, items: Vec<InboxItem>) -> &mut Self {
+        let current_story_gid = self.current_inbox_item().map(|i| i.story_gid.to_owned());
+        let index = current_story_gid
+            .and_then(|gid| items.iter().position(|i| i.story_gid == gid))
+            .unwrap_or(self.current_view_index);
+        self.inbox_items = items;
+        self.set_tasks(tasks);
+        self.current_view_index = index.min(self.inbox_items.len().saturating_sub(1));
+        self
+    }
+
+    /// Sets the stories read in the inbox.
+    ///
+    pub fn set_inbox_reads(&mut self, inbox: Inbox) -> &mut Self {
+        self.inbox = inbox;
+        self
+    }
+
+    /// Return true if the inbox story with the given GID was read.
+    ///
+    pub fn is_inbox_item_read(&self, story_gid: &str) -> bool {
+        self.inbox.is_read(story_gid)
+    }
+
+    /// Return the number of unread stories in the inbox.
+    ///
+    pub fn unread_inbox_count(&self) -> usize {
+        self.inbox_items
+            .iter()
+            .filter(|i| !self.inbox.is_read(&i.story_gid))
+            .count()
+    }
+
+    /// Toggle whether the current inbox story was read.
+    ///
+    pub fn toggle_current_inbox_item_read(&mut self) -> &mut Self {
+        if *self.current_view() != View::Inbox {
+            return self;
+        }
+        if let Some(item) = self.current_inbox_item() {
+            let story_gid = item.story_gid.to_owned();
+            let read = !self.inbox.is_read(&story_gid);
+            self.set_inbox_item_read(&story_gid, read);
+        }
+        self
+    }
+
+    /// Return the permalink of the current task or, from the project list or
+    /// an empty project, of the project.
+    ///
+    pub fn current_permalink(&self) -> Option<&str> {
+        let permalink_url = match (self.current_focus, &self.current_menu) {
+            (Focus::Menu, Menu::TopList) => self
+                .projects
+                .get(self.current_top_list_index)
+                .and_then(|p| p.permalink_url.as_ref()),
+            (Focus::Menu, _) => None,
+            (Focus::View, _) => match (self.current_task(), self.current_view()) {
+                (Some(task), _) => task.permalink_url.as_ref(),
+                (None, View::ProjectTasks) => {
+                    self.project.as_ref().and_then(|p| p.permalink_url.as_ref())
+                }
+                (None, _) => None,
+            },
+        };
+        permalink_url.map(|url| url.as_str())
+    }
+
+    /// Return the journal entry for the current view item if the view lists
+    /// pending changes.
+    ///
+    pub fn current_journal_entry(&self) -> Option<&Entry> {
+        match self.current_view() {
+            View::PendingChanges => self.journal.entries().get(self.current_view_index),
+            _ => None,
+        }
+    }
+
+    /// Return the list of tasks.
+    ///
+    pub fn get_tasks(&self) -> &Vec<Task> {
+        &self.tasks
+    }
+
+    /// Set the list of tasks, reapplying any mutations still awaiting replay
+    /// and keeping the current view item on the same task where possible.
+    ///
+    pub fn set_tasks(&mut self, tasks: Vec<Task>) -> &mut Self {
+        let current_task_gid = self.current_task().map(|t| t.gid.to_owned());
+        self.tasks = tasks;
+        self.tasks.retain(|t| !self.journal.is_deleted(&t.gid));
+        let tasks = &self.tasks;
+        self.marked_task_gids
+            .retain(|gid| tasks.iter().any(|t| &t.gid == gid));
+        for task in self.tasks.iter_mut() {
+            self.journal.reapply(task);
+        }
+        if let Some(index) =
+            current_task_gid.and_then(|gid| self.tasks.iter().position(|t| t.gid == gid))
+        {
+            self.current_view_index = index;
+        } else if self.current_view_index >= self.tasks.len() {
+            self.current_view_index = self.tasks.len().max(1) - 1;
+        }
+        self.last_updated = Some(Utc::now());
+        self
+    }
+
+    /// Replace the task having the same GID as the given task, reapplying any
+    /// mutations still awaiting replay.
+    ///
+    pub fn update_task(&mut self, mut task: Task) -> &mut Self {
+        if let Some(existing) = self.tasks.iter_mut().find(|t| t.gid == task.gid) {
+            self.journal.reapply(&mut task);
+            *existing = task;
+        }
+        self.sort_my_tasks()
+    }
+
+    /// Add the task to the end of the list or replace the task having the
+    /// same GID if it is already listed, keeping My Tasks ordered by section.
+    ///
+    pub fn add_task(&mut self, mut task: Task) -> &mut Self {
+        if self.journal.is_deleted(&task.gid) {
+            return self;
+        }
+        self.journal.reapply(&mut task);
+        match self.tasks.iter_mut().find(|t| t.gid == task.gid) {
+            Some(existing) => *existing = task,
+            None => self.tasks.push(task),
+        }
+        self.sort_my_tasks()
+    }
+
+    /// Remove the task with the given GID, keeping the current view item in
+    /// bounds.
+    ///
+    pub fn remove_task(&mut self, task_gid: &str) -> &mut Self {
+        self.tasks.retain(|t| t.gid != task_gid);
+        self.marked_task_gids.retain(|gid| gid != task_gid);
+        if self.detail_task_gid.as_deref() == Some(task_gid) {
+            self.close_task_detail();
+        }
+        if self.is_task_view()
+            && self.current_view_index >= self.tasks.len()
+            && self.current_view_index > 0
+        {
+            self.current_view_index = self.tasks.len().max(1) - 1;
+        }
+        self
+    }
+
+    /// Return the task with the given GID.
+    ///
+    pub fn get_task(&self, task_gid: &str) -> Option<&Task> {
+        self.tasks.iter().find(|t| t.gid == task_gid)
+    }
+
+    /// Replace the task with the given GID by the task returned from Asana,
+    /// removing it if it was deleted.
+    ///
+    pub fn replace_task(&mut self, task_gid: &str, task: Option<Task>) -> &mut Self {
+        match task {
+            Some(task) => self.update_task(task),
+            None => self.remove_task(task_gid),
+        }
+    }
+
+    /// Apply the mutation to the local task and dispatch it to Asana.
+    ///
+    pub fn mutate(&mut self, mutation: Mutation) -> &mut Self {
+        self.mutate_all(vec![mutation])
+    }
+
+    /// Apply the mutations to the local tasks and dispatch them to Asana
+    /// together as a single change which can be undone, clearing the marked
+    /// tasks.
+    ///
+    pub fn mutate_all(&mut self, mutations: Vec<Mutation>) -> &mut Self {
+        self.clear_marks();
+        if mutations.is_empty() {
+            return self;
+        }
+        let subject = match mutations.len() {
+            1 => match self.get_task(mutations[0].task_gid()) {
+                Some(task) => format!("'{}'", task.name),
+                None => format!("task {}", mutations[0].task_gid()),
+            },
+            count => format!("{} tasks", count),
+        };
+        let change = Change {
+            summary: mutations[0].summarize(&subject),
+            inverses: mutations
+                .iter()
+                .filter_map(|m| self.get_task(m.task_gid()).and_then(|t| m.inverse(t)))
+                .collect(),
+            mutations: mutations.to_owned(),
+        };
+        match change.inverses.is_empty() {
+            true => {
+                self.set_message(change.summary.to_owned());
+            }
+            false => {
+                let hint = self.key_hint(Action::Undo, "undo");
+                self.set_message(format!("{}{}", change.summary, hint));
+                self.undo_stack.push(change);
+                if self.undo_stack.len() > UNDO_LIMIT {
+                    self.undo_stack.remove(0);
+                }
+                self.redo_stack.clear();
+            }
+        }
+        self.send(mutations);
+        self
+    }
+
+    /// Return a hint naming the keys bound to the action, or nothing if it is
+    /// unbound.
+    ///
+    fn key_hint(&self, action: Action, description: &str) -> String {
+        match self.keymap.keys(action) {
+            Some(keys) => format!(" — press {} to {}", keys, description),
+            None => String::new(),
+        }
+    }
+
+    /// Reverse the most recent change.
+    ///
+    pub fn undo(&mut self) -> &mut Self {
+        let change = match self.undo_stack.pop() {
+            Some(change) => change,
+            None => return self.set_message(String::from("Nothing to undo")),
+        };
+        self.send(change.inverses.to_owned());
+        let hint = self.key_hint(Action::Redo, "redo");
+        self.set_message(format!("Undid: {}{}", change.summary, hint));
+        self.redo_stack.push(change);
+        self
+    }
+
+    /// Make the most recently undone change again.
+    ///
+    pub fn redo(&mut self) -> &mut Self {
+        let change = match self.redo_stack.pop() {
+            Some(change) => change,
+            None => return self.set_message(String::from("Nothing to redo")),
+        };
+        self.send(change.mutations.to_owned());
+        let hint = self.key_hint(Action::Undo, "undo");
+        self.set_message(format!("Redid: {}{}", change.summary, hint));
+        self.undo_stack.push(change);
+        self
+    }
+
+    /// Return the message to show briefly after an action, if still recent.
+    ///
+    pub fn get_message(&self) -> Option<&str> {
+        match &self.message {
+            Some((message, shown_at))
+                if Utc::now() - *shown_at < Duration::seconds(MESSAGE_DURATION_IN_SECS) =>
+            {
+                Some(message)
+            }
+            _ => None,
+        }
+    }
+
+    /// Show the message briefly.
+    ///
+    pub fn set_message(&mut self, message: String) -> &mut Self {
+        self.message = Some((message, Utc::now()));
+        self
+    }
+
+    /// Returns the active keymap.
+    ///
+    pub fn get_keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Sets the active keymap.
+    ///
+    pub fn set_keymap(&mut self, keymap: Keymap) -> &mut Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Returns the sizes of resizable panels.
+    ///
+    pub fn get_panels(&self) -> &Panels {
+        &self.panels
+    }
+
+    /// Sets the sizes of resizable panels, brought within bounds.
+    ///
+    pub fn set_panels(&mut self, panels: Panels) -> &mut Self {
+        self.panels = panels.clamped();
+        self
+    }
+
+    /// Returns how the log panel is shown.
+    ///
+    pub fn get_log_display(&self) -> &LogDisplay {
+        &self.log_display
+    }
+
+    /// Hide the log panel or show it again if hidden.
+    ///
+    pub fn toggle_log(&mut self) -> &mut Self {
+        self.log_display = match self.log_display {
+            LogDisplay::Hidden => LogDisplay::Normal,
+            _ => LogDisplay::Hidden,
+        };
+        self.log_search = None;
+        self
+    }
+
+    /// Zoom the log panel to fill the screen or restore it if zoomed,
+    /// clearing any search when leaving.
+    ///
+    pub fn toggle_log_zoom(&mut self) -> &mut Self {
+        self.log_display = match self.log_display {
+            LogDisplay::Zoomed => LogDisplay::Normal,
+            _ => LogDisplay::Zoomed,
+        };
+        self.log_search = None;
+        self
+    }
+
+    /// Returns the state of the log widget, holding the level shown for
+    /// each target and the page being viewed.
+    ///
+    pub fn get_log_widget(&self) -> &TuiWidgetState {
+        &self.log_widget
+    }
+
+    /// Apply the given event to the log widget while the log is zoomed.
+    ///
+    pub fn transition_log_widget(&mut self, event: TuiWidgetEvent) -> &mut Self {
+        if self.log_display == LogDisplay::Zoomed {
+            self.log_widget.transition(&event);
+        }
+        self
+    }
+
+    /// Sets the path of the log file searched through.
+    ///
+    pub fn set_log_file_path(&mut self, file_path: PathBuf) -> &mut Self {
+        self.log_file_path = Some(file_path);
+        self
+    }
+
+    /// Sets the directory projects are exported to.
+    ///
+    pub fn set_export_directory(&mut self, directory: PathBuf) -> &mut Self {
+        self.export_directory = directory;
+        self
+    }
+
+    /// Returns the directory projects are exported to.
+    ///
+    pub fn get_export_directory(&self) -> &PathBuf {
+        &self.export_directory
+    }
+
+    /// Returns the current log search if any.
+    ///
+    pub fn get_log_search(&self) -> Option<&LogSearch> {
+        self.log_search.as_ref()
+    }
+
+    /// Search the log file for lines containing the query, clearing the
+    /// search if the query is empty.
+    ///
+    pub fn search_log(&mut self, query: &str) -> &mut Self {
+        let query = query.trim();
+        if query.is_empty() {
+            self.log_search = None;
+            return self;
+        }
+        let file_path = match &self.log_file_path {
+            Some(file_path) => file_path,
+            None => return self,
+        };
+        match logger::search(file_path, query) {
+            Ok(matches) => {
+                self.log_search = Some(LogSearch {
+                    query: query.to_owned(),
+                    matches,
+                });
+            }
+            Err(err) => {
+                error!("Failed to search log file: {}", err);
+            }
+        }
+        self
+    }
+
+    /// Clear the log search if any or otherwise leave the zoomed log.
+    ///
+    pub fn cancel_log(&mut self) -> &mut Self {
+        match self.log_search {
+            Some(_) => {
+                self.log_search = None;
+                self
+            }
+            None => self.toggle_log_zoom(),
+        }
+    }
+
+    /// Returns the help overlay if open.
+    ///
+    pub fn get_help(&self) -> Option<&Help> {
+        self.help.as_ref()
+    }
+
+    /// Open the help overlay or close it if already open.
+    ///
+    pub fn toggle_help(&mut self) -> &mut Self {
+        self.help = match self.help {
+            Some(_) => None,
+            None => Some(Help::default()),
+        };
+        self
+    }
+
+    /// Return the context determining which actions are available.
+    ///
+    pub fn current_context(&self) -> Context {
+        if self.log_display == LogDisplay::Zoomed {
+            return Context::Log;
+        }
+        match (self.current_focus, self.current_view()) {
+            (Focus::Menu, _) => Context::Menu,
+            (Focus::View, View::PendingChanges) => Context::PendingChanges,
+            (Focus::View, View::ImportPreview) => Context::ImportPreview,
+            (Focus::View, View::TaskDetail) => Context::TaskDetail,
+            (Focus::View, View::Inbox) => Context::Inbox,
+            (Focus::View, View::Calendar) => Context::Calendar,
+            (Focus::View, View::Timeline) => Context::Timeline,
+            (Focus::View, _) if self.is_task_view() => Context::Tasks,
+            (Focus::View, _) => Context::Welcome,
+        }
+    }
+
+    /// Return the help overlay lines for the current context matching the
+    /// search query, followed by those for entering text while an input is
+    /// being entered.
+    ///
+    pub fn help_lines(&self) -> Vec<(String, &'static str)> {
+        let query = match &self.help {
+            Some(help) => help.query.to_lowercase(),
+            None => String::new(),
+        };
+        let input_bindings = match self.current_input() {
+            Some(_) => INPUT_BINDINGS,
+            None => &[],
+        };
+        self.keymap
+            .help(self.current_context())
+            .into_iter()
+            .chain(
+                input_bindings
+                    .iter()
+                    .map(|(keys, description)| (keys.to_string(), *description)),
+            )
+            .filter(|(keys, description)| {
+                keys.to_lowercase().contains(&query) || description.contains(&query)
+            })
+            .collect()
+    }
+
+    /// Scroll the help overlay down or up by one line.
+    ///
+    pub fn scroll_help(&mut self, down: bool) -> &mut Self {
+        let max_scroll = self.help_lines().len().saturating_sub(1);
+        if let Some(help) = &mut self.help {
+            help.scroll = match down {
+                true => (help.scroll + 1).min(max_scroll),
+                false => help.scroll.saturating_sub(1),
+            };
+        }
+        self
+    }
+
+    /// Start or stop typing a search query in the help overlay.
+    ///
+    pub fn set_help_searching(&mut self, searching: bool) -> &mut Self {
+        if let Some(help) = &mut self.help {
+            help.searching = searching;
+        }
+        self
+    }
+
+    /// Append a character to the help overlay search query.
+    ///
+    pub fn push_help_char(&mut self, c: char) -> &mut Self {
+        if let Some(help) = &mut self.help {
+            help.query.push(c);
+            help.scroll = 0;
+        }
+        self
+    }
+
+    /// Remove the last character of the help overlay search query.
+    ///
+    pub fn pop_help_char(&mut self) -> &mut Self {
+        if let Some(help) = &mut self.help {
+            help.query.pop();
+            help.scroll = 0;
+        }
+        self
+    }
+
+    /// Returns the active theme.
+    ///
+    pub fn get_theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Sets the active theme, approximating its colors by those the terminal
+    /// can display.
+    ///
+    pub fn set_theme(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme.degrade(self.color_support);
+        self
+    }
+
+    /// Sets how many colors the terminal can display.
+    ///
+    pub fn set_color_support(&mut self, color_support: ColorSupport) -> &mut Self {
+        self.color_support = color_support;
+        self
+    }
+
+    /// Switch to the built-in theme following the active one.
+    ///
+    pub fn next_theme(&mut self) -> &mut Self {
+        let theme = self.theme.next();
+        self.set_message(format!("Switched to the {} theme", theme.name));
+        self.set_theme(theme)
+    }
+
+    /// Returns the keys pressed so far towards a multi-key sequence.
+    ///
+    pub fn get_pending_keys(&self) -> &[Key] {
+        &self.pending_keys
+    }
+
+    /// Record the given key press, returning the action it completes if any.
+    /// A key which breaks off a partial sequence is looked up on its own.
+    ///
+    pub fn push_key(&mut self, key: Key) -> Option<Action> {
+        self.pending_keys.push(key);
+        loop {
+            match self.keymap.resolve(&self.pending_keys) {
+                Resolution::Action(action) => {
+                    self.pending_keys.clear();
+                    return Some(action);
+                }
+                Resolution::Partial => return None,
+                Resolution::Unbound if self.pending_keys.len() > 1 => {
+                    self.pending_keys = vec![key];
+                }
+                Resolution::Unbound => {
+                    self.pending_keys.clear();
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Toggle completion of the selected tasks, completing all of them unless
+    /// all are already complete.
+    ///
+    pub fn toggle_current_task_completed(&mut self) -> &mut Self {
+        let task_gids = self.selected_task_gids();
+        let completed = !task_gids
+            .iter()
+            .filter_map(|gid| self.get_task(gid))
+            .all(|t| t.completed);
+        let mutations = task_gids
+            .into_iter()
+            .map(|task_gid| Mutation::Complete {
+                task_gid,
+                completed,
+            })
+            .collect();
+        self.mutate_all(mutations)
+    }
+
+    /// Toggle whether the current task is marked for a bulk action.
+    ///
+    pub fn toggle_current_task_marked(&mut self) -> &mut Self {
+        let task_gid = match self.current_task() {
+            Some(task) => task.gid.to_owned(),
+            None => return self,
+        };
+        match self
+            .marked_task_gids
+            .iter()
+            .position(|gid| *gid == task_gid)
+        {
+            Some(index) => {
+                self.marked_task_gids.remove(index);
+            }
+            None => self.marked_task_gids.push(task_gid),
+        }
+        self
+    }
+
+    /// Start selecting a range of tasks from the current task or, if already
+    /// selecting, mark the tasks in the range.
+    ///
+    pub fn toggle_visual_mode(&mut self) -> &mut Self {
+        if !self.is_task_view() || self.tasks.is_empty() {
+            return self;
+        }
+        match self.visual_anchor {
+            Some(_) => {
+                self.marked_task_gids = self.selected_task_gids();
+                self.visual_anchor = None;
+            }
+            None => self.visual_anchor = Some(self.current_view_index),
+        }
+        self
+    }
+
+    /// Return true if tasks are marked or a range is being selected.
+    ///
+    pub fn has_marks(&self) -> bool {
+        !self.marked_task_gids.is_empty() || self.visual_anchor.is_some()
+    }
+
+    /// Return true if actions apply to the marked tasks rather than the
+    /// current one, which the task detail view always acts on.
+    ///
+    fn uses_marks(&self) -> bool {
+        self.has_marks() && self.is_task_view()
+    }
+
+    /// Unmark all tasks and stop selecting a range.
+    ///
+    pub fn clear_marks(&mut self) -> &mut Self {
+        self.marked_task_gids.clear();
+        self.visual_anchor = None;
+        self
+    }
+
+    /// Return true if the task is marked or within the range being selected.
+    ///
+    pub fn is_marked(&self, task_gid: &str) -> bool {
+        self.marked_task_gids.iter().any(|gid| gid == task_gid)
+            || self
+                .visual_range()
+                .map(|range| self.tasks[range].iter().any(|t| t.gid == task_gid))
+                .unwrap_or(false)
+    }
+
+    /// Return the GIDs of the tasks a bulk action applies to in list order:
+    /// the marked tasks and the range being selected if any, or the current
+    /// task otherwise.
+    ///
+    pub fn selected_task_gids(&self) -> Vec<String> {
+        if !self.uses_marks() {
+            return self
+                .current_task()
+                .map(|t| t.gid.to_owned())
+                .into_iter()
+                .collect();
+        }
+        self.tasks
+            .iter()
+            .filter(|t| self.is_marked(&t.gid))
+            .map(|t| t.gid.to_owned())
+            .collect()
+    }
+
+    /// Return the number of tasks done and in total for the bulk action in
+    /// progress.
+    ///
+    pub fn get_bulk_progress(&self) -> Option<&(usize, usize)> {
+        self.bulk_progress.as_ref()
+    }
+
+    /// Start or finish reporting progress of a bulk action on the given
+    /// number of tasks.
+    ///
+    pub fn set_bulk_progress(&mut self, total: Option<usize>) -> &mut Self {
+        self.bulk_progress = total.map(|total| (0, total));
+        self
+    }
+
+    /// Record that another task of the bulk action in progress is done.
+    ///
+    pub fn advance_bulk_progress(&mut self) -> &mut Self {
+        if let Some((done, _)) = self.bulk_progress.as_mut() {
+            *done += 1;
+        }
+        self
+    }
+
+    /// Return the current input.
+    ///
+    pub fn current_input(&self) -> Option<&Input> {
+        self.input.as_ref()
+    }
+
+    /// Begin an input of the given kind for the selected tasks or current
+    /// project, prefilling the buffer with the current value where one
+    /// exists.
+    ///
+    pub fn start_input(&mut self, kind: InputKind) -> &mut Self {
+        if kind == InputKind::Search {
+            if self.log_display == LogDisplay::Zoomed {
+                self.input = Some(Input {
+                    kind,
+                    buffer: String::new(),
+                });
+            }
+            return self;
+        }
+        if kind == InputKind::Command {
+            self.history_index = None;
+            self.input = Some(Input {
+                kind,
+                buffer: String::new(),
+            });
+            return self;
+        }
+        if kind == InputKind::Team {
+            if !self.teams.is_empty() {
+                self.input = Some(Input {
+                    kind,
+                    buffer: String::new(),
+                });
+                self.picker_index = 0;
+            }
+            return self;
+        }
+        if matches!(kind, InputKind::Export | InputKind::Import) {
+            if *self.current_view() == View::ProjectTasks && self.project.is_some() {
+                let buffer = match kind {
+                    InputKind::Export => String::from(export::FORMATS[0]),
+                    _ => String::new(),
+                };
+                self.input = Some(Input { kind, buffer });
+            }
+            return self;
+        }
+        if kind == InputKind::Section
+            && !(*self.current_view() == View::ProjectTasks
+                || *self.current_view() == View::MyTasks && self.user_task_list.is_some())
+        {
+            return self;
+        }
+        if self.selected_task_gids().is_empty() {
+            return self;
+        }
+        let task = self.current_task().filter(|_| !self.uses_marks());
+        let buffer = match (kind, task) {
+            (InputKind::Rename | InputKind::Comment | InputKind::RemoveDependency, None) => {
+                return self
+            }
+            (InputKind::RemoveDependency, Some(task)) if task.dependencies.is_empty() => {
+                return self
+            }
+            (InputKind::Rename, Some(task)) => task.name.to_owned(),
+            (InputKind::DueDate, Some(task)) => task.due_on.to_owned().unwrap_or_default(),
+            _ => String::new(),
+        };
+        self.input = Some(Input { kind, buffer });
+        self.picker_index = 0;
+        self
+    }
+
+    /// Append the character to the current input.
+    ///
+    pub fn push_input_char(&mut self, c: char) -> &mut Self {
+        if let Some(input) = self.input.as_mut() {
+            input.buffer.push(c);
+            self.picker_index = 0;
+        }
+        self
+    }
+
+    /// Remove the last character from the current input.
+    ///
+    pub fn pop_input_char(&mut self) -> &mut Self {
+        if let Some(input) = self.input.as_mut() {
+            input.buffer.pop();
+            self.picker_index = 0;
+        }
+        self
+    }
+
+    /// Return true if the current input picks a task or team.
+    ///
+    pub fn is_picking(&self) -> bool {
+        matches!(&self.input, Some(input) if input.kind.picks_task() || input.kind == InputKind::Team)
+    }
+
+    /// Return the tasks which can be picked for the current input, being
+    /// those whose names contain the text entered.
+    ///
+    pub fn picker_tasks(&self) -> Vec<RelatedTask> {
+        match &self.input {
+            Some(input) => self.matching_tasks(input),
+            None => vec![],
+        }
+    }
+
+    /// Return the teams which can be picked for the current input, being
+    /// None for all teams followed by those whose names contain the text
+    /// entered.
+    ///
+    pub fn picker_teams(&self) -> Vec<Option<Team>> {
+        match &self.input {
+            Some(input) => self.matching_teams(input),
+            None => vec![],
+        }
+    }
+
+    /// Return the index of the item picked among those matching the input.
+    ///
+    pub fn get_picker_index(&self) -> &usize {
+        &self.picker_index
+    }
+
+    /// Pick the next item matching the input.
+    ///
+    pub fn next_picker_index(&mut self) -> &mut Self {
+        let len = self.picker_len();
+        self.picker_index = match self.picker_index + 1 < len {
+            true => self.picker_index + 1,
+            false => 0,
+        };
+        self
+    }
+
+    /// Pick the previous item matching the input.
+    ///
+    pub fn previous_picker_index(&mut self) -> &mut Self {
+        let len = self.picker_len();
+        self.picker_index = match self.picker_index {
+            0 => len.saturating_sub(1),
+            index => index - 1,
+        };
+        self
+    }
+
+    /// Discard the current input.
+    ///
+    pub fn cancel_input(&mut self) -> &mut Self {
+        self.input = None;
+        self
+    }
+
+    /// Submit the current input as mutations of the selected tasks or an
+    /// export of or import into the current project.
+    ///
+    pub fn submit_input(&mut self) -> &mut Self {
+        let input = match self.input.take() {
+            Some(input) => input,
+            None => return self,
+        };
+        if input.kind == InputKind::Command {
+            if let Err(err) = self.history.push(&input.buffer) {
+                error!("Failed to update command history: {}", err);
+            }
+            return self.run_command(&input.buffer);
+        }
+        if input.kind == InputKind::Search {
+            return self.search_log(&input.buffer);
+        }
+        if input.kind == InputKind::Export {
+            match input.buffer.trim().parse::<export::Format>() {
+                Ok(format) => self.dispatch(NetworkEvent::Export(format)),
+                Err(err) => warn!("Skipping export: {}", err),
+            }
+            return self;
+        }
+        if input.kind == InputKind::Import {
+            let file_path = input.buffer.trim();
+            if !file_path.is_empty() {
+                self.dispatch(NetworkEvent::PreviewImport(PathBuf::from(file_path)));
+            }
+            return self;
+        }
+        if input.kind == InputKind::Team {
+            let team = match self.matching_teams(&input).get(self.picker_index).cloned() {
+                Some(team) => team,
+                None => return self,
+            };
+            let message = match &team {
+                Some(team) => format!("Showing projects of team {}", team.name),
+                None => String::from("Showing projects of all teams"),
+            };
+            self.team = team;
+            self.current_top_list_index = 0;
+            self.filter_projects()
+                .focus_menu_item(Menu::TopList)
+                .set_message(message);
+            return self;
+        }
+        let text = input.buffer.trim().to_owned();
+        if matches!(input.kind, InputKind::Rename | InputKind::Comment) {
+            let task_gid = match self.current_task() {
+                Some(task) => task.gid.to_owned(),
+                None => return self,
+            };
+            let mutation = match input.kind {
+                InputKind::Rename if !text.is_empty() => Mutation::Rename {
+                    task_gid,
+                    name: text,
+                },
+                InputKind::Comment if !text.is_empty() => Mutation::Comment { task_gid, text },
+                _ => return self,
+            };
+            return self.mutate(mutation);
+        }
+        let mutation: Box<dyn Fn(String) -> Mutation> = match input.kind {
+            InputKind::DueDate => {
+                let due_on = match text.is_empty() {
+                    true => None,
+                    false => match date::parse(&text, Local::today().naive_local()) {
+                        Ok(due_on) => Some(due_on.to_string()),
+                        Err(_) => {
+                            warn!("Skipping invalid due date '{}'.", text);
+                            return self;
+                        }
+                    },
+                };
+                Box::new(move |task_gid| Mutation::SetDueDate {
+                    task_gid,
+                    due_on: due_on.to_owned(),
+                })
+            }
+            InputKind::Assign => {
+                let assignee = Some(text).filter(|t| !t.is_empty());
+                Box::new(move |task_gid| Mutation::Assign {
+                    task_gid,
+                    assignee: assignee.to_owned(),
+                })
+            }
+            InputKind::Section => {
+                let section = match self
+                    .sections
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(&text))
+                {
+                    Some(section) => section.to_owned(),
+                    None => {
+                        warn!("Skipping unknown section '{}'.", text);
+                        return self;
+                    }
+                };
+                if *self.current_view() == View::MyTasks {
+                    Box::new(move |task_gid| Mutation::SetAssigneeSection {
+                        task_gid,
+                        section_gid: section.gid.to_owned(),
+                        section_name: section.name.to_owned(),
+                    })
+                } else {
+                    let project_gid = match &self.project {
+                        Some(project) => project.gid.to_owned(),
+                        None => return self,
+                    };
+                    Box::new(move |task_gid| Mutation::MoveSection {
+                        task_gid,
+                        project_gid: project_gid.to_owned(),
+                        section_gid: section.gid.to_owned(),
+                        section_name: section.name.to_owned(),
+                    })
+                }
+            }
+            InputKind::Tag => {
+                let tag = match self
+                    .tags
+                    .iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(&text))
+                {
+                    Some(tag) => tag.to_owned(),
+                    None => {
+                        warn!("Skipping unknown tag '{}'.", text);
+                        return self;
+                    }
+                };
+                Box::new(move |task_gid| Mutation::AddTag {
+                    task_gid,
+                    tag_gid: tag.gid.to_owned(),
+                    tag_name: tag.name.to_owned(),
+                })
+            }
+            InputKind::AddDependency | InputKind::RemoveDependency => {
+                let dependency = match self.matching_tasks(&input).get(self.picker_index) {
+                    Some(dependency) => dependency.to_owned(),
+                    None => {
+                        warn!("Skipping unknown task '{}'.", text);
+                        return self;
+                    }
+                };
+                let kind = input.kind;
+                Box::new(move |task_gid| match kind {
+                    InputKind::AddDependency => Mutation::AddDependency {
+                        task_gid,
+                        dependency_gid: dependency.gid.to_owned(),
+                        dependency_name: dependency.name.to_owned(),
+                    },
+                    _ => Mutation::RemoveDependency {
+                        task_gid,
+                        dependency_gid: dependency.gid.to_owned(),
+                        dependency_name: dependency.name.to_owned(),
+                    },
+                })
+            }
+            InputKind::Delete if matches!(text.to_lowercase().as_str(), "y" | "yes") => {
+                Box::new(|task_gid| Mutation::Delete { task_gid })
+            }
+            _ => return self,
+        };
+        let mutations = self
+            .selected_task_gids()
+            .into_iter()
+            .map(mutation)
+            .collect();
+        self.mutate_all(mutations)
+    }
+
+    /// Sets the history of entered commands.
+    ///
+    pub fn set_history(&mut self, history: History) -> &mut Self {
+        self.history = history;
+        self
+    }
+
+    /// Replace the command being entered with the previous one in the
+    /// history.
+    ///
+    pub fn previous_history_input(&mut self) -> &mut Self {
+        let len = self.history.entries().len();
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None if len > 0 => len - 1,
+            None => return self,
+        };
+        self.set_history_input(Some(index))
+    }
+
+    /// Replace the command being entered with the next one in the history,
+    /// clearing it after the newest.
+    ///
+    pub fn next_history_input(&mut self) -> &mut Self {
+        let index = match self.history_index {
+            Some(index) if index + 1 < self.history.entries().len() => Some(index + 1),
+            _ => None,
+        };
+        self.set_history_input(index)
+    }
+
+    /// Complete the command name or argument being entered against the
+    /// commands, projects, users, tags, workspaces or formats available.
+    ///
+    pub fn complete_input(&mut self) -> &mut Self {
+        let buffer = match &self.input {
+            Some(input) if input.kind == InputKind::Command => input.buffer.to_owned(),
+            _ => return self,
+        };
+        let (name, argument) = match buffer.split_once(' ') {
+            Some((name, argument)) => (name, argument),
+            None => {
+                let commands: Vec<String> = COMMANDS.iter().map(|c| c.to_string()).collect();
+                if let Some(completion) = command::complete(&buffer, &commands) {
+                    let suffix = match commands.contains(&completion) {
+                        true => " ",
+                        false => "",
+                    };
+                    self.input.as_mut().unwrap().buffer = format!("{}{}", completion, suffix);
+                }
+                return self;
+            }
+        };
+        let candidates: Vec<String> = match command::resolve(name) {
+            Ok("project") => self.projects.iter().map(|p| p.name.to_owned()).collect(),
+            Ok("assign") => self
+                .known_users()
+                .iter()
+                .map(|u| format!("@{}", u.name))
+                .collect(),
+            Ok("tag") => self.tags.iter().map(|t| t.name.to_owned()).collect(),
+            Ok("workspace" | "ws") => self.workspaces.iter().map(|w| w.name.to_owned()).collect(),
+            Ok("export") => export::FORMATS.iter().map(|f| f.to_string()).collect(),
+            _ => vec![],
+        };
+        if let Some(completion) = command::complete(argument, &candidates) {
+            self.input.as_mut().unwrap().buffer = format!("{} {}", name, completion);
+        }
+        self
+    }
+
+    /// Parse and run the command, showing a message if it fails.
+    ///
+    pub fn run_command(&mut self, text: &str) -> &mut Self {
+        let command = match text.parse::<Command>() {
+            Ok(command) => command,
+            Err(err) => return self.set_message(err.to_string()),
+        };
+        let (kind, argument) = match command {
+            Command::Project(name) => {
+                let names: Vec<&str> = self.projects.iter().map(|p| p.name.as_str()).collect();
+                return match find_by_name(&names, &name) {
+                    Some(index) => self
+                        .set_top_list_index(index)
+                        .select_current_top_list_index(),
+                    None => self.set_message(format!("No project matches '{}'", name)),
+                };
+            }
+            Command::Workspace(name) => {
+                let names: Vec<&str> = self.workspaces.iter().map(|w| w.name.as_str()).collect();
+                return match find_by_name(&names, &name) {
+                    Some(index) => {
+                        let workspace = self.workspaces[index].to_owned();
+                        self.set_active_workspace(workspace.gid);
+                        self.workspace_projects.clear();
+                        self.projects.clear();
+                        self.teams.clear();
+                        self.team = None;
+                        self.tags.clear();
+                        self.current_top_list_index = 0;
+                        self.select_status_menu();
+                        self.dispatch(NetworkEvent::Workspace);
+                        self.set_message(format!("Switched to workspace {}", workspace.name))
+                    }
+                    None => self.set_message(format!("No workspace matches '{}'", name)),
+                };
+            }
+            Command::Refresh => return self.refresh_current_view(),
+            Command::Assign(assignee) => match self.resolve_assignee(&assignee) {
+                Some(gid) => (InputKind::Assign, gid),
+                None => return self.set_message(format!("No user matches '{}'", assignee)),
+            },
+            Command::Due(due) => (InputKind::DueDate, due),
+            Command::Tag(tag) => (InputKind::Tag, tag),
+            Command::Export(format) => (InputKind::Export, format),
+        };
+        self.start_input(kind);
+        match self.input.as_mut() {
+            Some(input) => {
+                input.buffer = argument;
+                self.submit_input()
+            }
+            None => self.set_message(format!(
+                "Command '{}' is unavailable here",
+                text.trim().trim_start_matches(':')
+            )),
+        }
+    }
+
+    /// Return the current user alongside the assignees of loaded tasks.
+    ///
+    fn known_users(&self) -> Vec<User> {
+        let mut users: Vec<User> = self.user.iter().cloned().collect();
+        for user in self.tasks.iter().filter_map(|t| t.assignee.as_ref()) {
+            if !users.iter().any(|u| u.gid == user.gid) {
+                users.push(user.to_owned());
+            }
+        }
+        users
+    }
+
+    /// Return the GID of the known user matching the `@name` given, the
+    /// assignee as given if it has no `@`, or None if no user matches.
+    ///
+    fn resolve_assignee(&self, assignee: &str) -> Option<String> {
+        let name = match assignee.strip_prefix('@') {
+            Some(name) => name,
+            None => return Some(assignee.to_owned()),
+        };
+        let users = self.known_users();
+        let names: Vec<&str> = users.iter().map(|u| u.name.as_str()).collect();
+        find_by_name(&names, name).map(|index| users[index].gid.to_owned())
+    }
+
+    fn set_history_input(&mut self, index: Option<usize>) -> &mut Self {
+        let buffer = match index {
+            Some(index) => self.history.entries()[index].to_owned(),
+            None => String::new(),
+        };
+        if let Some(input) = self.input.as_mut().filter(|i| i.kind == InputKind::Command) {
+            input.buffer = buffer;
+            self.history_index = index;
+        }
+        self
+    }
+
+    /// Return the journal of mutations awaiting replay.
+    ///
+    pub fn get_journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// Return the mutable journal of mutations awaiting replay.
+    ///
+    pub fn get_journal_mut(&mut self) -> &mut Journal {
+        &mut self.journal
+    }
+
+    /// Retry the current journal entry, overwriting any server changes.
+    ///
+    pub fn retry_current_journal_entry(&mut self) -> &mut Self {
+        if let Some(id) = self.current_journal_entry().map(|e| e.id) {
+            if let Err(err) = self.journal.retry(id) {
+                error!("Failed to update journal: {}", err);
+            }
+            self.dispatch(NetworkEvent::ReplayJournal);
+        }
+        self
+    }
+
+    /// Discard the current journal entry.
+    ///
+    pub fn discard_current_journal_entry(&mut self) -> &mut Self {
+        if let Some(id) = self.current_journal_entry().map(|e| e.id) {
+            if let Err(err) = self.journal.remove(id) {
+                error!("Failed to update journal: {}", err);
+            }
+            if self.current_view_index >= self.view_len() && self.current_view_index > 0 {
+                self.current_view_index -= 1;
+            }
+        }
+        self
+    }
+
+    /// Return the batch of tasks being imported.
+    ///
+    pub fn get_import(&self) -> Option<&Batch> {
+        self.import.as_ref()
+    }
+
+    /// Show a preview of the batch of tasks to be imported.
+    ///
+    pub fn set_import(&mut self, batch: Batch) -> &mut Self {
+        self.import = Some(batch);
+        if *self.current_view() != View::ImportPreview {
+            self.view_stack.push(View::ImportPreview);
+        }
+        self.current_view_index = 0;
+        self.focus_view();
+        self
+    }
+
+    /// Set the status of the row with the given index in the batch of tasks
+    /// being imported.
+    ///
+    pub fn set_import_status(&mut self, index: usize, status: ImportStatus) -> &mut Self {
+        if let Some(row) = self.import.as_mut().and_then(|b| b.rows.get_mut(index)) {
+            row.status = status;
+        }
+        self
+    }
+
+    /// Create the tasks in the import preview which are ready.
+    ///
+    pub fn confirm_import(&mut self) -> &mut Self {
+        if *self.current_view() != View::ImportPreview {
+            return self;
+        }
+        if let Some(batch) = &self.import {
+            if batch.count(|s| *s == ImportStatus::Ready) > 0 {
+                self.dispatch(NetworkEvent::Import);
+            }
+        }
+        self
+    }
+
+    /// Close the import preview, returning to the project.
+    ///
+    pub fn close_import(&mut self) -> &mut Self {
+        if *self.current_view() == View::ImportPreview {
+            self.view_stack.pop();
+            self.import = None;
+            self.current_view_index = 0;
+        }
+        self
+    }
+
+    /// Set the interval between automatic refreshes of the current view or
+    /// None to disable them.
+    ///
+    pub fn set_refresh_interval(&mut self, refresh_interval: Option<Duration>) -> &mut Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Return when the data for the current view was last updated.
+    ///
+    pub fn get_last_updated(&self) -> Option<&DateTime<Utc>> {
+        self.last_updated.as_ref()
+    }
+
+    /// Return true if the current view was loaded and neither it nor a
+    /// refresh request is more recent than the refresh interval.
+    ///
+    pub fn is_refresh_due(&self) -> bool {
+        let (interval, last_updated) = match (self.refresh_interval, self.last_updated) {
+            (Some(interval), Some(last_updated)) => (interval, last_updated),
+            _ => return false,
+        };
+        let latest = match self.refresh_requested_at {
+            Some(requested_at) if requested_at > last_updated => requested_at,
+            _ => last_updated,
+        };
+        self.current_view_event().is_some() && Utc::now() - latest >= interval
+    }
+
+    /// Reload the current view in the background, leaving its data in place
+    /// until the new data arrives.
+    ///
+    pub fn refresh_current_view(&mut self) -> &mut Self {
+        if let Some(event) = self.current_view_event() {
+            self.refresh_requested_at = Some(Utc::now());
+            self.dispatch(event);
+        }
+        self
+    }
+
+    /// Return true if Asana was unreachable on the last request.
+    ///
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Set whether Asana was unreachable on the last request.
+    ///
+    pub fn set_offline(&mut self, offline: bool) -> &mut Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Return the list of projects.
+    ///
+    pub fn get_projects(&self) -> &Vec<Project> {
+        &self.projects
+    }
+
+    /// Set the projects of the active workspace, listing those of the
+    /// picked team, or of the user's teams if limited to them, grouped by
+    /// team.
+    ///
+    pub fn set_projects(&mut self, projects: Vec<Project>) -> &mut Self {
+        self.workspace_projects = projects;
+        self.filter_projects()
+    }
+
+    /// Set the teams of the active workspace, dropping the picked team if
+    /// it is no longer among them.
+    ///
+    pub fn set_teams(&mut self, teams: Vec<Team>) -> &mut Self {
+        if let Some(team) = &self.team {
+            if !teams.iter().any(|t| t.gid == team.gid) {
+                self.team = None;
+            }
+        }
+        self.teams = teams;
+        self.filter_projects()
+    }
+
+    /// Return the team whose projects are listed, if one has been picked.
+    ///
+    pub fn get_team(&self) -> Option<&Team> {
+        self.team.as_ref()
+    }
+
+    /// Return true if projects are limited to the user's teams.
+    ///
+    pub fn is_my_teams_only(&self) -> bool {
+        self.my_teams_only
+    }
+
+    /// Set whether projects are limited to the user's teams.
+    ///
+    pub fn set_my_teams_only(&mut self, my_teams_only: bool) -> &mut Self {
+        self.my_teams_only = my_teams_only;
+        self.filter_projects()
+    }
+
+    /// Return for each listed project the name of its team if it starts a
+    /// team's projects, or None if it continues the team of the project
+    /// before it or no project has a team.
+    ///
+    pub fn project_headings(&self) -> Vec<Option<&str>> {
+        if self.projects.iter().all(|p| p.team.is_none()) {
+            return vec![None; self.projects.len()];
+        }
+        let mut previous: Option<&str> = None;
+        self.projects
+            .iter()
+            .enumerate()
+            .map(|(index, project)| {
+                let gid = project.team.as_ref().map(|t| t.gid.as_str());
+                if index > 0 && gid == previous {
+                    return None;
+                }
+                previous = gid;
+                Some(match &project.team {
+                    Some(team) => team.name.as_str(),
+                    None => "No team",
+                })
+            })
+            .collect()
+    }
+
+    /// Return the index of the project shown on the given row of the top
+    /// list, accounting for the team headings above projects.
+    ///
+    pub fn top_list_index_at_row(&self, row: usize) -> Option<usize> {
+        self.project_headings()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, heading)| heading.map(|_| None).into_iter().chain([Some(index)]))
+            .nth(row)
+            .flatten()
+    }
+
+    /// Return the sections of the current project.
+    ///
+    pub fn get_sections(&self) -> &Vec<Section> {
+        &self.sections
+    }
+
+    /// Set the sections of the current project.
+    ///
+    pub fn set_sections(&mut self, sections: Vec<Section>) -> &mut Self {
+        self.sections = sections;
+        self
+    }
+
+    /// Set the user's task list with its sections and tasks, ordering the
+    /// tasks by section as My Tasks does.
+    ///
+    pub fn set_user_task_list(
+        &mut self,
+        user_task_list: UserTaskList,
+        sections: Vec<Section>,
+        mut tasks: Vec<Task>,
+    ) -> &mut Self {
+        tasks.sort_by_key(|t| section_position(&sections, t));
+        self.user_task_list = Some(user_task_list);
+        self.sections = sections;
+        self.set_tasks(tasks)
+    }
+
+    /// Order the tasks of My Tasks by section again after tasks change
+    /// section or arrive, keeping the current view item on the same task.
+    ///
+    fn sort_my_tasks(&mut self) -> &mut Self {
+        if *self.current_view() != View::MyTasks || self.user_task_list.is_none() {
+            return self;
+        }
+        let current_task_gid = self.current_task().map(|t| t.gid.to_owned());
+        let sections = &self.sections;
+        self.tasks.sort_by_key(|t| section_position(sections, t));
+        if let Some(index) =
+            current_task_gid.and_then(|gid| self.tasks.iter().position(|t| t.gid == gid))
+        {
+            self.current_view_index = index;
+        }
+        self
+    }
+
+    /// Return for each task of My Tasks the name of its section if it starts
+    /// a section, or None if it continues the section of the task before it
+    /// or the view is not grouped by section.
+    ///
+    pub fn section_headings(&self) -> Vec<Option<&str>> {
+        if *self.current_view() != View::MyTasks || self.user_task_list.is_none() {
+            return vec![None; self.tasks.len()];
+        }
+        let mut previous: Option<&str> = None;
+        self.tasks
+            .iter()
+            .map(|task| {
+                let gid = task.assignee_section.as_ref().map(|s| s.gid.as_str());
+                if gid == previous {
+                    return None;
+                }
+                previous = gid;
+                Some(match &task.assignee_section {
+                    Some(section) => self
+                        .sections
+                        .iter()
+                        .find(|s| s.gid == section.gid)
+                        .map(|s| s.name.as_str())
+                        .unwrap_or(section.name.as_str()),
+                    None => "No section",
+                })
+            })
+            .collect()
+    }
+
+    /// Set the tags of the active workspace.
+    ///
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> &mut Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Return the current project.
+    ///
+    pub fn get_project(&self) -> Option<&Project> {
+        self.project.as_ref()
+    }
+
+    /// Return the network event which loads the current view, if any.
+    ///
+    fn current_view_event(&self) -> Option<NetworkEvent> {
+        match self.current_view() {
+            View::MyTasks => Some(NetworkEvent::MyTasks),
+            View::Inbox => Some(NetworkEvent::Inbox),
+            View::ProjectTasks => Some(NetworkEvent::ProjectTasks),
+            View::TaskDetail => self
+                .detail_task_gid
+                .as_ref()
+                .map(|gid| NetworkEvent::TaskDetail(gid.to_owned())),
+            View::Calendar => match self.view_stack.iter().rev().nth(1) {
+                Some(View::MyTasks) => Some(NetworkEvent::MyTasks),
+                Some(View::ProjectTasks) => Some(NetworkEvent::ProjectTasks),
+                _ => None,
+            },
+            View::Timeline => Some(NetworkEvent::ProjectTasks),
+            _ => None,
+        }
+    }
+
+    /// Apply the mutations to the local tasks and dispatch them to Asana,
+    /// concurrently if there are several.
+    ///
+    fn send(&mut self, mut mutations: Vec<Mutation>) {
+        for mutation in mutations.iter() {
+            self.apply_mutation(mutation);
+        }
+        if mutations
+            .iter()
+            .any(|m| matches!(m, Mutation::SetAssigneeSection { .. }))
+        {
+            self.sort_my_tasks();
+        }
+        match mutations.len() {
+            0 => (),
+            1 => self.dispatch(NetworkEvent::Mutate(mutations.remove(0))),
+            _ => self.dispatch(NetworkEvent::BulkMutate(mutations)),
+        }
+    }
+
+    /// Apply the mutation to the local copy of its task, removing the task
+    /// if it is deleted.
+    ///
+    fn apply_mutation(&mut self, mutation: &Mutation) {
+        if mutation.is_delete() {
+            self.remove_task(mutation.task_gid());
+        } else if let Some(task) = self.tasks.iter_mut().find(|t| t.gid == mutation.task_gid()) {
+            mutation.apply(task);
+        }
+    }
+
+    /// Return the range of tasks being selected from the anchor to the
+    /// current task.
+    ///
+    fn visual_range(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        let anchor = self.visual_anchor?;
+        if self.tasks.is_empty() {
+            return None;
+        }
+        let last = self.tasks.len() - 1;
+        let (start, end) = match anchor <= self.current_view_index {
+            true => (anchor, self.current_view_index),
+            false => (self.current_view_index, anchor),
+        };
+        Some(start.min(last)..=end.min(last))
+    }
+
+    /// Return the number of items in the current view.
+    ///
+    fn view_len(&self) -> usize {
+        match self.current_view() {
+            View::TaskDetail => self
+                .task_detail
+                .as_ref()
+                .map(|d| d.comments.len() + 1)
+                .unwrap_or(0),
+            View::Inbox => self.inbox_items.len(),
+            View::Calendar => match &self.calendar {
+                Some(calendar) => self.calendar_tasks(calendar.date).len(),
+                None => 0,
+            },
+            View::Timeline => self.timeline_tasks().len(),
+            View::PendingChanges => self.journal.entries().len(),
+            View::ImportPreview => self.import.as_ref().map(|b| b.rows.len()).unwrap_or(0),
+            _ if self.is_task_view() => self.tasks.len(),
+            _ => 0,
+        }
+    }
+
+    /// Mark the inbox story with the given GID as read or unread.
+    ///
+    fn set_inbox_item_read(&mut self, story_gid: &str, read: bool) {
+        if let Err(err) = self.inbox.set_read(story_gid, read) {
+            error!("Failed to update read inbox items: {}", err);
+        }
+    }
+
+    /// Return the tasks which can be picked for the input, being other
+    /// listed tasks to add as dependencies of the selected tasks or the
+    /// current task's dependencies to remove, matching the text entered.
+    ///
+    fn matching_tasks(&self, input: &Input) -> Vec<RelatedTask> {
+        let task = self.current_task().filter(|_| !self.uses_marks());
+        let candidates = match input.kind {
+            InputKind::AddDependency => {
+                let selected = self.selected_task_gids();
+                self.tasks
+                    .iter()
+                    .filter(|t| !selected.contains(&t.gid))
+                    .filter(|t| match task {
+                        Some(task) => !task.dependencies.iter().any(|d| d.gid == t.gid),
+                        None => true,
+                    })
+                    .map(|t| RelatedTask {
+                        gid: t.gid.to_owned(),
+                        name: t.name.to_owned(),
+                        completed: t.completed,
+                    })
+                    .collect()
+            }
+            InputKind::RemoveDependency => {
+                task.map(|t| t.dependencies.to_owned()).unwrap_or_default()
+            }
+            _ => vec![],
+        };
+        let query = input.buffer.trim().to_lowercase();
+        candidates
+            .into_iter()
+            .filter(|t| t.name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Return the teams which can be picked for the input, being None for
+    /// all teams followed by the teams matching the text entered.
+    ///
+    fn matching_teams(&self, input: &Input) -> Vec<Option<Team>> {
+        if input.kind != InputKind::Team {
+            return vec![];
+        }
+        let query = input.buffer.trim().to_lowercase();
+        let teams = self
+            .teams
+            .iter()
+            .filter(|t| t.name.to_lowercase().contains(&query))
+            .cloned()
+            .map(Some);
+        [None].into_iter().chain(teams).collect()
+    }
+
+    /// Return the number of items which can be picked for the current input.
+    ///
+    fn picker_len(&self) -> usize {
+        match &self.input {
+            Some(input) if input.kind == InputKind::Team => self.picker_teams().len(),
+            _ => self.picker_tasks().len(),
+        }
+    }
+
+    /// List the projects of the active workspace belonging to the picked
+    /// team, or to the user's teams if limited to them, grouped by team and
+    /// keeping the current top list item on the same project where possible.
+    ///
+    fn filter_projects(&mut self) -> &mut Self {
+        let teams = &self.teams;
+        let team = &self.team;
+        let team_index = |project: &Project| {
+            project
+                .team
+                .as_ref()
+                .and_then(|team| teams.iter().position(|t| t.gid == team.gid))
+        };
+        let mut projects: Vec<Project> = self
+            .workspace_projects
+            .iter()
+            .filter(|p| match team {
+                Some(team) => p.team.as_ref().map(|t| &t.gid) == Some(&team.gid),
+                None => !self.my_teams_only || teams.is_empty() || team_index(p).is_some(),
+            })
+            .cloned()
+            .collect();
+        // Order known teams as listed, then others by name, then no team.
+        projects.sort_by_key(|p| {
+            (
+                team_index(p).unwrap_or(teams.len()),
+                p.team.is_none(),
+                p.team
+                    .as_ref()
+                    .map(|t| (t.name.to_owned(), t.gid.to_owned())),
+            )
+        });
+        let current_project_gid = self
+            .projects
+            .get(self.current_top_list_index)
+            .map(|p| p.gid.to_owned());
+        self.current_top_list_index = current_project_gid
+            .and_then(|gid| projects.iter().position(|p| p.gid == gid))
+            .unwrap_or(0);
+        self.projects = projects;
+        self
+    }
+
+    /// Return the tasks of the timeline in the order shown.
+    ///
+    fn timeline_tasks(&self) -> Vec<&Task> {
+        self.timeline_sections()
+            .into_iter()
+            .flat_map(|(_, tasks)| tasks)
+            .collect()
+    }
+
+    /// Select the given day in the calendar, keeping the task being moved
+    /// selected.
+    ///
+    fn set_calendar_date(&mut self, date: NaiveDate) -> &mut Self {
+        let moving_task_gid = match self.calendar.as_mut() {
+            Some(calendar) => {
+                calendar.date = date;
+                calendar.index = 0;
+                calendar.moving_task_gid.to_owned()
+            }
+            None => return self,
+        };
+        match moving_task_gid {
+            Some(task_gid) => self.select_calendar_task(&task_gid),
+            None => self,
+        }
+    }
+
+    /// Select the task with the given GID if it is on the selected day.
+    ///
+    fn select_calendar_task(&mut self, task_gid: &str) -> &mut Self {
+        let index = match &self.calendar {
+            Some(calendar) => self
+                .calendar_tasks(calendar.date)
+                .iter()
+                .position(|t| t.gid == task_gid),
+            None => None,
+        };
+        if let (Some(calendar), Some(index)) = (self.calendar.as_mut(), index) {
+            calendar.index = index;
+        }
+        self
+    }
+
+    /// Return the index of the current item, being that of the task detail
+    /// view while it is open.
+    ///
+    fn view_index_mut(&mut self) -> &mut usize {
+        match self.current_view() {
+            View::TaskDetail => &mut self.detail_index,
+            View::Calendar if self.calendar.is_some() => &mut self.calendar.as_mut().unwrap().index,
+            View::Timeline if self.timeline.is_some() => &mut self.timeline.as_mut().unwrap().index,
+            _ => &mut self.current_view_index,
+        }
+    }
+
+    /// Dispatches an asynchronous network event.
+    ///
+    fn dispatch(&self, event: NetworkEvent) {
+        if let Some(net_sender) = &self.net_sender {
+            if let Err(err) = net_sender.send(event) {
+                error!("Recieved error from network dispatch: {}", err);
+            }
+        }
+    }
+}
+
+/// Return the position of the task's section among the sections of the
+/// user's task list, placing tasks without a known section last.
+///
+fn section_position(sections: &[Section], task: &Task) -> usize {
+    task.assignee_section
+        .as_ref()
+        .and_then(|section| sections.iter().position(|s| s.gid == section.gid))
+        .unwrap_or(sections.len())
+}
+
+/// Return the index of the name equal to the query ignoring case, or else
+/// the only name starting with it.
+///
+fn find_by_name(names: &[&str], query: &str) -> Option<usize> {
+    let query = query.to_lowercase();
+    if let Some(index) = names.iter().position(|n| n.to_lowercase() == query) {
+        return Some(index);
+    }
+    let matches: Vec<usize> = names
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| n.to_lowercase().starts_with(&query))
+        .map(|(i, _)| i)
+        .collect();
+    match matches.as_slice() {
+        [index] => Some(*index),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asana::Membership;
+    use crate::import::Row;
+    use crate::journal::Status;
+    use crate::keymap::Binding;
+    use crate::notify::Kind as NotificationKind;
+    use crossterm::event::{KeyCode, KeyEvent};
+    use fake::uuid::UUIDv4;
+    use fake::{Fake, Faker};
+    use tui::style::Color;
+    use uuid::Uuid;
+
+    #[test]
+    fn get_user() {
+        let user: User = Faker.fake();
+        let state = State {
+            user: Some(user.to_owned()),
+            ..State::default()
+        };
+        assert_eq!(user, *state.get_user().unwrap());
+    }
+
+    #[test]
+    fn set_user() {
+        let mut state = State::default();
+        let user: User = Faker.fake();
+        state.set_user(user.to_owned());
+        assert_eq!(user, state.user.unwrap());
+    }
+
+    #[test]
+    fn get_active_workspace() {
+        let workspaces = vec![
+            Faker.fake::<Workspace>(),
+            Faker.fake::<Workspace>(),
+            Faker.fake::<Workspace>(),
+        ];
+        let active_workspace = workspaces[0].to_owned();
+        let state = State {
+            active_workspace_gid: Some(active_workspace.gid.to_owned()),
+            workspaces,
+            ..State::default()
+        };
+        assert_eq!(active_workspace, *state.get_active_workspace().unwrap());
+    }
+
+    #[test]
+    fn set_active_workspace() {
+        let workspace_gid: Uuid = UUIDv4.fake();
+        let mut state = State::default();
+        state.set_active_workspace(workspace_gid.to_string());
+        assert_eq!(
+            workspace_gid.to_string(),
+            state.active_workspace_gid.unwrap()
+        );
+    }
+
+    #[test]
+    fn set_workspaces() {
+        let workspaces = vec![
+            Faker.fake::<Workspace>(),
+            Faker.fake::<Workspace>(),
+            Faker.fake::<Workspace>(),
+        ];
+        let mut state = State::default();
+        state.set_workspaces(workspaces.to_owned());
+        assert_eq!(workspaces, state.workspaces);
+    }
+
+    #[test]
+    fn set_terminal_size() {
+        let mut state = State::default();
+        let size = Rect::new(Faker.fake(), Faker.fake(), Faker.fake(), Faker.fake());
+        state.set_terminal_size(size);
+        assert_eq!(size, state.terminal_size);
+    }
+
+    #[test]
+    fn advance_spinner_index() {
+        let mut state = State::default();
+        state.advance_spinner_index();
+        assert_eq!(state.spinner_index, 1);
+        for _ in 0..SPINNER_FRAME_COUNT {
+            state.advance_spinner_index();
+        }
+        assert_eq!(state.spinner_index, 1);
+    }
+
+    #[test]
+    fn get_spinner_index() {
+        let state = State {
+            spinner_index: 2,
+            ..State::default()
+        };
+        assert_eq!(*state.get_spinner_index(), 2);
+    }
+
+    #[test]
+    fn current_focus() {
+        let mut state = State {
+            current_focus: Focus::Menu,
+            ..State::default()
+        };
+        assert_eq!(*state.current_focus(), Focus::Menu);
+        state.current_focus = Focus::View;
+        assert_eq!(*state.current_focus(), Focus::View);
+    }
+
+    #[test]
+    fn focus_menu() {
+        let mut state = State {
+            current_focus: Focus::View,
+            ..State::default()
+        };
+        state.focus_menu();
+        assert_eq!(state.current_focus, Focus::Menu);
+    }
+
+    #[test]
+    fn focus_view() {
+        let mut state = State {
+            current_focus: Focus::Menu,
+            ..State::default()
+        };
+        state.focus_view();
+        assert_eq!(state.current_focus, Focus::View);
+    }
+
+    #[test]
+    fn current_menu() {
+        let state = State {
+            current_menu: Menu::Status,
+            ..State::default()
+        };
+        assert_eq!(*state.current_menu(), Menu::Status);
+    }
+
+    #[test]
+    fn next_menu() {
+        let mut state = State {
+            current_menu: Menu::Status,
+            ..State::default()
+        };
+        state.next_menu();
+        assert_eq!(state.current_menu, Menu::Shortcuts);
+        state.next_menu();
+        assert_eq!(state.current_menu, Menu::TopList);
         state.next_menu();
-        assert_eq!(state.current_menu, Menu::Shortcuts);
-        state.next_menu();
+        assert_eq!(state.current_menu, Menu::Status);
+    }
+
+    #[test]
+    fn previous_menu() {
+        let mut state = State {
+            current_menu: Menu::Status,
+            ..State::default()
+        };
+        state.previous_menu();
+        assert_eq!(state.current_menu, Menu::TopList);
+        state.previous_menu();
+        assert_eq!(state.current_menu, Menu::Shortcuts);
+        state.previous_menu();
+        assert_eq!(state.current_menu, Menu::Status);
+    }
+
+    #[test]
+    fn select_status_menu() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            ..State::default()
+        };
+        state.select_status_menu();
+        assert_eq!(*state.view_stack.last().unwrap(), View::Welcome);
+    }
+
+    #[test]
+    fn current_shortcut_index() {
+        let state = State {
+            current_shortcut_index: 0,
+            ..State::default()
+        };
+        assert_eq!(*state.current_shortcut_index(), 0);
+    }
+
+    #[test]
+    fn next_shortcut_index() {
+        let mut state = State {
+            current_shortcut_index: 0,
+            ..State::default()
+        };
+        state.next_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 1);
+        state.next_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 2);
+        state.next_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 3);
+        state.next_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 4);
+        state.next_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 0);
+    }
+
+    #[test]
+    fn previous_shortcut_index() {
+        let mut state = State {
+            current_shortcut_index: 0,
+            ..State::default()
+        };
+        state.previous_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 4);
+        state.previous_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 3);
+        state.previous_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 2);
+        state.previous_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 1);
+        state.previous_shortcut_index();
+        assert_eq!(state.current_shortcut_index, 0);
+    }
+
+    #[test]
+    fn select_current_shortcut_index() {
+        let mut state = State {
+            current_shortcut_index: 0,
+            current_focus: Focus::Menu,
+            ..State::default()
+        };
+        state.select_current_shortcut_index();
+        assert_eq!(*state.view_stack.last().unwrap(), View::MyTasks);
+        assert_eq!(state.current_focus, Focus::View);
+        state.current_shortcut_index = 1;
+        state.select_current_shortcut_index();
+        assert_eq!(*state.view_stack.last().unwrap(), View::Inbox);
+        state.current_shortcut_index = 2;
+        state.select_current_shortcut_index();
+        assert_eq!(*state.view_stack.last().unwrap(), View::RecentlyModified);
+        assert_eq!(state.current_focus, Focus::View);
+    }
+
+    #[test]
+    fn current_top_list_index() {
+        let state = State {
+            current_top_list_index: 2,
+            ..State::default()
+        };
+        assert_eq!(*state.current_top_list_index(), 2);
+    }
+
+    #[test]
+    fn next_top_list_index_when_nonempty() {
+        let mut state = State {
+            current_top_list_index: 0,
+            projects: vec![Faker.fake::<Project>(), Faker.fake::<Project>()],
+            ..State::default()
+        };
+        state.next_top_list_index();
+        assert_eq!(state.current_top_list_index, 1);
+        state.next_top_list_index();
+        assert_eq!(state.current_top_list_index, 0);
+    }
+
+    #[test]
+    fn next_top_list_index_when_empty() {
+        let mut state = State {
+            current_top_list_index: 0,
+            projects: vec![],
+            ..State::default()
+        };
+        state.next_top_list_index();
+        assert_eq!(state.current_top_list_index, 0);
+    }
+
+    #[test]
+    fn previous_top_list_index_when_nonempty() {
+        let mut state = State {
+            current_top_list_index: 0,
+            projects: vec![Faker.fake::<Project>(), Faker.fake::<Project>()],
+            ..State::default()
+        };
+        state.previous_top_list_index();
+        assert_eq!(state.current_top_list_index, 1);
+        state.previous_top_list_index();
+        assert_eq!(state.current_top_list_index, 0);
+    }
+
+    #[test]
+    fn previous_top_list_index_when_empty() {
+        let mut state = State {
+            current_top_list_index: 0,
+            projects: vec![],
+            ..State::default()
+        };
+        state.previous_top_list_index();
+        assert_eq!(state.current_top_list_index, 0);
+    }
+
+    #[test]
+    fn current_view() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            ..State::default()
+        };
+        assert_eq!(*state.current_view(), View::MyTasks);
+        state.view_stack = vec![View::RecentlyCompleted];
+        assert_eq!(*state.current_view(), View::RecentlyCompleted);
+    }
+
+    #[test]
+    fn get_tasks() {
+        let tasks = vec![
+            Faker.fake::<Task>(),
+            Faker.fake::<Task>(),
+            Faker.fake::<Task>(),
+        ];
+        let state = State {
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        assert_eq!(tasks, *state.get_tasks());
+    }
+
+    #[test]
+    fn set_tasks() {
+        let mut state = State::default();
+        let tasks = vec![
+            Faker.fake::<Task>(),
+            Faker.fake::<Task>(),
+            Faker.fake::<Task>(),
+        ];
+        state.set_tasks(tasks.to_owned());
+        assert_eq!(tasks, state.tasks);
+    }
+
+    #[test]
+    fn get_projects() {
+        let projects = vec![
+            Faker.fake::<Project>(),
+            Faker.fake::<Project>(),
+            Faker.fake::<Project>(),
+        ];
+        let state = State {
+            projects: projects.to_owned(),
+            ..State::default()
+        };
+        assert_eq!(projects, *state.get_projects());
+    }
+
+    #[test]
+    fn set_projects() {
+        let mut state = State::default();
+        let project = || Project {
+            team: None,
+            ..Faker.fake()
+        };
+        let projects = vec![project(), project(), project()];
+        state.set_projects(projects.to_owned());
+        assert_eq!(projects, state.projects);
+    }
+
+    #[test]
+    fn next_view_index() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![Faker.fake::<Task>(), Faker.fake::<Task>()],
+            ..State::default()
+        };
+        state.next_view_index();
+        assert_eq!(state.current_view_index, 1);
+        state.next_view_index();
+        assert_eq!(state.current_view_index, 0);
+    }
+
+    #[test]
+    fn set_view_index_at_row() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: (0..10).map(|_| Faker.fake::<Task>()).collect(),
+            ..State::default()
+        };
+        state.set_view_index_at_row(2, 4);
+        assert_eq!(state.current_view_index, 2);
+        state.current_view_index = 6;
+        state.set_view_index_at_row(0, 4);
+        assert_eq!(state.current_view_index, 3);
+        state.set_view_index_at_row(20, 4);
+        assert_eq!(state.current_view_index, 3);
+    }
+
+    #[test]
+    fn next_and_previous_panel() {
+        let mut state = State {
+            current_focus: Focus::Menu,
+            current_menu: Menu::Shortcuts,
+            ..State::default()
+        };
+        state.next_panel();
         assert_eq!(state.current_menu, Menu::TopList);
-        state.next_menu();
+        state.next_panel();
+        assert_eq!(state.current_focus, Focus::View);
+        state.next_panel();
+        assert_eq!(state.current_focus, Focus::Menu);
         assert_eq!(state.current_menu, Menu::Status);
+        state.previous_panel();
+        assert_eq!(state.current_focus, Focus::View);
+        state.previous_panel();
+        assert_eq!(state.current_focus, Focus::Menu);
+        assert_eq!(state.current_menu, Menu::TopList);
+    }
+
+    #[test]
+    fn first_and_last_view_index() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![Faker.fake::<Task>(), Faker.fake::<Task>()],
+            ..State::default()
+        };
+        state.last_view_index();
+        assert_eq!(state.current_view_index, 1);
+        state.first_view_index();
+        assert_eq!(state.current_view_index, 0);
+    }
+
+    #[test]
+    fn previous_view_index() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![Faker.fake::<Task>(), Faker.fake::<Task>()],
+            ..State::default()
+        };
+        state.previous_view_index();
+        assert_eq!(state.current_view_index, 1);
+        state.previous_view_index();
+        assert_eq!(state.current_view_index, 0);
+    }
+
+    #[test]
+    fn current_task() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            current_view_index: 1,
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        assert_eq!(*state.current_task().unwrap(), tasks[1]);
+        state.view_stack = vec![View::Welcome];
+        assert!(state.current_task().is_none());
+    }
+
+    #[test]
+    fn set_tasks_reapplies_pending_mutations() {
+        let task = Task {
+            completed: false,
+            ..Faker.fake::<Task>()
+        };
+        let mut state = State::default();
+        state
+            .journal
+            .push(
+                Mutation::Complete {
+                    task_gid: task.gid.to_owned(),
+                    completed: true,
+                },
+                None,
+            )
+            .unwrap();
+        state.set_tasks(vec![task]);
+        assert!(state.tasks[0].completed);
+    }
+
+    #[test]
+    fn add_task() {
+        let task: Task = Faker.fake();
+        let mut state = State {
+            tasks: vec![task.to_owned()],
+            ..State::default()
+        };
+        let added: Task = Faker.fake();
+        state.add_task(added.to_owned());
+        assert_eq!(state.tasks, vec![task.to_owned(), added]);
+        let updated = Task {
+            gid: task.gid.to_owned(),
+            ..Faker.fake::<Task>()
+        };
+        state.add_task(updated.to_owned());
+        assert_eq!(state.tasks[0], updated);
+        assert_eq!(state.tasks.len(), 2);
+    }
+
+    #[test]
+    fn remove_task() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
+        let mut state = State {
+            view_stack: vec![View::ProjectTasks],
+            current_view_index: 1,
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        state.remove_task(&tasks[1].gid);
+        assert_eq!(state.tasks, vec![tasks[0].to_owned()]);
+        assert_eq!(state.current_view_index, 0);
+    }
+
+    #[test]
+    fn update_task() {
+        let task: Task = Faker.fake();
+        let mut state = State {
+            tasks: vec![Faker.fake::<Task>(), task.to_owned()],
+            ..State::default()
+        };
+        let updated = Task {
+            gid: task.gid.to_owned(),
+            ..Faker.fake::<Task>()
+        };
+        state.update_task(updated.to_owned());
+        assert_eq!(state.tasks[1], updated);
+    }
+
+    #[test]
+    fn toggle_current_task_completed() {
+        let task = Task {
+            completed: false,
+            ..Faker.fake::<Task>()
+        };
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![task],
+            ..State::default()
+        };
+        state.toggle_current_task_completed();
+        assert!(state.tasks[0].completed);
+        state.toggle_current_task_completed();
+        assert!(!state.tasks[0].completed);
+    }
+
+    #[test]
+    fn toggle_current_task_marked() {
+        let tasks: Vec<Task> = vec![Faker.fake(), Faker.fake()];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            current_view_index: 1,
+            ..State::default()
+        };
+        assert_eq!(state.selected_task_gids(), vec![tasks[1].gid.to_owned()]);
+        state.toggle_current_task_marked();
+        state.current_view_index = 0;
+        assert!(state.has_marks());
+        assert_eq!(state.selected_task_gids(), vec![tasks[1].gid.to_owned()]);
+        state.toggle_current_task_marked();
+        assert_eq!(
+            state.selected_task_gids(),
+            vec![tasks[0].gid.to_owned(), tasks[1].gid.to_owned()]
+        );
+        state.clear_marks();
+        assert!(!state.has_marks());
+    }
+
+    #[test]
+    fn toggle_visual_mode() {
+        let tasks: Vec<Task> = vec![Faker.fake(), Faker.fake(), Faker.fake()];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            current_view_index: 2,
+            ..State::default()
+        };
+        state.toggle_visual_mode();
+        state.previous_view_index();
+        assert!(state.is_marked(&tasks[1].gid));
+        assert!(!state.is_marked(&tasks[0].gid));
+        state.toggle_visual_mode();
+        assert!(state.visual_anchor.is_none());
+        assert_eq!(
+            state.marked_task_gids,
+            vec![tasks[1].gid.to_owned(), tasks[2].gid.to_owned()]
+        );
+    }
+
+    #[test]
+    fn toggle_marked_tasks_completed() {
+        let mut tasks: Vec<Task> = vec![Faker.fake(), Faker.fake(), Faker.fake()];
+        tasks[0].completed = true;
+        tasks[1].completed = false;
+        tasks[2].completed = false;
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks,
+            marked_task_gids: vec![],
+            ..State::default()
+        };
+        state.marked_task_gids = vec![state.tasks[0].gid.to_owned(), state.tasks[1].gid.to_owned()];
+        state.toggle_current_task_completed();
+        assert!(state.tasks[0].completed);
+        assert!(state.tasks[1].completed);
+        assert!(!state.tasks[2].completed);
+        assert!(!state.has_marks());
+    }
+
+    #[test]
+    fn submit_input_delete_marked() {
+        let tasks: Vec<Task> = vec![Faker.fake(), Faker.fake(), Faker.fake()];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            marked_task_gids: vec![tasks[0].gid.to_owned(), tasks[2].gid.to_owned()],
+            ..State::default()
+        };
+        state.start_input(InputKind::Delete);
+        state.push_input_char('n');
+        state.submit_input();
+        assert_eq!(state.tasks.len(), 3);
+        state.start_input(InputKind::Delete);
+        state.push_input_char('y');
+        state.submit_input();
+        assert_eq!(state.tasks, vec![tasks[1].to_owned()]);
+    }
+
+    #[test]
+    fn pick_dependency() {
+        let tasks = vec![
+            Task {
+                dependencies: vec![],
+                ..Faker.fake()
+            },
+            Task {
+                name: String::from("Draft brief"),
+                ..Faker.fake()
+            },
+            Task {
+                name: String::from("Review brief"),
+                ..Faker.fake()
+            },
+        ];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        state.start_input(InputKind::RemoveDependency);
+        assert!(state.input.is_none());
+        state.start_input(InputKind::AddDependency);
+        assert!(state.is_picking());
+        assert_eq!(state.picker_tasks().len(), 2);
+        for c in "BRIEF".chars() {
+            state.push_input_char(c);
+        }
+        state.previous_picker_index();
+        assert_eq!(*state.get_picker_index(), 1);
+        assert_eq!(state.picker_tasks()[1].name, "Review brief");
+        state.submit_input();
+        assert_eq!(state.tasks[0].dependencies.len(), 1);
+        assert_eq!(state.tasks[0].dependencies[0].gid, tasks[2].gid);
+        assert!(!state.tasks[0].dependencies[0].completed);
+
+        state.start_input(InputKind::AddDependency);
+        assert_eq!(state.picker_tasks()[0].gid, tasks[1].gid);
+        state
+            .cancel_input()
+            .start_input(InputKind::RemoveDependency);
+        assert_eq!(state.picker_tasks()[0].gid, tasks[2].gid);
+        state.submit_input();
+        assert!(state.tasks[0].dependencies.is_empty());
+        state.undo();
+        assert_eq!(state.tasks[0].dependencies[0].gid, tasks[2].gid);
+    }
+
+    #[test]
+    fn submit_input_section() {
+        let project: Project = Faker.fake();
+        let task = Task {
+            memberships: vec![Membership {
+                project_gid: project.gid.to_owned(),
+                section: Faker.fake(),
+            }],
+            ..Faker.fake()
+        };
+        let section: Section = Faker.fake();
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![task.to_owned()],
+            project: Some(project.to_owned()),
+            sections: vec![section.to_owned()],
+            ..State::default()
+        };
+        state.start_input(InputKind::Section);
+        assert!(state.input.is_none());
+        state.view_stack = vec![View::ProjectTasks];
+        state.start_input(InputKind::Section);
+        assert!(state.input.is_some());
+        state.input.as_mut().unwrap().buffer = section.name.to_uppercase();
+        state.submit_input();
+        assert!(state.input.is_none());
+        assert_eq!(state.tasks[0].memberships[0].section, section);
+        state.undo();
+        assert_eq!(state.tasks[0].memberships, task.memberships);
+    }
+
+    #[test]
+    fn projects_by_team() {
+        let mut teams = vec![Faker.fake::<Team>(), Faker.fake::<Team>()];
+        teams[0].name = String::from("Design");
+        teams[1].name = String::from("Engineering");
+        let project = |team: Option<&Team>| Project {
+            team: team.cloned(),
+            ..Faker.fake()
+        };
+        let projects = vec![
+            project(Some(&teams[1])),
+            project(None),
+            project(Some(&teams[0])),
+            project(Some(&teams[1])),
+        ];
+        let mut state = State::default();
+        state
+            .set_teams(teams.to_owned())
+            .set_projects(projects.to_owned());
+        assert_eq!(
+            state.projects,
+            vec![
+                projects[2].to_owned(),
+                projects[0].to_owned(),
+                projects[3].to_owned(),
+                projects[1].to_owned()
+            ]
+        );
+        assert_eq!(
+            state.project_headings(),
+            vec![
+                Some(teams[0].name.as_str()),
+                Some(teams[1].name.as_str()),
+                None,
+                Some("No team")
+            ]
+        );
+        assert_eq!(state.top_list_index_at_row(0), None);
+        assert_eq!(state.top_list_index_at_row(3), Some(1));
+        assert_eq!(state.top_list_index_at_row(6), Some(3));
+
+        state.current_top_list_index = 2;
+        state.set_teams(vec![teams[1].to_owned(), teams[0].to_owned()]);
+        assert_eq!(state.projects[state.current_top_list_index], projects[3]);
+        state.set_teams(vec![]);
+        assert_eq!(state.projects[state.current_top_list_index], projects[3]);
+        assert_eq!(
+            state.project_headings().iter().flatten().count(),
+            teams.len() + 1
+        );
+
+        state
+            .set_my_teams_only(true)
+            .set_teams(vec![teams[1].to_owned()]);
+        assert_eq!(
+            state.projects,
+            vec![projects[0].to_owned(), projects[3].to_owned()]
+        );
+
+        state.set_my_teams_only(false).set_teams(teams.to_owned());
+        state.start_input(InputKind::Team);
+        state.input.as_mut().unwrap().buffer = String::from("DES");
+        assert_eq!(state.picker_teams(), vec![None, Some(teams[0].to_owned())]);
+        state.next_picker_index().submit_input();
+        assert_eq!(state.team, Some(teams[0].to_owned()));
+        assert_eq!(state.projects, vec![projects[2].to_owned()]);
+        assert_eq!(state.current_menu, Menu::TopList);
+
+        state.start_input(InputKind::Team).submit_input();
+        assert_eq!(state.team, None);
+        assert_eq!(state.projects.len(), projects.len());
+    }
+
+    #[test]
+    fn my_tasks_by_section() {
+        let sections = vec![Faker.fake::<Section>(), Faker.fake::<Section>()];
+        let task = |section: Option<&Section>| Task {
+            assignee_section: section.cloned(),
+            ..Faker.fake()
+        };
+        let tasks = vec![
+            task(None),
+            task(Some(&sections[1])),
+            task(Some(&sections[0])),
+            task(Some(&sections[1])),
+        ];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            ..State::default()
+        };
+        state.set_user_task_list(Faker.fake(), sections.to_owned(), tasks.to_owned());
+        assert_eq!(
+            state.tasks,
+            vec![
+                tasks[2].to_owned(),
+                tasks[1].to_owned(),
+                tasks[3].to_owned(),
+                tasks[0].to_owned()
+            ]
+        );
+        assert_eq!(
+            state.section_headings(),
+            vec![
+                Some(sections[0].name.as_str()),
+                Some(sections[1].name.as_str()),
+                None,
+                Some("No section")
+            ]
+        );
+        state.set_view_index_at_row(3, 10);
+        assert_eq!(state.current_view_index, 1);
+        state.set_view_index_at_row(2, 10);
+        assert_eq!(state.current_view_index, 1);
+
+        state.start_input(InputKind::Section);
+        state.input.as_mut().unwrap().buffer = sections[0].name.to_owned();
+        state.submit_input();
+        assert_eq!(
+            state.tasks[1].assignee_section,
+            Some(sections[0].to_owned())
+        );
+        assert_eq!(state.tasks[1].memberships, tasks[1].memberships);
+        state.undo();
+        assert_eq!(
+            state.tasks[1].assignee_section,
+            Some(sections[1].to_owned())
+        );
+
+        state.current_view_index = 2;
+        state.start_input(InputKind::Section);
+        state.input.as_mut().unwrap().buffer = sections[0].name.to_owned();
+        state.submit_input();
+        assert_eq!(
+            state.tasks.iter().map(|t| &t.gid).collect::<Vec<&String>>(),
+            vec![&tasks[2].gid, &tasks[3].gid, &tasks[1].gid, &tasks[0].gid]
+        );
+        assert_eq!(state.current_view_index, 1);
+        assert_eq!(
+            state.section_headings(),
+            vec![
+                Some(sections[0].name.as_str()),
+                None,
+                Some(sections[1].name.as_str()),
+                Some("No section")
+            ]
+        );
+        let added = task(Some(&sections[0]));
+        state.add_task(added.to_owned());
+        assert_eq!(state.tasks[2], added);
+        assert_eq!(state.current_view_index, 1);
+    }
+
+    #[test]
+    fn undo_redo() {
+        let task = Task {
+            completed: false,
+            ..Faker.fake::<Task>()
+        };
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![task.to_owned()],
+            ..State::default()
+        };
+        state.toggle_current_task_completed();
+        assert_eq!(
+            state.get_message(),
+            Some(format!("Completed '{}' — press u to undo", task.name).as_str())
+        );
+        state.undo();
+        assert!(!state.tasks[0].completed);
+        assert_eq!(
+            state.get_message(),
+            Some(format!("Undid: Completed '{}' — press ^r to redo", task.name).as_str())
+        );
+        let overrides = HashMap::from([(Action::Undo, Binding::One(String::from("z")))]);
+        state.set_keymap(Keymap::with_overrides(&overrides).unwrap());
+        state.redo();
+        assert!(state.tasks[0].completed);
+        assert_eq!(
+            state.get_message(),
+            Some(format!("Redid: Completed '{}' — press z to undo", task.name).as_str())
+        );
+        state.undo().undo();
+        assert_eq!(state.get_message(), Some("Nothing to undo"));
+        assert!(!state.tasks[0].completed);
+    }
+
+    #[test]
+    fn undo_skips_irreversible_changes() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![Faker.fake()],
+            ..State::default()
+        };
+        state.toggle_current_task_completed();
+        state.start_input(InputKind::Comment);
+        state.push_input_char('x');
+        state.submit_input();
+        let task_gid = state.tasks[0].gid.to_owned();
+        state.mutate(Mutation::AddTag {
+            task_gid,
+            tag_gid: Faker.fake(),
+            tag_name: Faker.fake(),
+        });
+        assert_eq!(state.undo_stack.len(), 1);
+        state.toggle_current_task_completed();
+        state.undo();
+        assert_eq!(state.redo_stack.len(), 1);
+        state.toggle_current_task_completed();
+        assert!(state.redo_stack.is_empty());
+    }
+
+    #[test]
+    fn undo_keeps_existing_dependencies() {
+        let dependency: RelatedTask = Faker.fake();
+        let tasks = vec![
+            Task {
+                dependencies: vec![dependency.to_owned()],
+                ..Faker.fake()
+            },
+            Task {
+                dependencies: vec![],
+                ..Faker.fake()
+            },
+        ];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        state.mutate_all(
+            tasks
+                .iter()
+                .map(|task| Mutation::AddDependency {
+                    task_gid: task.gid.to_owned(),
+                    dependency_gid: dependency.gid.to_owned(),
+                    dependency_name: dependency.name.to_owned(),
+                })
+                .collect(),
+        );
+        assert_eq!(
+            state.undo_stack[0].inverses,
+            vec![Mutation::RemoveDependency {
+                task_gid: tasks[1].gid.to_owned(),
+                dependency_gid: dependency.gid.to_owned(),
+                dependency_name: dependency.name.to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn set_tasks_keeps_marks() {
+        let tasks: Vec<Task> = vec![Faker.fake(), Faker.fake()];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            marked_task_gids: vec![tasks[0].gid.to_owned(), tasks[1].gid.to_owned()],
+            ..State::default()
+        };
+        state.set_tasks(vec![tasks[1].to_owned()]);
+        assert_eq!(state.marked_task_gids, vec![tasks[1].gid.to_owned()]);
+    }
+
+    #[test]
+    fn start_input() {
+        let task: Task = Faker.fake();
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![task.to_owned()],
+            ..State::default()
+        };
+        state.start_input(InputKind::Rename);
+        assert_eq!(
+            state.input,
+            Some(Input {
+                kind: InputKind::Rename,
+                buffer: task.name,
+            })
+        );
+    }
+
+    #[test]
+    fn submit_input() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![Faker.fake::<Task>()],
+            ..State::default()
+        };
+        state.start_input(InputKind::DueDate);
+        state.input.as_mut().unwrap().buffer.clear();
+        "2021-12-31".chars().for_each(|c| {
+            state.push_input_char(c);
+        });
+        state.submit_input();
+        assert!(state.input.is_none());
+        assert_eq!(state.tasks[0].due_on, Some(String::from("2021-12-31")));
+    }
+
+    #[test]
+    fn submit_input_invalid_due_date() {
+        let task: Task = Faker.fake();
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![task.to_owned()],
+            input: Some(Input {
+                kind: InputKind::DueDate,
+                buffer: String::from("someday"),
+            }),
+            ..State::default()
+        };
+        state.submit_input();
+        assert!(state.input.is_none());
+        assert_eq!(state.tasks[0].due_on, task.due_on);
+    }
+
+    #[test]
+    fn start_input_export() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            project: Some(Faker.fake()),
+            ..State::default()
+        };
+        state.start_input(InputKind::Export);
+        assert!(state.input.is_none());
+        state.view_stack = vec![View::ProjectTasks];
+        state.start_input(InputKind::Export);
+        assert_eq!(
+            state.input,
+            Some(Input {
+                kind: InputKind::Export,
+                buffer: String::from("md"),
+            })
+        );
+    }
+
+    #[test]
+    fn run_command_due() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![Faker.fake::<Task>()],
+            ..State::default()
+        };
+        state.start_input(InputKind::Command);
+        ":due 2021-12-31".chars().for_each(|c| {
+            state.push_input_char(c);
+        });
+        state.submit_input();
+        assert!(state.input.is_none());
+        assert_eq!(state.tasks[0].due_on, Some(String::from("2021-12-31")));
+        assert_eq!(
+            state.history.entries(),
+            &vec![String::from(":due 2021-12-31")]
+        );
     }
 
     #[test]
-    fn previous_menu() {
+    fn run_command_project() {
+        let mut projects: Vec<Project> = vec![Faker.fake(), Faker.fake()];
+        projects[0].name = String::from("Marketing");
+        projects[1].name = String::from("Mobile App");
         let mut state = State {
-            current_menu: Menu::Status,
+            projects: projects.to_owned(),
             ..State::default()
         };
-        state.previous_menu();
-        assert_eq!(state.current_menu, Menu::TopList);
-        state.previous_menu();
-        assert_eq!(state.current_menu, Menu::Shortcuts);
-        state.previous_menu();
-        assert_eq!(state.current_menu, Menu::Status);
+        state.run_command("project mob");
+        assert_eq!(state.project, Some(projects[1].to_owned()));
+        assert_eq!(*state.current_view(), View::ProjectTasks);
+        state.run_command("project Sales");
+        assert_eq!(state.get_message(), Some("No project matches 'Sales'"));
     }
 
     #[test]
-    fn select_status_menu() {
+    fn run_command_unavailable() {
+        let mut state = State::default();
+        state.run_command("tag urgent");
+        assert_eq!(
+            state.get_message(),
+            Some("Command 'tag urgent' is unavailable here")
+        );
+        state.run_command("frobnicate");
+        assert_eq!(state.get_message(), Some("Unknown command 'frobnicate'"));
+    }
+
+    #[test]
+    fn run_command_assign() {
+        let mut alice: User = Faker.fake();
+        alice.name = String::from("Alice Smith");
+        alice.email = String::new();
+        let task = Task {
+            assignee: None,
+            ..Faker.fake()
+        };
+        let other = Task {
+            assignee: Some(alice.to_owned()),
+            ..Faker.fake()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
         let mut state = State {
+            net_sender: Some(tx),
             view_stack: vec![View::MyTasks],
+            tasks: vec![task.to_owned(), other],
             ..State::default()
         };
-        state.select_status_menu();
-        assert_eq!(*state.view_stack.last().unwrap(), View::Welcome);
+        assert_eq!(state.resolve_assignee("me"), Some(String::from("me")));
+        state.run_command("assign @alice");
+        match rx.try_recv() {
+            Ok(NetworkEvent::Mutate(mutation)) => assert_eq!(
+                mutation,
+                Mutation::Assign {
+                    task_gid: task.gid.to_owned(),
+                    assignee: Some(alice.gid.to_owned()),
+                }
+            ),
+            event => panic!("Unexpected event {:?}", event),
+        }
+        state.run_command("assign @bob");
+        assert_eq!(state.get_message(), Some("No user matches '@bob'"));
+        assert!(rx.try_recv().is_err());
     }
 
     #[test]
-    fn current_shortcut_index() {
-        let state = State {
-            current_shortcut_index: 0,
+    fn complete_input() {
+        let mut tag: Tag = Faker.fake();
+        tag.name = String::from("urgent");
+        let mut state = State {
+            tags: vec![tag],
             ..State::default()
         };
-        assert_eq!(*state.current_shortcut_index(), 0);
+        state.start_input(InputKind::Command);
+        state.push_input_char('t');
+        state.complete_input();
+        assert_eq!(state.input.as_ref().unwrap().buffer, "tag ");
+        state.push_input_char('U');
+        state.complete_input();
+        assert_eq!(state.input.as_ref().unwrap().buffer, "tag urgent");
     }
 
     #[test]
-    fn next_shortcut_index() {
+    fn history_input() -> anyhow::Result<()> {
+        let mut history = History::default();
+        history.push("refresh")?;
+        history.push("due fri")?;
+        let mut state = State::default();
+        state.set_history(history);
+        state.start_input(InputKind::Command);
+        state.previous_history_input();
+        assert_eq!(state.input.as_ref().unwrap().buffer, "due fri");
+        state.previous_history_input().previous_history_input();
+        assert_eq!(state.input.as_ref().unwrap().buffer, "refresh");
+        state.next_history_input();
+        assert_eq!(state.input.as_ref().unwrap().buffer, "due fri");
+        state.next_history_input();
+        assert_eq!(state.input.as_ref().unwrap().buffer, "");
+        Ok(())
+    }
+
+    #[test]
+    fn set_import() {
         let mut state = State {
-            current_shortcut_index: 0,
+            view_stack: vec![View::ProjectTasks],
+            current_view_index: 2,
             ..State::default()
         };
-        state.next_shortcut_index();
-        assert_eq!(state.current_shortcut_index, 1);
-        state.next_shortcut_index();
-        assert_eq!(state.current_shortcut_index, 2);
-        state.next_shortcut_index();
-        assert_eq!(state.current_shortcut_index, 0);
+        state.set_import(import_batch());
+        assert_eq!(*state.current_view(), View::ImportPreview);
+        assert_eq!(state.current_view_index, 0);
+        assert_eq!(*state.current_focus(), Focus::View);
+        state.set_import(import_batch());
+        assert_eq!(state.view_stack.len(), 2);
     }
 
     #[test]
-    fn previous_shortcut_index() {
+    fn set_import_status() {
         let mut state = State {
-            current_shortcut_index: 0,
+            import: Some(import_batch()),
             ..State::default()
         };
-        state.previous_shortcut_index();
-        assert_eq!(state.current_shortcut_index, 2);
-        state.previous_shortcut_index();
-        assert_eq!(state.current_shortcut_index, 1);
-        state.previous_shortcut_index();
-        assert_eq!(state.current_shortcut_index, 0);
+        let status = ImportStatus::Created(Faker.fake());
+        state.set_import_status(1, status.to_owned());
+        assert_eq!(state.import.unwrap().rows[1].status, status);
     }
 
     #[test]
-    fn select_current_shortcut_index() {
+    fn inbox() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
+        let item = |task: &Task| InboxItem {
+            task_gid: task.gid.to_owned(),
+            ..Faker.fake()
+        };
+        let items = vec![item(&tasks[0]), item(&tasks[1]), item(&tasks[1])];
         let mut state = State {
-            current_shortcut_index: 0,
-            current_focus: Focus::Menu,
+            view_stack: vec![View::Inbox],
+            current_focus: Focus::View,
             ..State::default()
         };
-        state.select_current_shortcut_index();
-        assert_eq!(*state.view_stack.last().unwrap(), View::MyTasks);
-        assert_eq!(state.current_focus, Focus::View);
-        state.current_shortcut_index = 1;
-        state.select_current_shortcut_index();
-        assert_eq!(*state.view_stack.last().unwrap(), View::RecentlyModified);
-        assert_eq!(state.current_focus, Focus::View);
+        state.set_inbox(tasks.to_owned(), items.to_owned());
+        assert_eq!(state.current_context(), Context::Inbox);
+        assert_eq!(state.unread_inbox_count(), 3);
+        state.next_view_index();
+        assert_eq!(state.current_task(), Some(&tasks[1]));
+
+        state.set_inbox(tasks.to_owned(), items[1..].to_vec());
+        assert_eq!(*state.current_view_index(), 0);
+        assert_eq!(state.current_inbox_item(), Some(&items[1]));
+
+        state.toggle_current_inbox_item_read();
+        assert!(state.is_inbox_item_read(&items[1].story_gid));
+        state.toggle_current_inbox_item_read();
+        assert_eq!(state.unread_inbox_count(), 2);
+
+        state.open_task_detail();
+        assert_eq!(*state.current_view(), View::TaskDetail);
+        assert_eq!(state.current_task(), Some(&tasks[1]));
+        assert_eq!(state.unread_inbox_count(), 1);
+        state.close_task_detail();
+        assert_eq!(*state.current_view(), View::Inbox);
     }
 
     #[test]
-    fn current_top_list_index() {
-        let state = State {
-            current_top_list_index: 2,
+    fn notify() {
+        let notification = |kind, key: &str| Notification {
+            kind,
+            key: key.to_owned(),
+            message: Faker.fake(),
+        };
+        let mut state = State {
+            notifications: Notifications {
+                due_soon: true,
+                assignments: true,
+                bell: true,
+                ..Notifications::default()
+            },
             ..State::default()
         };
-        assert_eq!(*state.current_top_list_index(), 2);
+        let raised = state.notify(vec![
+            notification(NotificationKind::DueSoon, "1"),
+            notification(NotificationKind::Mention, "2"),
+            notification(NotificationKind::Assignment, "3"),
+        ]);
+        assert_eq!(raised.len(), 2);
+        assert_eq!(state.get_message(), Some(raised[1].message.as_str()));
+        assert!(state.take_bell());
+        assert!(!state.take_bell());
+        assert!(state
+            .notify(vec![notification(NotificationKind::DueSoon, "1")])
+            .is_empty());
+        assert_eq!(state.get_notification_count(), 2);
+        state.select_current_shortcut_index();
+        assert_eq!(state.get_notification_count(), 0);
     }
 
     #[test]
-    fn next_top_list_index_when_nonempty() {
+    fn start_notification_check() {
         let mut state = State {
-            current_top_list_index: 0,
-            projects: vec![Faker.fake::<Project>(), Faker.fake::<Project>()],
+            notifications: Notifications {
+                mentions: true,
+                interval: 0,
+                ..Notifications::default()
+            },
             ..State::default()
         };
-        state.next_top_list_index();
-        assert_eq!(state.current_top_list_index, 1);
-        state.next_top_list_index();
-        assert_eq!(state.current_top_list_index, 0);
+        assert!(state.start_notification_check().is_none());
+        state.user = Some(Faker.fake());
+        state.active_workspace_gid = Some(Faker.fake());
+        let since = state.start_notification_check().unwrap();
+        assert!(Utc::now() - since >= Duration::seconds(60));
+        assert!(state.start_notification_check().is_none());
+        state.notifications_checked_at = Some(Utc::now() - Duration::seconds(61));
+        assert!(state.start_notification_check().is_some());
     }
 
     #[test]
-    fn next_top_list_index_when_empty() {
+    fn current_permalink() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
+        let project = Project {
+            permalink_url: Some(Faker.fake()),
+            ..Faker.fake()
+        };
         let mut state = State {
-            current_top_list_index: 0,
-            projects: vec![],
+            view_stack: vec![View::ProjectTasks],
+            projects: vec![project.to_owned()],
+            project: Some(project.to_owned()),
+            tasks: tasks.to_owned(),
+            current_view_index: 1,
+            current_focus: Focus::View,
             ..State::default()
         };
-        state.next_top_list_index();
-        assert_eq!(state.current_top_list_index, 0);
+        assert_eq!(state.current_permalink(), tasks[1].permalink_url.as_deref());
+        state.tasks.clear();
+        assert_eq!(state.current_permalink(), project.permalink_url.as_deref());
+        state.focus_menu();
+        assert_eq!(state.current_permalink(), None);
+        state.current_menu = Menu::TopList;
+        assert_eq!(state.current_permalink(), project.permalink_url.as_deref());
     }
 
     #[test]
-    fn previous_top_list_index_when_nonempty() {
+    fn open_and_close_task_detail() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
         let mut state = State {
-            current_top_list_index: 0,
-            projects: vec![Faker.fake::<Project>(), Faker.fake::<Project>()],
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            current_view_index: 1,
+            current_focus: Focus::View,
             ..State::default()
         };
-        state.previous_top_list_index();
-        assert_eq!(state.current_top_list_index, 1);
-        state.previous_top_list_index();
-        assert_eq!(state.current_top_list_index, 0);
+        state.open_task_detail();
+        assert_eq!(*state.current_view(), View::TaskDetail);
+        assert_eq!(state.current_task(), Some(&tasks[1]));
+        assert_eq!(state.current_context(), Context::TaskDetail);
+        assert!(state.get_task_detail().is_none());
+        state.open_task_detail();
+        assert_eq!(state.view_stack.len(), 2);
+
+        state.set_task_detail(TaskDetail {
+            task_gid: tasks[0].gid.to_owned(),
+            ..TaskDetail::default()
+        });
+        assert!(state.get_task_detail().is_none());
+        let task_detail = TaskDetail {
+            task_gid: tasks[1].gid.to_owned(),
+            comments: vec![Faker.fake(), Faker.fake()],
+            ..TaskDetail::default()
+        };
+        state.set_task_detail(task_detail.to_owned());
+        assert_eq!(state.get_task_detail(), Some(&task_detail));
+        assert!(state.shows_task_detail(&tasks[1].gid));
+
+        state.close_task_detail();
+        assert_eq!(*state.current_view(), View::MyTasks);
+        assert_eq!(*state.current_view_index(), 1);
+        assert!(state.get_task_detail().is_none());
     }
 
     #[test]
-    fn previous_top_list_index_when_empty() {
+    fn navigate_task_detail() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
         let mut state = State {
-            current_top_list_index: 0,
-            projects: vec![],
+            view_stack: vec![View::MyTasks, View::TaskDetail],
+            tasks: tasks.to_owned(),
+            detail_task_gid: Some(tasks[0].gid.to_owned()),
+            task_detail: Some(TaskDetail {
+                task_gid: tasks[0].gid.to_owned(),
+                comments: vec![Faker.fake(), Faker.fake()],
+                ..TaskDetail::default()
+            }),
             ..State::default()
         };
-        state.previous_top_list_index();
-        assert_eq!(state.current_top_list_index, 0);
+        state.next_view_index().next_view_index();
+        assert_eq!(*state.get_detail_index(), 2);
+        assert_eq!(*state.current_view_index(), 0);
+        state.next_view_index();
+        assert_eq!(*state.get_detail_index(), 0);
+        state.previous_view_index();
+        assert_eq!(*state.get_detail_index(), 2);
+        state.first_view_index();
+        assert_eq!(*state.get_detail_index(), 0);
+        state.set_view_index_at_row(3, 10);
+        assert_eq!(*state.get_detail_index(), 0);
+
+        state.marked_task_gids = vec![tasks[1].gid.to_owned()];
+        assert_eq!(state.selected_task_gids(), vec![tasks[0].gid.to_owned()]);
+        state.remove_task(&tasks[0].gid);
+        assert_eq!(*state.current_view(), View::MyTasks);
     }
 
     #[test]
-    fn current_view() {
+    fn open_calendar_at_due_date() {
+        let tasks = vec![
+            Task {
+                due_on: Some(String::from("2022-01-31")),
+                ..Faker.fake()
+            },
+            Task {
+                due_on: Some(String::from("2022-01-31")),
+                ..Faker.fake()
+            },
+        ];
         let mut state = State {
             view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            current_view_index: 1,
+            current_focus: Focus::View,
             ..State::default()
         };
+        state.open_calendar();
+        assert_eq!(*state.current_view(), View::Calendar);
+        assert_eq!(state.current_context(), Context::Calendar);
+        assert_eq!(state.current_task(), Some(&tasks[1]));
+        state.next_view_index();
+        assert_eq!(state.current_task(), Some(&tasks[0]));
+
+        state.move_calendar_days(1);
+        assert_eq!(
+            state.get_calendar().unwrap().date,
+            NaiveDate::from_ymd(2022, 2, 1)
+        );
+        assert_eq!(state.current_task(), None);
+        state.move_calendar_span(true);
+        assert_eq!(
+            state.get_calendar().unwrap().date,
+            NaiveDate::from_ymd(2022, 3, 1)
+        );
+        state.toggle_calendar_span().move_calendar_span(false);
+        assert_eq!(state.get_calendar().unwrap().span, CalendarSpan::Week);
+        assert_eq!(
+            state.get_calendar().unwrap().date,
+            NaiveDate::from_ymd(2022, 2, 22)
+        );
+
+        state.close_calendar();
         assert_eq!(*state.current_view(), View::MyTasks);
-        state.view_stack = vec![View::RecentlyCompleted];
-        assert_eq!(*state.current_view(), View::RecentlyCompleted);
+        assert!(state.get_calendar().is_none());
     }
 
     #[test]
-    fn get_tasks() {
+    fn move_task_in_calendar() {
+        let task = Task {
+            due_on: Some(String::from("2022-01-31")),
+            ..Faker.fake()
+        };
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: vec![task.to_owned()],
+            current_focus: Focus::View,
+            ..State::default()
+        };
+        state.open_calendar().toggle_calendar_move();
+        state.move_calendar_days(2);
+        let date = NaiveDate::from_ymd(2022, 2, 2);
+        assert_eq!(state.calendar_tasks(date), vec![&task]);
+        assert!(state
+            .calendar_tasks(NaiveDate::from_ymd(2022, 1, 31))
+            .is_empty());
+        state.cancel_calendar_move();
+        assert_eq!(
+            state.get_calendar().unwrap().date,
+            NaiveDate::from_ymd(2022, 1, 31)
+        );
+        assert_eq!(state.tasks[0].due_on, task.due_on);
+
+        state
+            .toggle_calendar_move()
+            .move_calendar_days(2)
+            .toggle_calendar_move();
+        assert_eq!(state.tasks[0].due_on.as_deref(), Some("2022-02-02"));
+        assert_eq!(state.current_task().map(|t| &t.gid), Some(&task.gid));
+        assert!(state.get_calendar().unwrap().moving_task_gid.is_none());
+    }
+
+    #[test]
+    fn timeline_zoom_columns() {
+        let date = NaiveDate::from_ymd(2022, 1, 31);
+        assert_eq!(
+            TimelineZoom::Week.align(date),
+            NaiveDate::from_ymd(2022, 1, 31)
+        );
+        assert_eq!(
+            TimelineZoom::Month.align(date),
+            NaiveDate::from_ymd(2022, 1, 1)
+        );
+        assert_eq!(
+            TimelineZoom::Month.offset(date, 1),
+            NaiveDate::from_ymd(2022, 2, 28)
+        );
+        let start = NaiveDate::from_ymd(2022, 1, 3);
+        assert_eq!(TimelineZoom::Day.column(start, date), 28);
+        assert_eq!(TimelineZoom::Week.column(start, date), 4);
+        assert_eq!(
+            TimelineZoom::Week.column(start, NaiveDate::from_ymd(2022, 1, 2)),
+            -1
+        );
+        assert_eq!(TimelineZoom::Month.column(start, date), 0);
+        assert_eq!(
+            TimelineZoom::Month.column(start, NaiveDate::from_ymd(2021, 12, 31)),
+            -1
+        );
+    }
+
+    #[test]
+    fn open_timeline_by_section() {
+        let project = Faker.fake::<Project>();
+        let sections = vec![Faker.fake::<Section>(), Faker.fake::<Section>()];
+        let task = |section: &Section| Task {
+            memberships: vec![Membership {
+                project_gid: project.gid.to_owned(),
+                section: section.to_owned(),
+            }],
+            ..Faker.fake()
+        };
         let tasks = vec![
-            Faker.fake::<Task>(),
-            Faker.fake::<Task>(),
+            task(&sections[1]),
+            Task {
+                start_on: Some(String::from("2022-01-26")),
+                due_on: Some(String::from("2022-01-31")),
+                ..task(&sections[0])
+            },
             Faker.fake::<Task>(),
         ];
-        let state = State {
+        let mut state = State {
+            view_stack: vec![View::ProjectTasks],
+            project: Some(project),
+            sections: sections.to_owned(),
             tasks: tasks.to_owned(),
+            current_view_index: 1,
+            current_focus: Focus::View,
             ..State::default()
         };
-        assert_eq!(tasks, *state.get_tasks());
+        state.open_timeline();
+        assert_eq!(*state.current_view(), View::Timeline);
+        assert_eq!(state.current_context(), Context::Timeline);
+        assert_eq!(
+            state.timeline_sections(),
+            vec![
+                (sections[0].name.to_owned(), vec![&tasks[1]]),
+                (sections[1].name.to_owned(), vec![&tasks[0]]),
+                (String::from("No section"), vec![&tasks[2]]),
+            ]
+        );
+        assert_eq!(state.current_task(), Some(&tasks[1]));
+        let timeline = state.get_timeline().unwrap();
+        assert_eq!(timeline.zoom, TimelineZoom::Week);
+        assert_eq!(timeline.start, NaiveDate::from_ymd(2022, 1, 17));
+
+        state.next_view_index();
+        assert_eq!(state.current_task(), Some(&tasks[0]));
+        state.scroll_timeline(-2).zoom_timeline();
+        let timeline = state.get_timeline().unwrap();
+        assert_eq!(timeline.zoom, TimelineZoom::Month);
+        assert_eq!(timeline.start, NaiveDate::from_ymd(2022, 1, 1));
+
+        state.close_timeline();
+        assert_eq!(*state.current_view(), View::ProjectTasks);
+        assert!(state.get_timeline().is_none());
     }
 
     #[test]
-    fn set_tasks() {
-        let mut state = State::default();
+    fn close_import() {
+        let mut state = State {
+            view_stack: vec![View::ProjectTasks, View::ImportPreview],
+            import: Some(import_batch()),
+            ..State::default()
+        };
+        state.close_import();
+        assert_eq!(*state.current_view(), View::ProjectTasks);
+        assert!(state.import.is_none());
+    }
+
+    fn import_batch() -> Batch {
+        let row = Row {
+            line: 2,
+            name: Faker.fake(),
+            completed: false,
+            assignee: None,
+            due_on: None,
+            section: None,
+            notes: None,
+            status: ImportStatus::Ready,
+        };
+        Batch {
+            file_path: PathBuf::from("tasks.csv"),
+            rows: vec![row.to_owned(), Row { line: 3, ..row }],
+        }
+    }
+
+    #[test]
+    fn cancel_input() {
+        let mut state = State {
+            input: Some(Input {
+                kind: InputKind::Comment,
+                buffer: Faker.fake(),
+            }),
+            ..State::default()
+        };
+        state.cancel_input();
+        assert!(state.input.is_none());
+    }
+
+    #[test]
+    fn retry_current_journal_entry() {
+        let mut state = State {
+            view_stack: vec![View::PendingChanges],
+            ..State::default()
+        };
+        let mutation = Mutation::Comment {
+            task_gid: Faker.fake(),
+            text: Faker.fake(),
+        };
+        state.journal.push(mutation, None).unwrap();
+        state.journal.fail(1, Faker.fake()).unwrap();
+        state.retry_current_journal_entry();
+        assert_eq!(state.journal.entries()[0].status, Status::Pending);
+    }
+
+    #[test]
+    fn discard_current_journal_entry() {
+        let mut state = State {
+            view_stack: vec![View::PendingChanges],
+            current_view_index: 1,
+            ..State::default()
+        };
+        for _ in 0..2 {
+            let mutation = Mutation::Comment {
+                task_gid: Faker.fake(),
+                text: Faker.fake(),
+            };
+            state.journal.push(mutation, None).unwrap();
+        }
+        state.discard_current_journal_entry();
+        assert_eq!(state.journal.entries().len(), 1);
+        assert_eq!(state.current_view_index, 0);
+    }
+
+    #[test]
+    fn set_tasks_preserves_current_task() {
         let tasks = vec![
             Faker.fake::<Task>(),
             Faker.fake::<Task>(),
             Faker.fake::<Task>(),
         ];
-        state.set_tasks(tasks.to_owned());
-        assert_eq!(tasks, state.tasks);
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            current_view_index: 1,
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        state.set_tasks(vec![tasks[1].to_owned(), tasks[2].to_owned()]);
+        assert_eq!(state.current_view_index, 0);
+        assert!(state.last_updated.is_some());
+        state.set_tasks(vec![tasks[0].to_owned()]);
+        assert_eq!(state.current_view_index, 0);
     }
 
     #[test]
-    fn get_projects() {
-        let projects = vec![
-            Faker.fake::<Project>(),
-            Faker.fake::<Project>(),
-            Faker.fake::<Project>(),
-        ];
+    fn set_refresh_interval() {
+        let mut state = State::default();
+        state.set_refresh_interval(Some(Duration::minutes(5)));
+        assert_eq!(state.refresh_interval, Some(Duration::minutes(5)));
+    }
+
+    #[test]
+    fn get_last_updated() {
+        let last_updated = Utc::now();
         let state = State {
-            projects: projects.to_owned(),
+            last_updated: Some(last_updated),
             ..State::default()
         };
-        assert_eq!(projects, *state.get_projects());
+        assert_eq!(*state.get_last_updated().unwrap(), last_updated);
     }
 
     #[test]
-    fn set_projects() {
+    fn is_refresh_due() {
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            refresh_interval: Some(Duration::minutes(5)),
+            last_updated: Some(Utc::now() - Duration::minutes(10)),
+            ..State::default()
+        };
+        assert!(state.is_refresh_due());
+        state.refresh_requested_at = Some(Utc::now());
+        assert!(!state.is_refresh_due());
+        state.refresh_requested_at = None;
+        state.view_stack = vec![View::PendingChanges];
+        assert!(!state.is_refresh_due());
+        state.view_stack = vec![View::MyTasks];
+        state.refresh_interval = None;
+        assert!(!state.is_refresh_due());
+    }
+
+    #[test]
+    fn refresh_current_view() {
+        let tasks = vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
+        let mut state = State {
+            view_stack: vec![View::MyTasks],
+            tasks: tasks.to_owned(),
+            ..State::default()
+        };
+        state.refresh_current_view();
+        assert!(state.refresh_requested_at.is_some());
+        assert_eq!(state.tasks, tasks);
+        state.refresh_requested_at = None;
+        state.view_stack = vec![View::Welcome];
+        state.refresh_current_view();
+        assert!(state.refresh_requested_at.is_none());
+    }
+
+    #[test]
+    fn push_key() {
+        let key = |c| Key::from(KeyEvent::from(KeyCode::Char(c)));
         let mut state = State::default();
-        let projects = vec![
-            Faker.fake::<Project>(),
-            Faker.fake::<Project>(),
-            Faker.fake::<Project>(),
-        ];
-        state.set_projects(projects.to_owned());
-        assert_eq!(projects, state.projects);
+        assert_eq!(state.push_key(key('j')), Some(Action::NextItem));
+        assert_eq!(state.push_key(key('g')), None);
+        assert_eq!(state.get_pending_keys(), &[key('g')]);
+        assert_eq!(state.push_key(key('g')), Some(Action::FirstItem));
+        assert!(state.get_pending_keys().is_empty());
+        state.push_key(key('g'));
+        assert_eq!(state.push_key(key('k')), Some(Action::PreviousItem));
+        assert_eq!(state.push_key(key('z')), None);
+        assert!(state.get_pending_keys().is_empty());
+    }
+
+    #[test]
+    fn next_theme() {
+        let mut state = State {
+            color_support: ColorSupport::Ansi16,
+            ..State::default()
+        };
+        state.next_theme();
+        assert_eq!(state.get_theme().name, "light");
+        assert_eq!(state.get_theme().active, Color::LightBlue);
+        assert_eq!(state.get_message(), Some("Switched to the light theme"));
+    }
+
+    #[test]
+    fn help() {
+        let mut state = State {
+            current_focus: Focus::View,
+            view_stack: vec![View::PendingChanges],
+            ..State::default()
+        };
+        state.toggle_help();
+        assert_eq!(state.current_context(), Context::PendingChanges);
+        assert!(state
+            .help_lines()
+            .contains(&(String::from("r"), "retry change")));
+        assert!(!state
+            .help_lines()
+            .contains(&(String::from("enter"), "submit")));
+        state.start_input(InputKind::Command);
+        assert!(state
+            .help_lines()
+            .contains(&(String::from("enter"), "submit")));
+        state.cancel_input();
+        state.scroll_help(true).scroll_help(true);
+        assert_eq!(state.get_help().unwrap().scroll, 2);
+        state.push_help_char('R').push_help_char('e');
+        assert_eq!(state.get_help().unwrap().scroll, 0);
+        assert!(state
+            .help_lines()
+            .iter()
+            .all(|(keys, description)| keys.to_lowercase().contains("re")
+                || description.contains("re")));
+        state.pop_help_char();
+        assert_eq!(state.get_help().unwrap().query, "R");
+        state.toggle_help();
+        assert!(state.get_help().is_none());
+    }
+
+    #[test]
+    fn toggle_log() {
+        let mut state = State::default();
+        state.toggle_log();
+        assert_eq!(*state.get_log_display(), LogDisplay::Hidden);
+        state.toggle_log_zoom();
+        assert_eq!(*state.get_log_display(), LogDisplay::Zoomed);
+        assert_eq!(state.current_context(), Context::Log);
+        state.toggle_log_zoom();
+        assert_eq!(*state.get_log_display(), LogDisplay::Normal);
+        state.toggle_log();
+        assert_eq!(*state.get_log_display(), LogDisplay::Hidden);
+    }
+
+    #[test]
+    fn search_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("asana-tui.log");
+        std::fs::write(&file_path, "INFO:Fetching tasks...\nDEBUG:Tick\n").unwrap();
+        let mut state = State::default();
+        state.set_log_file_path(file_path);
+        state.start_input(InputKind::Search);
+        assert!(state.current_input().is_none());
+        state.toggle_log_zoom().start_input(InputKind::Search);
+        for c in "tick".chars() {
+            state.push_input_char(c);
+        }
+        state.submit_input();
+        let search = state.get_log_search().unwrap();
+        assert_eq!(search.query, "tick");
+        assert_eq!(search.matches, vec!["DEBUG:Tick"]);
+        state.cancel_log();
+        assert!(state.get_log_search().is_none());
+        assert_eq!(*state.get_log_display(), LogDisplay::Zoomed);
+        state.cancel_log();
+        assert_eq!(*state.get_log_display(), LogDisplay::Normal);
     }
 }
@@ -1,11 +1,10 @@
-use tui::backend;
-
-type Frame<'a> = tui::terminal::Frame<'a, backend::CrosstermBackend<std::io::Stdout>>;
+type Frame<'a, B> = tui::terminal::Frame<'a, B>;
 
 mod render;
 mod widgets;
 
 pub const SPINNER_FRAME_COUNT: usize = widgets::spinner::FRAMES.len();
 
-pub use render::render;
+pub use render::{areas, render, tab_at, Panels};
 pub use widgets::color;
+pub use widgets::theme;
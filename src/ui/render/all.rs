@@ -1,50 +1,472 @@
 use super::*;
-use crate::state::State;
+use crate::state::{Focus, LogDisplay, Menu, State};
+use serde::{Deserialize, Serialize};
+use tui::backend::Backend;
 use tui::layout::{Constraint, Direction, Layout, Rect};
 
-/// Render all to terminal frame according to state.
+/// Specifying the size below which the left column collapses into a tab bar.
+///
+const COMPACT_WIDTH: u16 = 60;
+const COMPACT_HEIGHT: u16 = 16;
+
+/// Specifying the bounds for resizing panels.
+///
+const SIDEBAR_WIDTH_RANGE: (u16, u16) = (10, 50);
+const LOG_HEIGHT_RANGE: (u16, u16) = (0, 20);
+
+/// Houses the sizes of resizable panels, persisted to the configuration file.
 ///
-pub fn all(frame: &mut Frame, state: &State) {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub sidebar_width: u16,
+    pub log_height: u16,
+}
+
+impl Default for Panels {
+    fn default() -> Panels {
+        Panels {
+            sidebar_width: 25,
+            log_height: 8,
+        }
+    }
+}
+
+impl Panels {
+    /// Return the panels with the sidebar width percentage changed by the
+    /// given amount within bounds.
+    ///
+    pub fn resize_sidebar(&self, delta: i16) -> Panels {
+        Panels {
+            sidebar_width: clamp(self.sidebar_width, delta, SIDEBAR_WIDTH_RANGE),
+            ..*self
+        }
+    }
+
+    /// Return the panels with the log height changed by the given number of
+    /// rows within bounds.
+    ///
+    pub fn resize_log(&self, delta: i16) -> Panels {
+        Panels {
+            log_height: clamp(self.log_height, delta, LOG_HEIGHT_RANGE),
+            ..*self
+        }
+    }
+
+    /// Return the panels with sizes brought within bounds, such as those
+    /// read from the configuration file.
+    ///
+    pub fn clamped(&self) -> Panels {
+        self.resize_sidebar(0).resize_log(0)
+    }
+}
+
+/// Houses the area occupied by each panel, being empty for hidden panels.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Areas {
+    pub tab_bar: Rect,
+    pub status: Rect,
+    pub shortcuts: Rect,
+    pub top_list: Rect,
+    pub main: Rect,
+    pub log: Rect,
+    pub footer: Rect,
+}
+
+/// Return the area occupied by each panel for the given terminal size. Small
+/// terminals show only the focused panel below a tab bar, while a zoomed log
+/// fills everything above the footer.
+///
+pub fn areas(size: Rect, state: &State) -> Areas {
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(1), Constraint::Length(1)].as_ref())
-        .split(frame.size());
+        .split(size);
+
+    if *state.get_log_display() == LogDisplay::Zoomed {
+        return Areas {
+            log: rows[0],
+            footer: rows[1],
+            ..Areas::default()
+        };
+    }
 
+    if size.width < COMPACT_WIDTH || size.height < COMPACT_HEIGHT {
+        let compact = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+            .split(rows[0]);
+        let mut areas = Areas {
+            tab_bar: compact[0],
+            footer: rows[1],
+            ..Areas::default()
+        };
+        let panel = match (state.current_focus(), state.current_menu()) {
+            (Focus::Menu, Menu::Status) => &mut areas.status,
+            (Focus::Menu, Menu::Shortcuts) => &mut areas.shortcuts,
+            (Focus::Menu, Menu::TopList) => &mut areas.top_list,
+            (Focus::View, _) => &mut areas.main,
+        };
+        *panel = compact[1];
+        return areas;
+    }
+
+    let panels = state.get_panels();
+    let log_height = match state.get_log_display() {
+        LogDisplay::Hidden => 0,
+        _ => panels.log_height,
+    };
     let columns = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(25), Constraint::Percentage(75)].as_ref())
+        .constraints(
+            [
+                Constraint::Percentage(panels.sidebar_width),
+                Constraint::Percentage(100 - panels.sidebar_width),
+            ]
+            .as_ref(),
+        )
         .split(rows[0]);
 
-    left(frame, columns[0], state);
-    right(frame, columns[1], state);
-    footer(frame, rows[1]);
-}
-
-/// Render left widgets to terminal frame according to state.
-///
-fn left(frame: &mut Frame, size: Rect, state: &State) {
-    let rows = Layout::default()
+    let left = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(5),
-            Constraint::Length(5),
+            Constraint::Length(6),
             Constraint::Min(1),
         ])
-        .split(size);
+        .split(columns[0]);
 
-    status(frame, rows[0], state);
-    shortcuts(frame, rows[1], state);
-    top_list(frame, rows[2], state);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(log_height)])
+        .split(columns[1]);
+
+    Areas {
+        tab_bar: Rect::default(),
+        status: left[0],
+        shortcuts: left[1],
+        top_list: left[2],
+        main: right[0],
+        log: right[1],
+        footer: rows[1],
+    }
 }
 
-/// Render right widgets to terminal frame according to state.
+/// Render all to terminal frame according to state.
 ///
-fn right(frame: &mut Frame, size: Rect, state: &State) {
-    let rows = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(1), Constraint::Length(8)])
-        .split(size);
+pub fn all<B: Backend>(frame: &mut Frame<B>, state: &State) {
+    let areas = areas(frame.size(), state);
+    if !is_empty(areas.tab_bar) {
+        tab_bar(frame, areas.tab_bar, state);
+    }
+    if !is_empty(areas.status) {
+        status(frame, areas.status, state);
+    }
+    if !is_empty(areas.shortcuts) {
+        shortcuts(frame, areas.shortcuts, state);
+    }
+    if !is_empty(areas.top_list) {
+        top_list(frame, areas.top_list, state);
+    }
+    if !is_empty(areas.main) {
+        main(frame, areas.main, state);
+    }
+    if !is_empty(areas.log) {
+        log(frame, areas.log, state);
+    }
+    if !is_empty(areas.footer) {
+        footer(frame, areas.footer, state);
+    }
+    if !is_empty(areas.main) {
+        picker(frame, areas.main, state);
+    }
+    help(frame, frame.size(), state);
+}
+
+fn is_empty(area: Rect) -> bool {
+    area.width == 0 || area.height == 0
+}
+
+fn clamp(value: u16, delta: i16, (min, max): (u16, u16)) -> u16 {
+    (value as i32 + delta as i32).clamp(min as i32, max as i32) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asana::{
+        InboxItem, Membership, Project, RelatedTask, Section, Task, TaskDetail, Team,
+    };
+    use crate::import::{Batch, Row, Status as ImportStatus};
+    use crate::state::InputKind;
+    use chrono::{Duration, Local, Utc};
+    use fake::{Fake, Faker};
+    use std::path::PathBuf;
+    use tui::{backend::TestBackend, Terminal};
+
+    fn states() -> Vec<State> {
+        let tasks = || vec![Faker.fake::<Task>(), Faker.fake::<Task>()];
+        let mut states = vec![State::default()];
+
+        let mut state = State::default();
+        state
+            .select_current_shortcut_index()
+            .set_tasks(tasks())
+            .focus_view()
+            .last_view_index();
+        states.push(state);
+
+        let mut state = State::default();
+        let sections = vec![Faker.fake::<Section>(), Faker.fake::<Section>()];
+        state.select_current_shortcut_index().set_user_task_list(
+            Faker.fake(),
+            sections.to_owned(),
+            vec![
+                Task {
+                    assignee_section: Some(sections[1].to_owned()),
+                    ..Faker.fake()
+                },
+                Task {
+                    assignee_section: Some(sections[0].to_owned()),
+                    ..Faker.fake()
+                },
+                Task {
+                    assignee_section: None,
+                    ..Faker.fake()
+                },
+            ],
+        );
+        state.start_input(InputKind::Section);
+        states.push(state);
+
+        let mut state = State::default();
+        state
+            .set_shortcut_index(4)
+            .select_current_shortcut_index()
+            .focus_view();
+        states.push(state);
+
+        let mut state = State::default();
+        state
+            .set_projects(vec![Faker.fake::<Project>(), Faker.fake::<Project>()])
+            .select_current_top_list_index()
+            .set_tasks(tasks())
+            .toggle_current_task_marked()
+            .start_input(InputKind::Rename);
+        states.push(state);
+
+        let mut state = State::default();
+        let team: Team = Faker.fake();
+        state
+            .set_teams(vec![team.to_owned(), Faker.fake()])
+            .set_projects(vec![
+                Project {
+                    team: Some(team),
+                    ..Faker.fake()
+                },
+                Project {
+                    team: None,
+                    ..Faker.fake()
+                },
+            ])
+            .focus_menu_item(Menu::TopList)
+            .start_input(InputKind::Team);
+        states.push(state);
+
+        let mut state = State::default();
+        state.set_import(Batch {
+            file_path: PathBuf::from("tasks.csv"),
+            rows: vec![Row {
+                line: 2,
+                name: Faker.fake(),
+                completed: false,
+                assignee: Some(String::from("me")),
+                due_on: None,
+                section: None,
+                notes: None,
+                status: ImportStatus::Invalid(Faker.fake()),
+            }],
+        });
+        states.push(state);
+
+        let mut state = State::default();
+        let inbox_tasks = tasks();
+        state
+            .set_shortcut_index(1)
+            .select_current_shortcut_index()
+            .set_inbox(
+                inbox_tasks.to_owned(),
+                vec![
+                    InboxItem {
+                        task_gid: inbox_tasks[0].gid.to_owned(),
+                        created_at: Utc::now().to_rfc3339(),
+                        ..Faker.fake()
+                    },
+                    Faker.fake(),
+                ],
+            );
+        states.push(state);
+
+        let mut state = State::default();
+        state
+            .select_current_shortcut_index()
+            .set_tasks(vec![
+                Task {
+                    due_on: Some(Local::today().naive_local().to_string()),
+                    ..Faker.fake()
+                };
+                6
+            ])
+            .focus_view()
+            .open_calendar()
+            .toggle_calendar_move();
+        states.push(state);
+
+        let mut state = State::default();
+        let project = Faker.fake::<Project>();
+        let section = Faker.fake::<Section>();
+        let day =
+            |days: i64| Some((Local::today().naive_local() + Duration::days(days)).to_string());
+        let blocker = Task {
+            start_on: day(-3),
+            due_on: day(2),
+            memberships: vec![Membership {
+                project_gid: project.gid.to_owned(),
+                section: section.to_owned(),
+            }],
+            ..Faker.fake()
+        };
+        let blocked = Task {
+            start_on: day(4),
+            due_on: day(40),
+            dependencies: vec![RelatedTask {
+                gid: blocker.gid.to_owned(),
+                name: blocker.name.to_owned(),
+                completed: false,
+            }],
+            ..Faker.fake()
+        };
+        state
+            .set_projects(vec![project])
+            .select_current_top_list_index()
+            .set_sections(vec![section])
+            .set_tasks(vec![blocker, blocked, Faker.fake()])
+            .focus_view()
+            .open_timeline();
+        states.push(state);
+
+        let mut state = State::default();
+        state
+            .select_current_shortcut_index()
+            .set_tasks(tasks())
+            .focus_view()
+            .start_input(InputKind::AddDependency);
+        states.push(state);
+
+        let mut state = State::default();
+        state.toggle_help();
+        states.push(state);
+
+        let mut state = State::default();
+        state.set_panels(Panels {
+            sidebar_width: 250,
+            log_height: 400,
+        });
+        states.push(state);
+
+        let mut state = State::default();
+        state.toggle_log_zoom();
+        states.push(state);
+
+        let mut state = State::default();
+        let tasks = tasks();
+        state
+            .select_current_shortcut_index()
+            .set_tasks(tasks.to_owned())
+            .focus_view()
+            .open_task_detail()
+            .set_task_detail(TaskDetail {
+                task_gid: tasks[0].gid.to_owned(),
+                html_notes: String::from(
+                    "<body><strong>Plan</strong><ul><li>Draft the <a href=\"https://example.com\">brief</a></li></ul></body>",
+                ),
+                comments: vec![Faker.fake(), Faker.fake()],
+                ..TaskDetail::default()
+            })
+            .last_view_index();
+        states.push(state);
+        states
+    }
+
+    #[test]
+    fn render_any_size() {
+        let sizes = (0..130)
+            .step_by(7)
+            .flat_map(|width| (0..45).step_by(3).map(move |height| (width, height)))
+            .chain([(u16::MAX, 1), (1, u16::MAX), (u16::MAX, u16::MAX)]);
+        for state in states() {
+            for (width, height) in sizes.clone() {
+                let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+                terminal.draw(|frame| all(frame, &state)).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn areas_compact() {
+        let mut state = State::default();
+        let compact = areas(Rect::new(0, 0, 40, 20), &state);
+        assert_eq!(compact.tab_bar, Rect::new(0, 0, 40, 1));
+        assert_eq!(compact.shortcuts, Rect::new(0, 1, 40, 18));
+        assert!(is_empty(compact.main));
+        assert!(is_empty(compact.log));
+        state.focus_view();
+        let compact = areas(Rect::new(0, 0, 40, 20), &state);
+        assert_eq!(compact.main, Rect::new(0, 1, 40, 18));
+    }
+
+    #[test]
+    fn areas_resized() {
+        let mut state = State::default();
+        state.set_panels(Panels::default().resize_sidebar(15).resize_log(-8));
+        let resized = areas(Rect::new(0, 0, 100, 40), &state);
+        assert!(is_empty(resized.tab_bar));
+        assert_eq!(resized.status.width, 40);
+        assert!(is_empty(resized.log));
+        state.set_panels(Panels {
+            sidebar_width: 250,
+            log_height: 400,
+        });
+        assert_eq!(
+            *state.get_panels(),
+            Panels {
+                sidebar_width: SIDEBAR_WIDTH_RANGE.1,
+                log_height: LOG_HEIGHT_RANGE.1,
+            }
+        );
+    }
+
+    #[test]
+    fn areas_log() {
+        let mut state = State::default();
+        state.toggle_log();
+        let hidden = areas(Rect::new(0, 0, 100, 40), &state);
+        assert!(is_empty(hidden.log));
+        assert_eq!(hidden.main.height, 39);
+        state.toggle_log_zoom();
+        let zoomed = areas(Rect::new(0, 0, 100, 40), &state);
+        assert_eq!(zoomed.log, Rect::new(0, 0, 100, 39));
+        assert!(is_empty(zoomed.main));
+        assert!(is_empty(zoomed.status));
+    }
 
-    main(frame, rows[0], state);
-    log(frame, rows[1]);
+    #[test]
+    fn resize_panels_within_bounds() {
+        let panels = Panels::default();
+        assert_eq!(panels.resize_sidebar(-100).sidebar_width, 10);
+        assert_eq!(panels.resize_sidebar(100).sidebar_width, 50);
+        assert_eq!(panels.resize_log(-100).log_height, 0);
+        assert_eq!(panels.resize_log(2).log_height, 10);
+    }
 }
@@ -1,24 +1,73 @@
 use super::Frame;
-use crate::ui::color::*;
+use crate::keymap::Action;
+use crate::state::{InputKind, LogDisplay, State, View};
 use tui::{
+    backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::Style,
     text::{Span, Spans},
     widgets::Paragraph,
 };
 
-/// Render footer widget.
+/// Render footer widget according to state.
 ///
-pub fn footer(frame: &mut Frame, size: Rect) {
-    let controls_content = Spans::from(vec![Span::styled(
-        "j k h l: navigate, enter: select, esc: cancel, q: quit",
-        Style::default().fg(YELLOW),
-    )]);
+pub fn footer<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let controls_content = match state.current_input() {
+        Some(input) => {
+            let prompt = match input.kind {
+                InputKind::Rename => String::from("Rename: "),
+                InputKind::Comment => String::from("Comment: "),
+                InputKind::DueDate => String::from("Due (e.g. fri, +3d, 2022-01-31): "),
+                InputKind::Export => String::from("Export (md, csv, ics): "),
+                InputKind::Import => String::from("Import file (.csv, .md): "),
+                InputKind::Assign => String::from("Assign to (email or me, empty to unassign): "),
+                InputKind::Section => {
+                    let names: Vec<&str> = state
+                        .get_sections()
+                        .iter()
+                        .map(|s| s.name.as_str())
+                        .collect();
+                    format!("Move to section ({}): ", names.join(", "))
+                }
+                InputKind::Tag => String::from("Add tag: "),
+                InputKind::AddDependency => {
+                    String::from("Blocked by (type to filter, up/down to pick): ")
+                }
+                InputKind::RemoveDependency => {
+                    String::from("No longer blocked by (type to filter, up/down to pick): ")
+                }
+                InputKind::Team => String::from("Team (type to filter, up/down to pick): "),
+                InputKind::Command => String::from(":"),
+                InputKind::Search => String::from("Search log: "),
+                InputKind::Delete => format!(
+                    "Delete {} task(s)? (y/n): ",
+                    state.selected_task_gids().len()
+                ),
+            };
+            Spans::from(vec![
+                Span::styled(prompt, state.get_theme().hint_text_style()),
+                Span::raw(format!("{}_", input.buffer)),
+            ])
+        }
+        None => match (state.get_bulk_progress(), state.get_message()) {
+            (Some((done, total)), _) => Spans::from(vec![Span::styled(
+                format!("Saving changes to tasks... {}/{}", done, total),
+                state.get_theme().hint_text_style(),
+            )]),
+            (None, Some(message)) => Spans::from(vec![Span::styled(
+                message.to_owned(),
+                state.get_theme().message_text_style(),
+            )]),
+            (None, None) => Spans::from(vec![Span::styled(
+                hint(state),
+                state.get_theme().hint_text_style(),
+            )]),
+        },
+    };
     let controls_widget = Paragraph::new(controls_content).alignment(Alignment::Left);
 
     let version_content = Spans::from(vec![Span::styled(
         format!(" {}", env!("CARGO_PKG_VERSION")),
-        Style::default().fg(GREEN),
+        state.get_theme().message_text_style(),
     )]);
     let version_content_width = version_content.width();
     let version_widget = Paragraph::new(version_content).alignment(Alignment::Right);
@@ -34,3 +83,127 @@ pub fn footer(frame: &mut Frame, size: Rect) {
     frame.render_widget(controls_widget, columns[0]);
     frame.render_widget(version_widget, columns[1]);
 }
+
+/// Return the hint text for the current view generated from the keymap,
+/// showing the keys pressed so far while a sequence is incomplete.
+///
+fn hint(state: &State) -> String {
+    let keymap = state.get_keymap();
+    if !state.get_pending_keys().is_empty() {
+        let keys: Vec<String> = state
+            .get_pending_keys()
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        return format!("{}-", keys.join(""));
+    }
+    if *state.get_log_display() == LogDisplay::Zoomed {
+        return keymap.hint(&[
+            (&[Action::NextItem, Action::PreviousItem], "targets"),
+            (&[Action::PreviousMenu, Action::NextMenu], "show less more"),
+            (&[Action::ShrinkLog, Action::GrowLog], "record less more"),
+            (&[Action::PageUp, Action::PageDown], "page"),
+            (&[Action::Search], "search"),
+            (&[Action::ZoomLog], "unzoom"),
+            (&[Action::Help], "help"),
+            (&[Action::Quit], "quit"),
+        ]);
+    }
+    match state.current_view() {
+        View::ImportPreview => keymap.hint(&[
+            (&[Action::NextItem, Action::PreviousItem], "navigate"),
+            (&[Action::Select], "create tasks"),
+            (&[Action::Cancel], "close"),
+            (&[Action::Quit], "quit"),
+        ]),
+        View::Inbox => keymap.hint(&[
+            (&[Action::NextItem, Action::PreviousItem], "navigate"),
+            (&[Action::Select], "open task"),
+            (&[Action::Mark], "toggle read"),
+            (&[Action::OpenLink, Action::CopyLink], "open copy link"),
+            (&[Action::Refresh], "refresh"),
+            (&[Action::Cancel], "menu"),
+            (&[Action::Help], "help"),
+            (&[Action::Quit], "quit"),
+        ]),
+        View::Calendar => keymap.hint(&[
+            (&[Action::PreviousMenu, Action::NextMenu], "days"),
+            (&[Action::NextItem, Action::PreviousItem], "tasks"),
+            (&[Action::PageUp, Action::PageDown], "page"),
+            (&[Action::FirstItem], "today"),
+            (&[Action::Mark], "move"),
+            (&[Action::Calendar], "week month"),
+            (&[Action::Select], "open task"),
+            (&[Action::Cancel], "back"),
+            (&[Action::Help], "help"),
+            (&[Action::Quit], "quit"),
+        ]),
+        View::Timeline => keymap.hint(&[
+            (&[Action::NextItem, Action::PreviousItem], "tasks"),
+            (&[Action::PreviousMenu, Action::NextMenu], "scroll"),
+            (&[Action::PageUp, Action::PageDown], "page"),
+            (&[Action::Timeline], "zoom"),
+            (&[Action::Select], "open task"),
+            (&[Action::Cancel], "back"),
+            (&[Action::Help], "help"),
+            (&[Action::Quit], "quit"),
+        ]),
+        View::TaskDetail => keymap.hint(&[
+            (&[Action::NextItem, Action::PreviousItem], "comments"),
+            (
+                &[Action::Complete, Action::Rename, Action::Comment],
+                "complete rename comment",
+            ),
+            (
+                &[Action::AddDependency, Action::RemoveDependency],
+                "block unblock",
+            ),
+            (&[Action::OpenLink, Action::CopyLink], "open copy link"),
+            (&[Action::Refresh], "refresh"),
+            (&[Action::Cancel], "back"),
+            (&[Action::Help], "help"),
+            (&[Action::Quit], "quit"),
+        ]),
+        _ => keymap.hint(&[
+            (
+                &[
+                    Action::NextItem,
+                    Action::PreviousItem,
+                    Action::PreviousMenu,
+                    Action::NextMenu,
+                ],
+                "navigate",
+            ),
+            (&[Action::NextPanel, Action::PreviousPanel], "panels"),
+            (&[Action::Select], "select"),
+            (&[Action::Mark, Action::VisualMode], "mark"),
+            (
+                &[
+                    Action::Complete,
+                    Action::Rename,
+                    Action::Comment,
+                    Action::DueDate,
+                    Action::Assign,
+                    Action::Section,
+                    Action::Tag,
+                    Action::Delete,
+                ],
+                "complete rename comment due assign section tag delete",
+            ),
+            (
+                &[Action::AddDependency, Action::RemoveDependency],
+                "block unblock",
+            ),
+            (&[Action::Export, Action::Import], "export import"),
+            (&[Action::Calendar, Action::Timeline], "calendar timeline"),
+            (&[Action::Team], "teams"),
+            (&[Action::OpenLink, Action::CopyLink], "open copy link"),
+            (&[Action::Undo, Action::Redo], "undo redo"),
+            (&[Action::Refresh], "refresh"),
+            (&[Action::ToggleLog, Action::ZoomLog], "log"),
+            (&[Action::Cancel], "cancel"),
+            (&[Action::Help], "help"),
+            (&[Action::Quit], "quit"),
+        ]),
+    }
+}
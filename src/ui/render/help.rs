@@ -0,0 +1,78 @@
+use super::Frame;
+use crate::keymap::Action;
+use crate::state::State;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+const BLOCK_TITLE: &str = "Help";
+
+/// Render help overlay listing the key bindings for the current context
+/// centered over the given area.
+///
+pub fn help<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let help = match state.get_help() {
+        Some(help) => help,
+        None => return,
+    };
+    let theme = state.get_theme();
+    let area = centered(size, 60, 80);
+
+    let title = match (help.searching, help.query.is_empty()) {
+        (true, _) => format!("{} (search: {}_)", BLOCK_TITLE, help.query),
+        (false, false) => format!("{} (search: {})", BLOCK_TITLE, help.query),
+        (false, true) => format!(
+            "{} ({})",
+            BLOCK_TITLE,
+            state
+                .get_keymap()
+                .hint(&[(&[Action::Search], "search"), (&[Action::Cancel], "close"),])
+        ),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.active_block_border_style())
+        .title(Span::styled(title, theme.active_block_title_style()));
+
+    let lines = state.help_lines();
+    let keys_width = lines.iter().map(|(keys, _)| keys.len()).max().unwrap_or(0);
+    let text: Vec<Spans> = match lines.is_empty() {
+        true => vec![Spans::from(Span::styled(
+            "No matching key bindings.",
+            theme.normal_text_style(),
+        ))],
+        false => lines
+            .into_iter()
+            .map(|(keys, description)| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{:width$}  ", keys, width = keys_width),
+                        theme.secondary_text_style(),
+                    ),
+                    Span::styled(description, theme.normal_text_style()),
+                ])
+            })
+            .collect(),
+    };
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .scroll((help.scroll.try_into().unwrap_or(u16::MAX), 0));
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// Return an area centered within the given one, sized by percentages.
+///
+pub(super) fn centered(size: Rect, width_percent: u16, height_percent: u16) -> Rect {
+    let width = (size.width as u32 * width_percent as u32 / 100) as u16;
+    let height = (size.height as u32 * height_percent as u32 / 100) as u16;
+    Rect {
+        x: size.x + (size.width - width) / 2,
+        y: size.y + (size.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -1,28 +1,81 @@
 use super::Frame;
-use crate::ui::color::*;
+use crate::state::{LogDisplay, State};
 use tui::{
+    backend::Backend,
     layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Borders},
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
 };
-use tui_logger::TuiLoggerWidget;
+use tui_logger::{TuiLoggerSmartWidget, TuiLoggerWidget};
 
-/// Render log widget according to state.
+/// Specifying the format of timestamps shown in the log.
 ///
-pub fn log(frame: &mut Frame, size: Rect) {
-    let log_widget: TuiLoggerWidget = TuiLoggerWidget::default()
+const TIMESTAMP_FORMAT: &str = "%F %H:%M:%S%.3f";
+
+/// Render log widget according to state, showing the target selector when
+/// zoomed and the matching lines of the log file while searching.
+///
+pub fn log<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let theme = state.get_theme();
+    if let Some(search) = state.get_log_search() {
+        let height = size.height.saturating_sub(2) as usize;
+        let lines: Vec<Spans> = search
+            .matches
+            .iter()
+            .skip(search.matches.len().saturating_sub(height))
+            .map(|line| Spans::from(Span::raw(line.to_owned())))
+            .collect();
+        let block = Block::default()
+            .title(format!(
+                "Log matching '{}' ({})",
+                search.query,
+                search.matches.len()
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_block_border_style());
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(theme.text).bg(theme.background));
+        frame.render_widget(paragraph, size);
+        return;
+    }
+    if *state.get_log_display() == LogDisplay::Zoomed {
+        let log_widget = TuiLoggerSmartWidget::default()
+            .title_log("Log")
+            .title_target("Targets")
+            .border_style(theme.active_block_border_style())
+            .highlight_style(theme.active_list_item_style())
+            .style_error(Style::default().fg(theme.error))
+            .style_warn(Style::default().fg(theme.warning))
+            .style_info(Style::default().fg(theme.secondary))
+            .style_debug(Style::default().fg(theme.success))
+            .style_trace(Style::default().fg(theme.active))
+            .output_separator(' ')
+            .output_timestamp(Some(TIMESTAMP_FORMAT.to_string()))
+            .output_level(None)
+            .output_target(true)
+            .output_file(false)
+            .output_line(false)
+            .style(Style::default().fg(theme.text).bg(theme.background))
+            .state(state.get_log_widget());
+        frame.render_widget(log_widget, size);
+        return;
+    }
+    let mut log_widget: TuiLoggerWidget = TuiLoggerWidget::default()
         .block(Block::default().title("Log").borders(Borders::ALL))
-        .style_error(Style::default().fg(PINK))
-        .style_warn(Style::default().fg(YELLOW))
-        .style_info(Style::default().fg(BLUE))
-        .style_debug(Style::default().fg(GREEN))
-        .style_trace(Style::default().fg(PURPLE))
+        .style_error(Style::default().fg(theme.error))
+        .style_warn(Style::default().fg(theme.warning))
+        .style_info(Style::default().fg(theme.secondary))
+        .style_debug(Style::default().fg(theme.success))
+        .style_trace(Style::default().fg(theme.active))
         .output_separator(' ')
-        .output_timestamp(Some("%F %H:%M:%S%.3f".to_string()))
+        .output_timestamp(Some(TIMESTAMP_FORMAT.to_string()))
         .output_level(None)
         .output_target(false)
         .output_file(false)
         .output_line(false)
-        .style(Style::default().fg(Color::White).bg(Color::Black));
+        .style(Style::default().fg(theme.text).bg(theme.background));
+    log_widget.state(state.get_log_widget());
     frame.render_widget(log_widget, size);
 }
@@ -1,17 +1,24 @@
+use super::timeline::timeline;
 use super::welcome::{BANNER, CONTENT};
-use super::widgets::spinner;
+use super::widgets::{rich_text::RichText, spinner};
 use super::Frame;
-use crate::state::{Focus, State, View};
-use crate::ui::widgets::styling;
+use crate::asana::{InboxKind, Task};
+use crate::date;
+use crate::import::Status as ImportStatus;
+use crate::journal::Status;
+use crate::state::{CalendarSpan, Focus, State, View};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
 use tui::{
+    backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
 };
 
 /// Render main widget according to state.
 ///
-pub fn main(frame: &mut Frame, size: Rect, state: &State) {
+pub fn main<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     match state.current_view() {
         View::Welcome => {
             welcome(frame, size, state);
@@ -19,6 +26,9 @@ pub fn main(frame: &mut Frame, size: Rect, state: &State) {
         View::MyTasks => {
             my_tasks(frame, size, state);
         }
+        View::Inbox => {
+            inbox(frame, size, state);
+        }
         View::RecentlyModified => {
             recently_modified(frame, size, state);
         }
@@ -28,10 +38,25 @@ pub fn main(frame: &mut Frame, size: Rect, state: &State) {
         View::ProjectTasks => {
             project_tasks(frame, size, state);
         }
+        View::PendingChanges => {
+            pending_changes(frame, size, state);
+        }
+        View::ImportPreview => {
+            import_preview(frame, size, state);
+        }
+        View::TaskDetail => {
+            task_detail(frame, size, state);
+        }
+        View::Calendar => {
+            calendar(frame, size, state);
+        }
+        View::Timeline => {
+            timeline(frame, size, state);
+        }
     }
 }
 
-fn welcome(frame: &mut Frame, size: Rect, state: &State) {
+fn welcome<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(6), Constraint::Length(94)].as_ref())
@@ -42,63 +67,585 @@ fn welcome(frame: &mut Frame, size: Rect, state: &State) {
     frame.render_widget(block, size);
 
     let mut banner = Text::from(BANNER);
-    banner.patch_style(styling::banner_style());
+    banner.patch_style(state.get_theme().banner_style());
     let banner_widget = Paragraph::new(banner);
     frame.render_widget(banner_widget, rows[0]);
 
     let mut content = Text::from(CONTENT);
-    content.patch_style(styling::normal_text_style());
+    content.patch_style(state.get_theme().normal_text_style());
     let content_widget = Paragraph::new(content);
     frame.render_widget(content_widget, rows[1]);
 }
 
-fn my_tasks(frame: &mut Frame, size: Rect, state: &State) {
+fn my_tasks<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     let block = view_block("My Tasks", state);
-    let list = task_list(state, size).block(block);
-    frame.render_widget(list, size);
+    let headings = state.section_headings();
+    if state.get_tasks().is_empty() || headings.iter().all(Option::is_none) {
+        task_list(frame, size, state, block);
+        return;
+    }
+    let mut items: Vec<ListItem> = vec![];
+    let mut selected = 0;
+    for (index, (task, heading)) in state.get_tasks().iter().zip(headings).enumerate() {
+        if let Some(heading) = heading {
+            items.push(ListItem::new(Span::styled(
+                heading,
+                state.get_theme().active_block_title_style(),
+            )));
+        }
+        if index == *state.current_view_index() {
+            selected = items.len();
+        }
+        let mut spans = task_spans(task, state);
+        spans.0.insert(0, Span::raw("  "));
+        items.push(ListItem::new(spans));
+    }
+    render_list_at(frame, size, state, List::new(items).block(block), selected);
 }
 
-fn recently_modified(frame: &mut Frame, size: Rect, state: &State) {
+fn inbox<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let block = view_block("Inbox", state);
+    let items = state.get_inbox_items();
+    if items.is_empty() {
+        match state.get_last_updated() {
+            Some(_) => {
+                let paragraph = Paragraph::new("No recent activity.")
+                    .style(state.get_theme().normal_text_style())
+                    .block(block);
+                frame.render_widget(paragraph, size);
+            }
+            None => frame.render_widget(spinner::widget(state, size.height).block(block), size),
+        }
+        return;
+    }
+    let theme = state.get_theme();
+    let items: Vec<ListItem> = items
+        .iter()
+        .map(|item| {
+            let read = state.is_inbox_item_read(&item.story_gid);
+            let task_name = match state.get_task(&item.task_gid) {
+                Some(task) => task.name.to_owned(),
+                None => item.task_gid.to_owned(),
+            };
+            let kind = match item.kind {
+                InboxKind::Mention => "mentioned you",
+                InboxKind::Assignment => "assigned",
+                InboxKind::Comment => "commented",
+                InboxKind::Completion => "completed",
+            };
+            let author = match &item.author {
+                Some(author) => author.name.as_str(),
+                None => "Someone",
+            };
+            let mut spans = vec![
+                match read {
+                    true => Span::raw("  "),
+                    false => Span::styled("● ", theme.marked_text_style()),
+                },
+                Span::styled(
+                    format!("{} {} ", author, kind),
+                    theme.secondary_text_style(),
+                ),
+                Span::raw(task_name),
+            ];
+            if item.kind == InboxKind::Mention || item.kind == InboxKind::Comment {
+                spans.push(Span::styled(
+                    format!(": {}", item.text.replace('\n', " ")),
+                    theme.secondary_text_style(),
+                ));
+            }
+            if let Ok(created_at) = DateTime::parse_from_rfc3339(&item.created_at) {
+                spans.push(Span::styled(
+                    format!(" ({})", time_ago(&created_at.with_timezone(&Utc))),
+                    theme.secondary_text_style(),
+                ));
+            }
+            ListItem::new(Spans::from(spans))
+        })
+        .collect();
+    render_list(frame, size, state, List::new(items).block(block));
+}
+
+fn recently_modified<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     let block = view_block("Recently Modified", state);
-    let list = task_list(state, size).block(block);
-    frame.render_widget(list, size);
+    task_list(frame, size, state, block);
 }
 
-fn recently_completed(frame: &mut Frame, size: Rect, state: &State) {
+fn recently_completed<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     let block = view_block("Recently Completed", state);
-    let list = task_list(state, size).block(block);
-    frame.render_widget(list, size);
+    task_list(frame, size, state, block);
 }
 
-fn project_tasks(frame: &mut Frame, size: Rect, state: &State) {
+fn project_tasks<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     let title = match state.get_project() {
         Some(project) => &project.name,
         None => "Project",
     };
     let block = view_block(title, state);
-    let list = task_list(state, size).block(block);
-    frame.render_widget(list, size);
+    task_list(frame, size, state, block);
+}
+
+fn pending_changes<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let block = view_block("Pending Changes", state);
+    let entries = state.get_journal().entries();
+    if entries.is_empty() {
+        let paragraph = Paragraph::new("No changes awaiting replay.")
+            .style(state.get_theme().normal_text_style())
+            .block(block);
+        frame.render_widget(paragraph, size);
+        return;
+    }
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|e| {
+            let task_name = match state.get_task(e.mutation.task_gid()) {
+                Some(task) => task.name.to_owned(),
+                None => e.mutation.task_gid().to_owned(),
+            };
+            let (status, style, reason) = match &e.status {
+                Status::Pending => (
+                    "pending",
+                    state.get_theme().pending_text_style(),
+                    String::new(),
+                ),
+                Status::Failed(reason) => (
+                    "failed",
+                    state.get_theme().failed_text_style(),
+                    format!(" - {}", reason),
+                ),
+            };
+            ListItem::new(Spans::from(vec![
+                Span::styled(format!("[{}] ", status), style),
+                Span::raw(format!("{}: {}", task_name, e.mutation.describe())),
+                Span::styled(reason, style),
+            ]))
+        })
+        .collect();
+    render_list(frame, size, state, List::new(items).block(block));
+}
+
+fn import_preview<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let batch = match state.get_import() {
+        Some(batch) => batch,
+        None => return,
+    };
+    let title = format!(
+        "Import {} ({} ready, {} invalid, {} created, {} failed)",
+        batch.file_path.display(),
+        batch.count(|s| *s == ImportStatus::Ready),
+        batch.count(|s| matches!(s, ImportStatus::Invalid(_))),
+        batch.count(|s| matches!(s, ImportStatus::Created(_))),
+        batch.count(|s| matches!(s, ImportStatus::Failed(_))),
+    );
+    let block = view_block(&title, state);
+    if batch.rows.is_empty() {
+        let paragraph = Paragraph::new("No tasks found to import.")
+            .style(state.get_theme().normal_text_style())
+            .block(block);
+        frame.render_widget(paragraph, size);
+        return;
+    }
+    let items: Vec<ListItem> = batch
+        .rows
+        .iter()
+        .map(|row| {
+            let (status, style, reason) = match &row.status {
+                ImportStatus::Ready => ("ready", state.get_theme().secondary_text_style(), None),
+                ImportStatus::Invalid(reason) => (
+                    "invalid",
+                    state.get_theme().failed_text_style(),
+                    Some(reason),
+                ),
+                ImportStatus::Created(_) => {
+                    ("created", state.get_theme().created_text_style(), None)
+                }
+                ImportStatus::Failed(reason) => (
+                    "failed",
+                    state.get_theme().failed_text_style(),
+                    Some(reason),
+                ),
+            };
+            let mut spans = vec![
+                Span::styled(format!("[{}] ", status), style),
+                Span::styled(
+                    format!("{}: ", row.line),
+                    state.get_theme().secondary_text_style(),
+                ),
+                Span::raw(match row.completed {
+                    true => "[x] ",
+                    false => "[ ] ",
+                }),
+                Span::raw(row.name.to_owned()),
+            ];
+            let details: Vec<String> = [
+                row.section.as_ref().map(|s| format!("in {}", s)),
+                row.assignee.as_ref().map(|a| format!("for {}", a)),
+                row.due_on.as_ref().map(|d| format!("due {}", d)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if !details.is_empty() {
+                spans.push(Span::styled(
+                    format!(" {}", details.join(" ")),
+                    state.get_theme().secondary_text_style(),
+                ));
+            }
+            if let Some(reason) = reason {
+                spans.push(Span::styled(format!(" - {}", reason), style));
+            }
+            ListItem::new(Spans::from(spans))
+        })
+        .collect();
+    render_list(frame, size, state, List::new(items).block(block));
+}
+
+fn task_detail<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let task = state.current_task();
+    let title = match task {
+        Some(task) => task.name.as_str(),
+        None => "Task",
+    };
+    let block = view_block(title, state);
+    let detail = match state.get_task_detail() {
+        Some(detail) => detail,
+        None => {
+            frame.render_widget(spinner::widget(state, size.height).block(block), size);
+            return;
+        }
+    };
+    let theme = state.get_theme();
+    let users = state.mention_names();
+    let width = size.width.saturating_sub(2) as usize;
+
+    let mut lines: Vec<Spans> = vec![];
+    if let Some(task) = task {
+        let mut meta = vec![match task.completed {
+            true => String::from("completed"),
+            false => String::from("incomplete"),
+        }];
+        if let Some(assignee) = &task.assignee {
+            meta.push(format!("for {}", assignee.name));
+        }
+        if let Some(due_on) = &task.due_on {
+            meta.push(format!("due {}", due_on));
+        }
+        lines.push(Spans::from(Span::styled(
+            meta.join(" · "),
+            theme.secondary_text_style(),
+        )));
+        lines.push(Spans::default());
+        for (heading, related) in [
+            ("Blocked by", &task.dependencies),
+            ("Blocking", &detail.dependents),
+        ] {
+            if related.is_empty() {
+                continue;
+            }
+            lines.push(Spans::from(Span::styled(
+                heading,
+                theme.active_block_title_style(),
+            )));
+            for related in related.iter() {
+                let (check, style) = match related.completed {
+                    true => ("[x] ", theme.secondary_text_style()),
+                    false => ("[ ] ", theme.normal_text_style()),
+                };
+                lines.push(Spans::from(Span::styled(
+                    format!("  {}{}", check, related.name),
+                    style,
+                )));
+            }
+            lines.push(Spans::default());
+        }
+    }
+
+    // Remember where each block starts so the selected one can be scrolled
+    // into view.
+    let mut offsets = vec![lines.len()];
+    let notes = RichText::parse(&detail.html_notes, theme, &users);
+    match notes.is_empty() {
+        true => lines.push(Spans::from(Span::styled(
+            "No description.",
+            theme.secondary_text_style(),
+        ))),
+        false => lines.extend(notes.wrap(width)),
+    }
+    lines.push(Spans::default());
+    lines.push(Spans::from(Span::styled(
+        format!("Comments ({})", detail.comments.len()),
+        theme.active_block_title_style(),
+    )));
+
+    for (index, comment) in detail.comments.iter().enumerate() {
+        lines.push(Spans::default());
+        offsets.push(lines.len());
+        let author = match &comment.author {
+            Some(author) => author.name.as_str(),
+            None => "Unknown",
+        };
+        let created_at = match DateTime::parse_from_rfc3339(&comment.created_at) {
+            Ok(time) => time.with_timezone(&Local).format("%F %H:%M").to_string(),
+            Err(_) => comment.created_at.to_owned(),
+        };
+        let header_style = match index + 1 == *state.get_detail_index() {
+            true => theme.active_list_item_style(),
+            false => theme.secondary_text_style(),
+        };
+        lines.push(Spans::from(Span::styled(
+            format!("{} · {}", author, created_at),
+            header_style,
+        )));
+        let text = RichText::parse(&comment.html_text, theme, &users);
+        for spans in text.wrap(width.saturating_sub(2)) {
+            let mut indented = vec![Span::raw("  ")];
+            indented.extend(spans.0);
+            lines.push(Spans::from(indented));
+        }
+    }
+
+    let height = size.height.saturating_sub(2) as usize;
+    let scroll = offsets
+        .get(*state.get_detail_index())
+        .copied()
+        .unwrap_or(0)
+        .min(lines.len().saturating_sub(height));
+    let paragraph = Paragraph::new(lines)
+        .style(theme.normal_text_style())
+        .block(block)
+        .scroll((scroll as u16, 0));
+    frame.render_widget(paragraph, size);
+}
+
+fn calendar<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let calendar = match state.get_calendar() {
+        Some(calendar) => calendar,
+        None => return,
+    };
+    let (title, first_day, week_count) = match calendar.span {
+        CalendarSpan::Week => (
+            format!("Week of {}", date::week_start(calendar.date)),
+            date::week_start(calendar.date),
+            1,
+        ),
+        CalendarSpan::Month => {
+            let month_start = calendar.date.with_day(1).unwrap();
+            let month_end = date::add_months(month_start, 1) - Duration::days(1);
+            let first_day = date::week_start(month_start);
+            (
+                calendar.date.format("%B %Y").to_string(),
+                first_day,
+                (month_end - first_day).num_days() / 7 + 1,
+            )
+        }
+    };
+    let block = view_block(&title, state);
+    let inner = block.inner(size);
+    frame.render_widget(block, size);
+    if state.get_tasks().is_empty() && state.get_last_updated().is_none() {
+        frame.render_widget(spinner::widget(state, inner.height), inner);
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            std::iter::once(Constraint::Length(1))
+                .chain((0..week_count).map(|_| Constraint::Ratio(1, week_count as u32)))
+                .collect::<Vec<Constraint>>(),
+        )
+        .split(inner);
+    let columns = |row: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 7); 7])
+            .split(row)
+    };
+    for (day, area) in columns(rows[0]).into_iter().enumerate() {
+        let name = (first_day + Duration::days(day as i64))
+            .format("%a")
+            .to_string();
+        let paragraph = Paragraph::new(name).style(state.get_theme().secondary_text_style());
+        frame.render_widget(paragraph, area);
+    }
+    for (week, row) in rows.into_iter().skip(1).enumerate() {
+        for (day, area) in columns(row).into_iter().enumerate() {
+            let date = first_day + Duration::days((week * 7 + day) as i64);
+            calendar_day(frame, area, state, date);
+        }
+    }
+}
+
+fn calendar_day<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State, date: NaiveDate) {
+    let calendar = match state.get_calendar() {
+        Some(calendar) => calendar,
+        None => return,
+    };
+    let theme = state.get_theme();
+    let today = Local::today().naive_local();
+    let selected = date == calendar.date;
+    let tasks = state.calendar_tasks(date);
+    let capacity = size.height.saturating_sub(2) as usize;
+    let overflow = tasks.len() > capacity;
+
+    let mut title = date.day().to_string();
+    if overflow {
+        title = format!("{} ({})", title, tasks.len());
+    }
+    let title_style = match (date == today, date.month() == calendar.date.month()) {
+        (true, _) => theme.active_block_title_style(),
+        (false, true) => theme.normal_text_style(),
+        (false, false) => theme.secondary_text_style(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(match selected {
+            true => theme.active_block_border_style(),
+            false => theme.normal_block_border_style(),
+        })
+        .title(Span::styled(title, title_style));
+
+    // Keep the selected task visible, leaving a line for the overflow badge.
+    let shown = match overflow {
+        true => capacity.saturating_sub(1),
+        false => capacity,
+    };
+    let offset = match selected {
+        true => (calendar.index + 1).saturating_sub(shown),
+        false => 0,
+    };
+    let mut lines: Vec<Spans> = tasks
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(shown)
+        .map(|(index, task)| {
+            let style = if calendar.moving_task_gid.as_ref() == Some(&task.gid) {
+                theme.marked_text_style()
+            } else if selected && index == calendar.index {
+                theme.active_list_item_style()
+            } else if task.completed {
+                theme.secondary_text_style()
+            } else if date < today {
+                theme.failed_text_style()
+            } else {
+                theme.normal_text_style()
+            };
+            Spans::from(Span::styled(task.name.to_owned(), style))
+        })
+        .collect();
+    if overflow {
+        lines.push(Spans::from(Span::styled(
+            format!("+{} more", tasks.len() - shown),
+            theme.hint_text_style(),
+        )));
+    }
+    frame.render_widget(Paragraph::new(lines).block(block), size);
 }
 
-fn task_list(state: &State, size: Rect) -> Paragraph {
+fn task_list<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State, block: Block) {
     if state.get_tasks().is_empty() {
-        return spinner::widget(state, size.height);
+        frame.render_widget(spinner::widget(state, size.height).block(block), size);
+        return;
     }
-    let items: Vec<Spans> = state
+    let items: Vec<ListItem> = state
         .get_tasks()
         .iter()
-        .map(|t| Spans::from(vec![Span::raw(t.name.to_owned())]))
+        .map(|t| ListItem::new(task_spans(t, state)))
         .collect();
-    let list = Paragraph::new(items).style(styling::normal_text_style());
-    list
+    render_list(frame, size, state, List::new(items).block(block));
 }
 
-fn view_block<'a>(title: &'a str, state: &State) -> Block<'a> {
+fn task_spans<'a>(task: &'a Task, state: &State) -> Spans<'a> {
+    let mut spans = vec![];
+    if state.has_marks() {
+        spans.push(match state.is_marked(&task.gid) {
+            true => Span::styled("+ ", state.get_theme().marked_text_style()),
+            false => Span::raw("  "),
+        });
+    }
+    spans.extend([
+        Span::raw(match task.completed {
+            true => "[x] ",
+            false => "[ ] ",
+        }),
+        Span::raw(task.name.as_str()),
+    ]);
+    if task.dependencies.iter().any(|d| !d.completed) {
+        spans.push(Span::styled(
+            " blocked",
+            state.get_theme().failed_text_style(),
+        ));
+    }
+    if let Some(due_on) = &task.due_on {
+        spans.push(Span::styled(
+            format!(" due {}", due_on),
+            state.get_theme().secondary_text_style(),
+        ));
+    }
+    match state.get_journal().task_status(&task.gid) {
+        Some(Status::Pending) => spans.push(Span::styled(
+            " (pending)",
+            state.get_theme().pending_text_style(),
+        )),
+        Some(Status::Failed(_)) => spans.push(Span::styled(
+            " (failed)",
+            state.get_theme().failed_text_style(),
+        )),
+        None => (),
+    }
+    Spans::from(spans)
+}
+
+fn render_list<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State, list: List) {
+    render_list_at(frame, size, state, list, *state.current_view_index());
+}
+
+fn render_list_at<B: Backend>(
+    frame: &mut Frame<B>,
+    size: Rect,
+    state: &State,
+    list: List,
+    selected: usize,
+) {
+    let highlight_style: Style = match *state.current_focus() {
+        Focus::View => state.get_theme().active_list_item_style(),
+        _ => state.get_theme().current_list_item_style(),
+    };
+    let list = list
+        .style(state.get_theme().normal_text_style())
+        .highlight_style(highlight_style);
+    let mut list_state = ListState::default();
+    list_state.select(Some(selected));
+    frame.render_stateful_widget(list, size, &mut list_state);
+}
+
+pub(super) fn view_block<'a>(title: &str, state: &State) -> Block<'a> {
+    let mut title = match state.get_last_updated() {
+        Some(last_updated) => format!("{} (updated {})", title, time_ago(last_updated)),
+        None => title.to_owned(),
+    };
+    if state.is_task_view() && state.has_marks() {
+        title = format!("{} ({} marked)", title, state.selected_task_gids().len());
+    }
     Block::default()
         .borders(Borders::ALL)
         .border_style(match *state.current_focus() {
-            Focus::View => styling::active_block_border_style(),
-            _ => styling::normal_block_border_style(),
+            Focus::View => state.get_theme().active_block_border_style(),
+            _ => state.get_theme().normal_block_border_style(),
         })
-        .title(Span::styled(title, styling::active_block_title_style()))
+        .title(Span::styled(
+            title,
+            state.get_theme().active_block_title_style(),
+        ))
+}
+
+fn time_ago(time: &DateTime<Utc>) -> String {
+    let elapsed = Utc::now() - *time;
+    match elapsed.num_minutes() {
+        0 => String::from("just now"),
+        minutes if minutes < 60 => format!("{}m ago", minutes),
+        _ if elapsed.num_hours() < 24 => format!("{}h ago", elapsed.num_hours()),
+        _ => format!("{}d ago", elapsed.num_days()),
+    }
 }
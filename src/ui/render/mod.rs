@@ -1,18 +1,26 @@
 mod all;
 mod footer;
+mod help;
 mod log;
 mod main;
+mod picker;
 mod shortcuts;
 mod status;
+mod tab_bar;
+mod timeline;
 mod top_list;
 mod welcome;
 
 use self::log::log;
 use super::*;
 use footer::footer;
+use help::help;
 use main::main;
+use picker::picker;
 use shortcuts::shortcuts;
 use status::status;
+use tab_bar::tab_bar;
 use top_list::top_list;
 
-pub use all::all as render;
+pub use all::{all as render, areas, Panels};
+pub use tab_bar::tab_at;
@@ -0,0 +1,74 @@
+use super::help::centered;
+use super::Frame;
+use crate::state::{InputKind, State};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Render the tasks or teams matching the input while picking one, centered
+/// over the given area.
+///
+pub fn picker<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let title = match state.current_input() {
+        Some(input) if input.kind == InputKind::AddDependency => "Blocked by",
+        Some(input) if input.kind == InputKind::RemoveDependency => "No longer blocked by",
+        Some(input) if input.kind == InputKind::Team => "Team",
+        _ => return,
+    };
+    let theme = state.get_theme();
+    let area = centered(size, 60, 60);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.active_block_border_style())
+        .title(Span::styled(title, theme.active_block_title_style()));
+    frame.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = match state.current_input() {
+        Some(input) if input.kind == InputKind::Team => state
+            .picker_teams()
+            .into_iter()
+            .map(|team| match team {
+                Some(team) => ListItem::new(Span::raw(team.name)),
+                None => ListItem::new(Span::styled("All teams", theme.secondary_text_style())),
+            })
+            .collect(),
+        _ => task_items(state),
+    };
+    if items.is_empty() {
+        let paragraph = Paragraph::new(Span::styled(
+            "No matching tasks.",
+            theme.secondary_text_style(),
+        ))
+        .block(block);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+    let list = List::new(items)
+        .block(block)
+        .style(theme.normal_text_style())
+        .highlight_style(theme.active_list_item_style());
+    let mut list_state = ListState::default();
+    list_state.select(Some(*state.get_picker_index()));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// Return the list items of the tasks which can be picked.
+///
+fn task_items<'a>(state: &State) -> Vec<ListItem<'a>> {
+    state
+        .picker_tasks()
+        .into_iter()
+        .map(|task| {
+            ListItem::new(Spans::from(vec![
+                Span::raw(match task.completed {
+                    true => "[x] ",
+                    false => "[ ] ",
+                }),
+                Span::raw(task.name),
+            ]))
+        })
+        .collect()
+}
@@ -1,7 +1,7 @@
 use super::Frame;
 use crate::state::{Focus, Menu, State, SHORTCUTS};
-use crate::ui::widgets::styling;
 use tui::{
+    backend::Backend,
     layout::Rect,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
@@ -11,21 +11,21 @@ const BLOCK_TITLE: &str = "Shortcuts";
 
 /// Render shortcuts widget according to state.
 ///
-pub fn shortcuts(frame: &mut Frame, size: Rect, state: &State) {
+pub fn shortcuts<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
     let mut block = Block::default()
         .title(BLOCK_TITLE)
         .borders(Borders::ALL)
-        .border_style(styling::normal_block_border_style());
+        .border_style(state.get_theme().normal_block_border_style());
 
-    let mut list_item_style = styling::current_list_item_style();
+    let mut list_item_style = state.get_theme().current_list_item_style();
     if *state.current_focus() == Focus::Menu && *state.current_menu() == Menu::Shortcuts {
         block = block
-            .border_style(styling::active_block_border_style())
+            .border_style(state.get_theme().active_block_border_style())
             .title(Span::styled(
                 BLOCK_TITLE,
-                styling::active_block_title_style(),
+                state.get_theme().active_block_title_style(),
             ));
-        list_item_style = styling::active_list_item_style();
+        list_item_style = state.get_theme().active_list_item_style();
     }
 
     let text: Vec<Spans> = SHORTCUTS
@@ -34,9 +34,25 @@ pub fn shortcuts(frame: &mut Frame, size: Rect, state: &State) {
         .map(|(i, s)| {
             let style = match i == *state.current_shortcut_index() {
                 true => list_item_style,
-                false => styling::normal_text_style(),
+                false => state.get_theme().normal_text_style(),
             };
-            Spans::from(vec![Span::styled(s.to_owned(), style)])
+            let journal = state.get_journal();
+            let label = match *s {
+                "Pending Changes" if journal.failed_count() > 0 => format!(
+                    "{} ({}, {} failed)",
+                    s,
+                    journal.entries().len(),
+                    journal.failed_count()
+                ),
+                "Pending Changes" if !journal.entries().is_empty() => {
+                    format!("{} ({})", s, journal.entries().len())
+                }
+                "Inbox" if state.unread_inbox_count() > 0 => {
+                    format!("{} ({})", s, state.unread_inbox_count())
+                }
+                _ => s.to_string(),
+            };
+            Spans::from(vec![Span::styled(label, style)])
         })
         .collect();
 
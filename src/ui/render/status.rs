@@ -1,29 +1,37 @@
 use super::widgets::spinner;
 use super::Frame;
 use crate::state::{Focus, Menu, State};
-use crate::ui::widgets::styling;
 use tui::{
+    backend::Backend,
     layout::Rect,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
 const BLOCK_TITLE: &str = "Status";
+const OFFLINE_BLOCK_TITLE: &str = "Status (offline)";
 
 /// Render status widget according to state.
 ///
-pub fn status(frame: &mut Frame, size: Rect, state: &State) {
+pub fn status<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let mut block_title = String::from(match state.is_offline() {
+        true => OFFLINE_BLOCK_TITLE,
+        false => BLOCK_TITLE,
+    });
+    if state.get_notification_count() > 0 {
+        block_title.push_str(&format!(" [{} new]", state.get_notification_count()));
+    }
     let mut block = Block::default()
-        .title(BLOCK_TITLE)
+        .title(block_title.as_str())
         .borders(Borders::ALL)
-        .border_style(styling::normal_block_border_style());
+        .border_style(state.get_theme().normal_block_border_style());
 
     if *state.current_focus() == Focus::Menu && *state.current_menu() == Menu::Status {
         block = block
-            .border_style(styling::active_block_border_style())
+            .border_style(state.get_theme().active_block_border_style())
             .title(Span::styled(
-                BLOCK_TITLE,
-                styling::active_block_title_style(),
+                block_title.as_str(),
+                state.get_theme().active_block_title_style(),
             ));
     }
 
@@ -37,15 +45,15 @@ pub fn status(frame: &mut Frame, size: Rect, state: &State) {
     let text = vec![
         Spans::from(vec![Span::styled(
             format!("User: {}", &user.name),
-            styling::normal_text_style(),
+            state.get_theme().normal_text_style(),
         )]),
         Spans::from(vec![Span::styled(
             format!("Email: {}", &user.email),
-            styling::normal_text_style(),
+            state.get_theme().normal_text_style(),
         )]),
         Spans::from(vec![Span::styled(
             format!("Workspace: {}", &workspace.name),
-            styling::normal_text_style(),
+            state.get_theme().normal_text_style(),
         )]),
     ];
     let paragraph = Paragraph::new(text).block(block);
@@ -0,0 +1,60 @@
+use super::Frame;
+use crate::state::{Focus, Menu, State};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::Tabs,
+};
+
+/// Specifying the titles of the panels collapsed into the tab bar.
+///
+pub const TITLES: [&str; 4] = ["Status", "Shortcuts", "Projects", "View"];
+
+/// Render tab bar standing in for the left column on small terminals.
+///
+pub fn tab_bar<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let theme = state.get_theme();
+    let selected = match (state.current_focus(), state.current_menu()) {
+        (Focus::Menu, Menu::Status) => 0,
+        (Focus::Menu, Menu::Shortcuts) => 1,
+        (Focus::Menu, Menu::TopList) => 2,
+        (Focus::View, _) => 3,
+    };
+    let titles: Vec<Spans> = TITLES.iter().map(|t| Spans::from(Span::raw(*t))).collect();
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .style(theme.normal_text_style())
+        .highlight_style(theme.active_list_item_style());
+    frame.render_widget(tabs, size);
+}
+
+/// Return the index of the tab at the given column of the tab bar, matching
+/// the padding and dividers drawn between titles.
+///
+pub fn tab_at(size: Rect, column: u16) -> Option<usize> {
+    let mut x = size.x;
+    for (index, title) in TITLES.iter().enumerate() {
+        let end = x + 1 + title.len() as u16 + 1;
+        if column >= x && column < end {
+            return Some(index);
+        }
+        x = end + 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_at_columns() {
+        let size = Rect::new(0, 0, 60, 1);
+        assert_eq!(tab_at(size, 0), Some(0));
+        assert_eq!(tab_at(size, 7), Some(0));
+        assert_eq!(tab_at(size, 9), Some(1));
+        assert_eq!(tab_at(size, 32), Some(3));
+        assert_eq!(tab_at(size, 59), None);
+    }
+}
@@ -0,0 +1,327 @@
+use super::main::view_block;
+use super::widgets::spinner;
+use super::Frame;
+use crate::asana::Task;
+use crate::state::{State, TimelineZoom};
+use chrono::{Datelike, Local, NaiveDate};
+use std::collections::HashMap;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+/// Specifying the widest the column of task names may be.
+///
+const NAME_WIDTH: u16 = 28;
+
+/// Houses the cells of a row of the chart.
+///
+type Cells = Vec<(char, Style)>;
+
+/// Houses the first and last dates spanned by the bar of a task.
+///
+type Dates = (NaiveDate, NaiveDate);
+
+/// Render the timeline of the project's tasks, drawing each as a bar from
+/// its start to its due date with arrows from the tasks blocking it.
+///
+pub fn timeline<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let timeline = match state.get_timeline() {
+        Some(timeline) => timeline,
+        None => return,
+    };
+    let title = format!(
+        "{} by {}",
+        state
+            .get_project()
+            .map(|p| p.name.as_str())
+            .unwrap_or("Project"),
+        match timeline.zoom {
+            TimelineZoom::Day => "day",
+            TimelineZoom::Week => "week",
+            TimelineZoom::Month => "month",
+        }
+    );
+    let block = view_block(&title, state);
+    let inner = block.inner(size);
+    frame.render_widget(block, size);
+    if state.get_tasks().is_empty() {
+        frame.render_widget(spinner::widget(state, inner.height), inner);
+        return;
+    }
+
+    let theme = state.get_theme();
+    let name_width = NAME_WIDTH.min(inner.width / 3) as usize;
+    let chart_width = (inner.width as usize).saturating_sub(name_width + 1);
+    let column_width = match timeline.zoom {
+        TimelineZoom::Day => 3,
+        TimelineZoom::Week => 4,
+        TimelineZoom::Month => 5,
+    };
+    let today = Local::today().naive_local();
+    let current_gid = state.current_task().map(|t| t.gid.as_str());
+
+    // Lay out a row per section followed by a row per task in it.
+    let mut names: Vec<Spans> = vec![];
+    let mut charts: Vec<Cells> = vec![];
+    let mut task_rows: HashMap<&str, (usize, Option<Dates>)> = HashMap::new();
+    let mut selected_row = 0;
+    for (section, tasks) in state.timeline_sections() {
+        names.push(Spans::from(Span::styled(
+            fit(&section, name_width),
+            theme.active_block_title_style(),
+        )));
+        charts.push(vec![(' ', Style::default()); chart_width]);
+        for task in tasks {
+            let dates = task_dates(task);
+            let style = if Some(task.gid.as_str()) == current_gid {
+                selected_row = names.len();
+                theme.active_list_item_style()
+            } else if task.completed {
+                theme.secondary_text_style()
+            } else {
+                theme.normal_text_style()
+            };
+            names.push(Spans::from(Span::styled(
+                fit(&format!(" {}", task.name), name_width),
+                style,
+            )));
+            let columns = dates.map(|(first, last)| {
+                (
+                    timeline.zoom.column(timeline.start, first) * column_width as i64,
+                    (timeline.zoom.column(timeline.start, last) + 1) * column_width as i64,
+                )
+            });
+            charts.push(bar(task, columns, chart_width, today, state));
+            task_rows.insert(task.gid.as_str(), (charts.len() - 1, dates));
+        }
+    }
+    for task in state.get_tasks() {
+        let (row, (first, _)) = match task_rows.get(task.gid.as_str()) {
+            Some((row, Some(dates))) => (*row, *dates),
+            _ => continue,
+        };
+        for dependency in task.dependencies.iter() {
+            if let Some((blocker_row, Some((_, blocker_last)))) =
+                task_rows.get(dependency.gid.as_str())
+            {
+                let column =
+                    |date| timeline.zoom.column(timeline.start, date) * column_width as i64;
+                arrow(
+                    &mut charts,
+                    (*blocker_row, column(*blocker_last) + column_width as i64),
+                    (row, column(first) - 1),
+                    first <= *blocker_last,
+                    state,
+                );
+            }
+        }
+    }
+
+    let mut lines = header(
+        timeline.zoom,
+        timeline.start,
+        column_width,
+        name_width,
+        chart_width,
+        state,
+    );
+    let height = (inner.height as usize).saturating_sub(lines.len());
+    let offset = (selected_row + 1).saturating_sub(height);
+    for (name, chart) in names.into_iter().zip(charts).skip(offset).take(height) {
+        let mut spans = name.0;
+        spans.push(Span::styled("│", theme.normal_block_border_style()));
+        spans.extend(cell_spans(chart));
+        lines.push(Spans::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Return the first and last dates the task spans, from its start date if
+/// it has one to its due date.
+///
+fn task_dates(task: &Task) -> Option<Dates> {
+    let parse = |date: &Option<String>| {
+        date.as_ref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+    };
+    match (parse(&task.start_on), parse(&task.due_on)) {
+        (Some(start_on), Some(due_on)) => Some((start_on.min(due_on), due_on.max(start_on))),
+        (Some(date), None) | (None, Some(date)) => Some((date, date)),
+        (None, None) => None,
+    }
+}
+
+/// Return the cells of the task's bar spanning the given range of cells,
+/// pointing to where the task lies if it is out of view.
+///
+fn bar(
+    task: &Task,
+    span: Option<(i64, i64)>,
+    chart_width: usize,
+    today: NaiveDate,
+    state: &State,
+) -> Cells {
+    let theme = state.get_theme();
+    let mut cells = vec![(' ', Style::default()); chart_width];
+    let (from, to) = match span {
+        Some(span) if chart_width > 0 => span,
+        _ => return cells,
+    };
+    let overdue = !task.completed
+        && task
+            .due_on
+            .as_ref()
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .map(|d| d < today)
+            .unwrap_or(false);
+    let style = if task.completed {
+        theme.secondary_text_style()
+    } else if overdue {
+        theme.failed_text_style()
+    } else {
+        theme.link_text_style()
+    };
+    if to <= 0 {
+        cells[0] = ('◀', style);
+    } else if from >= chart_width as i64 {
+        cells[chart_width - 1] = ('▶', style);
+    } else {
+        for cell in cells
+            .iter_mut()
+            .take(to.min(chart_width as i64) as usize)
+            .skip(from.max(0) as usize)
+        {
+            *cell = ('█', style);
+        }
+    }
+    cells
+}
+
+/// Draw an arrow from the cell after the end of the blocking task's bar to
+/// the cell before the start of the blocked task's bar, leaving cells of
+/// bars untouched and marking it as failed if the blocked task is late.
+///
+fn arrow(
+    charts: &mut [Cells],
+    (from_row, from): (usize, i64),
+    (to_row, to): (usize, i64),
+    late: bool,
+    state: &State,
+) {
+    if from_row == to_row {
+        return;
+    }
+    let style = match late {
+        true => state.get_theme().failed_text_style(),
+        false => state.get_theme().secondary_text_style(),
+    };
+    let mut draw = |row: usize, cell: i64, glyph: char| {
+        if let Some((existing, existing_style)) = usize::try_from(cell)
+            .ok()
+            .and_then(|cell| charts[row].get_mut(cell))
+        {
+            *existing = match (*existing, glyph) {
+                (' ', _) => glyph,
+                ('─', '│') | ('│', '─') => '┼',
+                _ => return,
+            };
+            *existing_style = style;
+        }
+    };
+    let down = to_row > from_row;
+    if from <= to {
+        for cell in from..to {
+            draw(from_row, cell, '─');
+        }
+        draw(from_row, to, if down { '┐' } else { '┘' });
+    }
+    let rows = match down {
+        true => from_row + 1..to_row,
+        false => to_row + 1..from_row,
+    };
+    for row in rows {
+        draw(row, to, '│');
+    }
+    draw(to_row, to, '▶');
+}
+
+/// Return the two header lines of the timeline, labelling each month or
+/// year above each column and highlighting the column containing today.
+///
+fn header<'a>(
+    zoom: TimelineZoom,
+    start: NaiveDate,
+    column_width: usize,
+    name_width: usize,
+    chart_width: usize,
+    state: &State,
+) -> Vec<Spans<'a>> {
+    let theme = state.get_theme();
+    let today = Local::today().naive_local();
+    let mut periods = " ".repeat(name_width + 1);
+    let mut columns = vec![Span::raw(" ".repeat(name_width + 1))];
+    let mut previous: Option<NaiveDate> = None;
+    for column in 0..(chart_width / column_width) as i32 {
+        let date = zoom.offset(start, column);
+        let new_period = match (zoom, previous) {
+            (_, None) => true,
+            (TimelineZoom::Month, Some(previous)) => date.year() != previous.year(),
+            (_, Some(previous)) => date.month() != previous.month(),
+        };
+        if new_period && periods.chars().count() <= name_width + 1 + column as usize * column_width
+        {
+            let label = match zoom {
+                TimelineZoom::Month => date.format("%Y").to_string(),
+                _ => date.format("%b %Y").to_string(),
+            };
+            let padding = name_width + 1 + column as usize * column_width - periods.chars().count();
+            periods.push_str(&" ".repeat(padding));
+            periods.push_str(&label);
+        }
+        previous = Some(date);
+        let label = match zoom {
+            TimelineZoom::Month => date.format("%b").to_string(),
+            _ => date.format("%d").to_string(),
+        };
+        let style = match zoom.column(date, today) == 0 {
+            true => theme.active_block_title_style(),
+            false => theme.secondary_text_style(),
+        };
+        columns.push(Span::styled(
+            format!("{:<width$}", label, width = column_width),
+            style,
+        ));
+    }
+    let periods: String = periods.chars().take(name_width + 1 + chart_width).collect();
+    vec![
+        Spans::from(Span::styled(periods, theme.secondary_text_style())),
+        Spans::from(columns),
+    ]
+}
+
+/// Return the cells as spans, joining neighbouring cells of the same style.
+///
+fn cell_spans<'a>(cells: Cells) -> Vec<Span<'a>> {
+    let mut spans: Vec<(String, Style)> = vec![];
+    for (glyph, style) in cells {
+        match spans.last_mut() {
+            Some((text, last_style)) if *last_style == style => text.push(glyph),
+            _ => spans.push((glyph.to_string(), style)),
+        }
+    }
+    spans
+        .into_iter()
+        .map(|(text, style)| Span::styled(text, style))
+        .collect()
+}
+
+/// Return the text cut or padded to the given number of characters.
+///
+fn fit(text: &str, width: usize) -> String {
+    let text: String = text.chars().take(width).collect();
+    format!("{:<width$}", text, width = width)
+}
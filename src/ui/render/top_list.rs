@@ -1,8 +1,8 @@
 use super::widgets::spinner;
 use super::Frame;
 use crate::state::{Focus, Menu, State};
-use crate::ui::widgets::styling;
 use tui::{
+    backend::Backend,
     layout::Rect,
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
@@ -12,23 +12,27 @@ const BLOCK_TITLE: &str = "Projects";
 
 /// Render top list widget according to state.
 ///
-pub fn top_list(frame: &mut Frame, size: Rect, state: &State) {
+pub fn top_list<B: Backend>(frame: &mut Frame<B>, size: Rect, state: &State) {
+    let title = match state.get_team() {
+        Some(team) => format!("{} ({})", BLOCK_TITLE, team.name),
+        None => BLOCK_TITLE.to_owned(),
+    };
     let mut block = Block::default()
-        .title(BLOCK_TITLE)
+        .title(title.to_owned())
         .borders(Borders::ALL)
-        .border_style(styling::normal_block_border_style());
+        .border_style(state.get_theme().normal_block_border_style());
 
     let list_item_style;
     if *state.current_focus() == Focus::Menu && *state.current_menu() == Menu::TopList {
-        list_item_style = styling::active_list_item_style();
+        list_item_style = state.get_theme().active_list_item_style();
         block = block
-            .border_style(styling::active_block_border_style())
+            .border_style(state.get_theme().active_block_border_style())
             .title(Span::styled(
-                BLOCK_TITLE,
-                styling::active_block_title_style(),
+                title,
+                state.get_theme().active_block_title_style(),
             ));
     } else {
-        list_item_style = styling::current_list_item_style();
+        list_item_style = state.get_theme().current_list_item_style();
     }
 
     if state.get_projects().is_empty() {
@@ -36,22 +40,29 @@ pub fn top_list(frame: &mut Frame, size: Rect, state: &State) {
         return;
     }
 
-    let items: Vec<Spans> = state
-        .get_projects()
-        .iter()
-        .enumerate()
-        .map(|(i, p)| {
-            let span;
-            if i == *state.current_top_list_index() {
-                span = Span::styled(p.name.to_owned(), list_item_style);
-            } else {
-                span = Span::raw(p.name.to_owned());
-            }
-            Spans::from(vec![span])
-        })
-        .collect();
+    let headings = state.project_headings();
+    let indent = match headings.iter().any(Option::is_some) {
+        true => " ",
+        false => "",
+    };
+    let mut items: Vec<Spans> = vec![];
+    for (i, (p, heading)) in state.get_projects().iter().zip(headings).enumerate() {
+        if let Some(heading) = heading {
+            items.push(Spans::from(Span::styled(
+                heading.to_owned(),
+                state.get_theme().secondary_text_style(),
+            )));
+        }
+        let name = format!("{}{}", indent, p.name);
+        let span = if i == *state.current_top_list_index() {
+            Span::styled(name, list_item_style)
+        } else {
+            Span::raw(name)
+        };
+        items.push(Spans::from(vec![span]));
+    }
     let list = Paragraph::new(items)
-        .style(styling::normal_text_style())
+        .style(state.get_theme().normal_text_style())
         .block(block);
     frame.render_widget(list, size);
 }
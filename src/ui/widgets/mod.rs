@@ -1,3 +1,5 @@
 pub mod color;
+pub mod rich_text;
 pub mod spinner;
 pub mod styling;
+pub mod theme;
@@ -0,0 +1,640 @@
+use crate::ui::theme::Theme;
+use std::collections::HashMap;
+use std::mem;
+use tui::style::{Modifier, Style};
+use tui::text::{Span, Spans};
+
+/// Houses a line of styled runs with the indent and prefix, such as a list
+/// bullet, drawn before it. Wrapped continuations hang below the text.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Line {
+    indent: usize,
+    prefix: String,
+    runs: Vec<(String, Style)>,
+    preformatted: bool,
+}
+
+/// Houses text converted from the HTML of Asana notes and comments, with the
+/// targets of links listed as footnotes after it.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RichText {
+    lines: Vec<Line>,
+}
+
+/// Specifying the kinds of nested lists, numbered lists holding the number
+/// of their next item.
+///
+enum List {
+    Bullet,
+    Numbered(usize),
+}
+
+/// Specifying what an open `<a>` tag stands for.
+///
+enum Anchor {
+    Mention { resolved: bool },
+    Link { href: String, text: String },
+}
+
+/// Oversees the conversion of HTML into lines, tracking the tags open at the
+/// current position.
+///
+struct Parser<'a> {
+    theme: &'a Theme,
+    users: &'a HashMap<String, String>,
+    lines: Vec<Line>,
+    line: Line,
+    styles: Vec<Style>,
+    lists: Vec<List>,
+    quotes: usize,
+    anchor: Option<Anchor>,
+    links: Vec<String>,
+    preformatted: bool,
+    block_ended: bool,
+}
+
+impl RichText {
+    /// Convert the HTML of notes or a comment, resolving @-mentions to the
+    /// names of the given users by GID.
+    ///
+    pub fn parse(html: &str, theme: &Theme, users: &HashMap<String, String>) -> RichText {
+        let mut parser = Parser {
+            theme,
+            users,
+            lines: vec![],
+            line: Line::default(),
+            styles: vec![],
+            lists: vec![],
+            quotes: 0,
+            anchor: None,
+            links: vec![],
+            preformatted: false,
+            block_ended: false,
+        };
+        let mut rest = html;
+        while let Some(start) = rest.find('<') {
+            parser.text(&rest[..start]);
+            match rest[start..].find('>') {
+                Some(end) => {
+                    parser.tag(&rest[start + 1..start + end]);
+                    rest = &rest[start + end + 1..];
+                }
+                None => {
+                    rest = &rest[start..];
+                    break;
+                }
+            }
+        }
+        parser.text(rest);
+        parser.finish()
+    }
+
+    /// Return true if there is no text.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.lines
+            .iter()
+            .all(|l| l.runs.iter().all(|(t, _)| t.trim().is_empty()))
+    }
+
+    /// Return the lines wrapped to the given width.
+    ///
+    pub fn wrap(&self, width: usize) -> Vec<Spans<'static>> {
+        let mut spans = vec![];
+        for line in &self.lines {
+            let prefix = format!("{}{}", " ".repeat(line.indent), line.prefix);
+            let hanging = " ".repeat(prefix.chars().count());
+            let available = width.saturating_sub(hanging.len()).max(1);
+            let mut rows = match line.preformatted {
+                true => chunk(&line.runs, available),
+                false => fill(&line.runs, available),
+            };
+            if rows.is_empty() {
+                rows.push(vec![]);
+            }
+            for (index, row) in rows.into_iter().enumerate() {
+                let lead = match index {
+                    0 => prefix.to_owned(),
+                    _ => hanging.to_owned(),
+                };
+                let mut row_spans = vec![Span::raw(lead)];
+                row_spans.extend(row.into_iter().map(|(t, s)| Span::styled(t, s)));
+                spans.push(Spans::from(row_spans));
+            }
+        }
+        spans
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Apply the tag given without its angle brackets.
+    ///
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let (name, attributes) = match tag.split_once(char::is_whitespace) {
+            Some((name, attributes)) => (name, attributes),
+            None => (tag, ""),
+        };
+        let name = name.to_lowercase();
+        if !closing {
+            self.open(&name, &parse_attributes(attributes));
+        }
+        if closing || self_closing {
+            self.close(&name);
+        }
+    }
+
+    fn open(&mut self, name: &str, attributes: &HashMap<String, String>) {
+        match name {
+            "strong" | "b" => self.push_style(Style::default().add_modifier(Modifier::BOLD)),
+            "em" | "i" => self.push_style(Style::default().add_modifier(Modifier::ITALIC)),
+            "u" => self.push_style(Style::default().add_modifier(Modifier::UNDERLINED)),
+            "s" | "strike" | "del" => {
+                self.push_style(Style::default().add_modifier(Modifier::CROSSED_OUT))
+            }
+            "code" => self.push_style(self.theme.code_text_style()),
+            "h1" | "h2" | "h3" => {
+                self.break_line(false);
+                self.push_style(Style::default().add_modifier(Modifier::BOLD));
+            }
+            "pre" => {
+                self.break_line(false);
+                self.preformatted = true;
+                self.line.preformatted = true;
+                self.push_style(self.theme.code_text_style());
+            }
+            "blockquote" => {
+                self.break_line(false);
+                self.quotes += 1;
+                self.line.indent = self.indent();
+                self.push_style(Style::default().add_modifier(Modifier::ITALIC));
+            }
+            "ul" | "ol" => {
+                self.break_line(false);
+                self.lists.push(match name {
+                    "ol" => List::Numbered(1),
+                    _ => List::Bullet,
+                });
+            }
+            "li" => {
+                self.break_line(false);
+                self.line.indent = self.indent().saturating_sub(2);
+                self.line.prefix = match self.lists.last_mut() {
+                    Some(List::Numbered(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => String::from("• "),
+                };
+            }
+            "p" | "div" => self.break_line(false),
+            "br" => self.break_line(true),
+            "hr" => {
+                self.break_line(false);
+                self.line
+                    .runs
+                    .push(("─".repeat(20), self.theme.secondary_text_style()));
+                self.break_line(false);
+            }
+            "a" => self.open_anchor(attributes),
+            _ => (),
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "strong" | "b" | "em" | "i" | "u" | "s" | "strike" | "del" | "code" => {
+                self.styles.pop();
+            }
+            "h1" | "h2" | "h3" => {
+                self.styles.pop();
+                self.break_line(false);
+            }
+            "pre" => {
+                self.styles.pop();
+                self.break_line(false);
+                self.preformatted = false;
+                self.line.preformatted = false;
+            }
+            "blockquote" => {
+                self.styles.pop();
+                self.break_line(false);
+                self.quotes = self.quotes.saturating_sub(1);
+                self.line.indent = self.indent();
+            }
+            "ul" | "ol" => {
+                self.break_line(false);
+                self.lists.pop();
+                self.line.indent = self.indent();
+            }
+            "li" | "p" | "div" => self.break_line(false),
+            "a" => self.close_anchor(),
+            _ => (),
+        }
+    }
+
+    /// Begin a link, or a mention written as the user's name if known.
+    ///
+    fn open_anchor(&mut self, attributes: &HashMap<String, String>) {
+        let kind = attributes.get("data-asana-type").map(|t| t.as_str());
+        if kind == Some("user") {
+            let name = attributes
+                .get("data-asana-gid")
+                .and_then(|gid| self.users.get(gid));
+            if let Some(name) = name {
+                let style = self.style().patch(self.theme.mention_text_style());
+                self.push_run(&format!("@{}", name), style);
+            }
+            self.push_style(self.theme.mention_text_style());
+            self.anchor = Some(Anchor::Mention {
+                resolved: name.is_some(),
+            });
+            return;
+        }
+        self.push_style(self.theme.link_text_style());
+        self.anchor = Some(Anchor::Link {
+            href: attributes.get("href").cloned().unwrap_or_default(),
+            text: String::new(),
+        });
+    }
+
+    /// End a link, referencing its target as a footnote unless the text
+    /// already shows it.
+    ///
+    fn close_anchor(&mut self) {
+        self.styles.pop();
+        if let Some(Anchor::Link { href, text }) = self.anchor.take() {
+            if !href.is_empty() && text.trim() != href {
+                self.links.push(href);
+                let style = self.theme.secondary_text_style();
+                self.push_run(&format!("[{}]", self.links.len()), style);
+            }
+        }
+    }
+
+    /// Append text found between tags, breaking lines at newlines.
+    ///
+    fn text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let text = decode(text);
+        match &mut self.anchor {
+            Some(Anchor::Mention { resolved: true }) => return,
+            Some(Anchor::Link {
+                text: link_text, ..
+            }) => link_text.push_str(&text),
+            _ => (),
+        }
+        let style = self.style();
+        for (index, part) in text.split('\n').enumerate() {
+            if index > 0 {
+                match self.block_ended && self.line.runs.is_empty() {
+                    true => self.block_ended = false,
+                    false => self.break_line(true),
+                }
+            }
+            if !part.is_empty() && (self.preformatted || !part.trim().is_empty()) {
+                self.push_run(part, style);
+            } else if !part.is_empty() && !self.line.runs.is_empty() {
+                self.push_run(" ", style);
+            }
+        }
+    }
+
+    fn push_run(&mut self, text: &str, style: Style) {
+        self.block_ended = false;
+        match self.line.runs.last_mut() {
+            Some((last, last_style)) if *last_style == style => last.push_str(text),
+            _ => self.line.runs.push((text.to_owned(), style)),
+        }
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.styles.push(self.style().patch(style));
+    }
+
+    fn style(&self) -> Style {
+        self.styles
+            .last()
+            .copied()
+            .unwrap_or_else(|| self.theme.normal_text_style())
+    }
+
+    fn indent(&self) -> usize {
+        2 * (self.lists.len() + self.quotes)
+    }
+
+    /// End the current line if it has text or the break is forced, as for
+    /// newlines, and begin the next one.
+    ///
+    fn break_line(&mut self, force: bool) {
+        let next = Line {
+            indent: self.indent(),
+            preformatted: self.preformatted,
+            ..Line::default()
+        };
+        let line = mem::replace(&mut self.line, next);
+        self.block_ended = !force;
+        if force || !line.runs.is_empty() {
+            self.lines.push(line);
+        }
+    }
+
+    /// Return the lines followed by the footnotes for links.
+    ///
+    fn finish(mut self) -> RichText {
+        self.break_line(false);
+        while self
+            .lines
+            .last()
+            .map(|l| l.runs.is_empty() && l.prefix.is_empty())
+            .unwrap_or(false)
+        {
+            self.lines.pop();
+        }
+        if !self.links.is_empty() {
+            self.lines.push(Line::default());
+        }
+        for (index, href) in self.links.iter().enumerate() {
+            self.lines.push(Line {
+                prefix: format!("[{}] ", index + 1),
+                runs: vec![(href.to_owned(), self.theme.link_text_style())],
+                ..Line::default()
+            });
+        }
+        RichText { lines: self.lines }
+    }
+}
+
+/// Return the attributes of a tag, e.g. `href="https://asana.com"`.
+///
+fn parse_attributes(attributes: &str) -> HashMap<String, String> {
+    let mut parsed = HashMap::new();
+    let mut rest = attributes.trim();
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..end].to_lowercase();
+        rest = rest[end..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(value) => {
+                let value = value.trim_start();
+                let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'');
+                let (value, remainder) = match quote {
+                    Some(quote) => {
+                        let value = &value[1..];
+                        let end = value.find(quote).unwrap_or(value.len());
+                        (&value[..end], value.get(end + 1..).unwrap_or(""))
+                    }
+                    None => {
+                        let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                        (&value[..end], &value[end..])
+                    }
+                };
+                rest = remainder.trim_start();
+                decode(value)
+            }
+            None => String::new(),
+        };
+        if !key.is_empty() {
+            parsed.insert(key, value);
+        }
+    }
+    parsed
+}
+
+/// Replace character references such as `&amp;` and `&#39;`.
+///
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find(';') {
+            Some(end) if end <= 10 => end,
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => match entity.strip_prefix('#') {
+                Some(code) => match code.strip_prefix('x') {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => code.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => None,
+            },
+        };
+        match c {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Return the runs split into rows of whole words no wider than the width,
+/// breaking words which are wider on their own.
+///
+fn fill(runs: &[(String, Style)], width: usize) -> Vec<Vec<(String, Style)>> {
+    let mut words: Vec<Vec<(String, Style)>> = vec![];
+    let mut word = vec![];
+    for (text, style) in runs {
+        for c in text.chars() {
+            match c.is_whitespace() {
+                true if !word.is_empty() => words.push(mem::take(&mut word)),
+                true => (),
+                false => push_char(&mut word, c, *style),
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut row_width = 0;
+    for word in words {
+        let word_width: usize = word.iter().map(|(t, _)| t.chars().count()).sum();
+        if row_width > 0 && row_width + 1 + word_width > width {
+            rows.push(mem::take(&mut row));
+            row_width = 0;
+        }
+        if row_width > 0 {
+            let style = match (row.last(), word.first()) {
+                (Some((_, before)), Some((_, after))) if before == after => *before,
+                _ => Style::default(),
+            };
+            push_char(&mut row, ' ', style);
+            row_width += 1;
+        }
+        for (text, style) in word {
+            for c in text.chars() {
+                if row_width == width {
+                    rows.push(mem::take(&mut row));
+                    row_width = 0;
+                }
+                push_char(&mut row, c, style);
+                row_width += 1;
+            }
+        }
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+/// Return the runs split into rows of exactly the width, keeping whitespace.
+///
+fn chunk(runs: &[(String, Style)], width: usize) -> Vec<Vec<(String, Style)>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut row_width = 0;
+    for (text, style) in runs {
+        for c in text.chars() {
+            if row_width == width {
+                rows.push(mem::take(&mut row));
+                row_width = 0;
+            }
+            push_char(&mut row, c, *style);
+            row_width += 1;
+        }
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+fn push_char(runs: &mut Vec<(String, Style)>, c: char, style: Style) {
+    match runs.last_mut() {
+        Some((text, last_style)) if *last_style == style => text.push(c),
+        _ => runs.push((c.to_string(), style)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(html: &str, width: usize) -> Vec<String> {
+        let users = HashMap::from([(String::from("42"), String::from("Ada Lovelace"))]);
+        RichText::parse(html, &Theme::default(), &users)
+            .wrap(width)
+            .iter()
+            .map(|spans| spans.0.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parse_emphasis() {
+        let theme = Theme::default();
+        let rich_text = RichText::parse(
+            "<body>Plain <strong>bold <em>both</em></strong> &amp; <code>x &lt; 1</code></body>",
+            &theme,
+            &HashMap::new(),
+        );
+        let runs = &rich_text.lines[0].runs;
+        assert_eq!(runs[0], (String::from("Plain "), theme.normal_text_style()));
+        assert!(runs[1].1.add_modifier.contains(Modifier::BOLD));
+        assert!(runs[2].1.add_modifier.contains(Modifier::ITALIC));
+        assert!(runs[2].1.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(runs[3].0, " & ");
+        assert_eq!(runs[4], (String::from("x < 1"), theme.code_text_style()));
+    }
+
+    #[test]
+    fn parse_lists() {
+        assert_eq!(
+            text(
+                "<body>Steps:\n<ol><li>One</li><li>Two<ul><li>Nested</li></ul></li></ol>Done</body>",
+                40
+            ),
+            vec!["Steps:", "1. One", "2. Two", "  • Nested", "Done"]
+        );
+    }
+
+    #[test]
+    fn parse_links_and_mentions() {
+        assert_eq!(
+            text(
+                "<body>Ask <a data-asana-gid=\"42\" data-asana-type=\"user\">@ada</a> \
+                 or <a data-asana-gid=\"7\" data-asana-type=\"user\">@Bob</a> about \
+                 <a href=\"https://example.com/spec\">the spec</a> at \
+                 <a href=\"https://asana.com\">https://asana.com</a></body>",
+                80
+            ),
+            vec![
+                "Ask @Ada Lovelace or @Bob about the spec[1] at https://asana.com",
+                "",
+                "[1] https://example.com/spec",
+            ]
+        );
+        assert_eq!(
+            text(
+                "<body>cc <a data-asana-gid='42' data-asana-type='user'/></body>",
+                40
+            ),
+            vec!["cc @Ada Lovelace"]
+        );
+    }
+
+    #[test]
+    fn parse_newlines_and_preformatted() {
+        assert_eq!(
+            text(
+                "<body>First\n\nSecond<pre>fn  main()\n  {}</pre></body>",
+                40
+            ),
+            vec!["First", "", "Second", "fn  main()", "  {}"]
+        );
+    }
+
+    #[test]
+    fn wrap_with_hanging_indent() {
+        assert_eq!(
+            text(
+                "<body><ul><li>alpha beta gamma delta</li></ul>abcdefghijkl</body>",
+                12
+            ),
+            vec!["• alpha beta", "  gamma", "  delta", "abcdefghijkl"]
+        );
+        assert_eq!(
+            text("<body>abcdefghijkl</body>", 5),
+            vec!["abcde", "fghij", "kl"]
+        );
+    }
+
+    #[test]
+    fn decode_entities() {
+        assert_eq!(
+            decode("&lt;&#39;&#x41;&unknown; & x&gt;"),
+            "<'A&unknown; & x>"
+        );
+    }
+}
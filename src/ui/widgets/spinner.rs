@@ -11,10 +11,10 @@ pub const FRAMES: &[&str] = &["▄ ▆ ▇", "▄ ▆ ▇", "▆ ▇ ▄", "▆
 
 /// Build the spinner widget according to state.
 ///
-pub fn widget(state: &State, container_height: u16) -> Paragraph {
+pub fn widget(state: &State, container_height: u16) -> Paragraph<'_> {
     // Remove a line for each border (top and bottom) as well as the line the
     // widget will be drawn on. Finally divide in half.
-    let vertical_line_offset = (container_height - 3) / 2;
+    let vertical_line_offset = container_height.saturating_sub(3) / 2;
 
     let mut text = vec![Spans::from(vec![Span::raw(
         FRAMES[*state.get_spinner_index()],
@@ -1,46 +1,112 @@
-use crate::ui::color::*;
-use tui::style::{Color, Modifier, Style};
+use crate::ui::theme::Theme;
+use tui::style::{Modifier, Style};
 
-/// Return the border style for active blocks.
-///
-pub fn active_block_border_style() -> Style {
-    Style::default().fg(PURPLE)
-}
+impl Theme {
+    /// Return the border style for active blocks.
+    ///
+    pub fn active_block_border_style(&self) -> Style {
+        Style::default().fg(self.active)
+    }
 
-/// Return the border style for normal blocks.
-///
-pub fn normal_block_border_style() -> Style {
-    Style::default().fg(Color::White)
-}
+    /// Return the border style for normal blocks.
+    ///
+    pub fn normal_block_border_style(&self) -> Style {
+        Style::default().fg(self.border)
+    }
 
-/// Return the title style for active blocks.
-///
-pub fn active_block_title_style() -> Style {
-    Style::default().add_modifier(Modifier::BOLD)
-}
+    /// Return the title style for active blocks.
+    ///
+    pub fn active_block_title_style(&self) -> Style {
+        Style::default().add_modifier(Modifier::BOLD)
+    }
 
-/// Return the style for current list items.
-///
-pub fn current_list_item_style() -> Style {
-    Style::default()
-        .fg(Color::White)
-        .add_modifier(Modifier::BOLD)
-}
+    /// Return the style for current list items.
+    ///
+    pub fn current_list_item_style(&self) -> Style {
+        Style::default().fg(self.text).add_modifier(Modifier::BOLD)
+    }
 
-/// Return the style for active list items.
-///
-pub fn active_list_item_style() -> Style {
-    current_list_item_style().fg(PURPLE)
-}
+    /// Return the style for active list items.
+    ///
+    pub fn active_list_item_style(&self) -> Style {
+        self.current_list_item_style().fg(self.active)
+    }
 
-/// Return the style for normal text.
-///
-pub fn normal_text_style() -> Style {
-    Style::default().fg(Color::White)
-}
+    /// Return the style for normal text.
+    ///
+    pub fn normal_text_style(&self) -> Style {
+        Style::default().fg(self.text)
+    }
+
+    /// Return the style for secondary text.
+    ///
+    pub fn secondary_text_style(&self) -> Style {
+        Style::default().fg(self.secondary)
+    }
+
+    /// Return the style for changes awaiting replay.
+    ///
+    pub fn pending_text_style(&self) -> Style {
+        Style::default().fg(self.warning)
+    }
+
+    /// Return the style for changes which failed to replay.
+    ///
+    pub fn failed_text_style(&self) -> Style {
+        Style::default().fg(self.error)
+    }
+
+    /// Return the style for the marker of tasks marked for a bulk action.
+    ///
+    pub fn marked_text_style(&self) -> Style {
+        Style::default()
+            .fg(self.success)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Return the style for tasks created by an import.
+    ///
+    pub fn created_text_style(&self) -> Style {
+        Style::default().fg(self.success)
+    }
+
+    /// Return the style for the banner.
+    ///
+    pub fn banner_style(&self) -> Style {
+        Style::default().fg(self.error)
+    }
+
+    /// Return the style for prompts and hints in the footer.
+    ///
+    pub fn hint_text_style(&self) -> Style {
+        Style::default().fg(self.warning)
+    }
+
+    /// Return the style for messages and the version in the footer.
+    ///
+    pub fn message_text_style(&self) -> Style {
+        Style::default().fg(self.success)
+    }
+
+    /// Return the style for links in rich text.
+    ///
+    pub fn link_text_style(&self) -> Style {
+        Style::default()
+            .fg(self.secondary)
+            .add_modifier(Modifier::UNDERLINED)
+    }
+
+    /// Return the style for @-mentions in rich text.
+    ///
+    pub fn mention_text_style(&self) -> Style {
+        Style::default()
+            .fg(self.active)
+            .add_modifier(Modifier::BOLD)
+    }
 
-/// Return the style for the banner.
-///
-pub fn banner_style() -> Style {
-    Style::default().fg(PINK)
+    /// Return the style for code in rich text.
+    ///
+    pub fn code_text_style(&self) -> Style {
+        Style::default().fg(self.warning)
+    }
 }
@@ -0,0 +1,335 @@
+use crate::ui::color::*;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+use tui::style::Color;
+
+/// Specifying the names of the built-in themes, in the order they are cycled.
+///
+pub const THEMES: [&str; 4] = ["dark", "light", "high-contrast", "basic"];
+
+/// Houses the colors used to render the interface.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub text: Color,
+    pub border: Color,
+    pub background: Color,
+    pub active: Color,
+    pub secondary: Color,
+    pub warning: Color,
+    pub error: Color,
+    pub success: Color,
+}
+
+/// Specifying colors overriding those of a built-in theme in the
+/// configuration file.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeColors {
+    pub text: Option<String>,
+    pub border: Option<String>,
+    pub background: Option<String>,
+    pub active: Option<String>,
+    pub secondary: Option<String>,
+    pub warning: Option<String>,
+    pub error: Option<String>,
+    pub success: Option<String>,
+}
+
+/// Specifying how many colors the terminal can display.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorSupport {
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+impl ColorSupport {
+    /// Detect color support from the `COLORTERM` and `TERM` environment
+    /// variables, which are often not forwarded over SSH.
+    ///
+    pub fn detect() -> ColorSupport {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+        match (colorterm.as_str(), term.as_str()) {
+            ("truecolor", _) | ("24bit", _) => ColorSupport::TrueColor,
+            (_, term) if term.contains("256") => ColorSupport::Ansi256,
+            _ => ColorSupport::Ansi16,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// Return the built-in theme with the given name.
+    ///
+    pub fn named(name: &str) -> Result<Theme> {
+        match name {
+            "dark" => Ok(Theme::dark()),
+            "light" => Ok(Theme::light()),
+            "high-contrast" => Ok(Theme::high_contrast()),
+            "basic" => Ok(Theme::basic()),
+            _ => Err(anyhow!(
+                "Unknown theme '{}', expected one of {}",
+                name,
+                THEMES.join(", ")
+            )),
+        }
+    }
+
+    /// Return the built-in theme following this one.
+    ///
+    pub fn next(&self) -> Theme {
+        let index = THEMES.iter().position(|n| *n == self.name).unwrap_or(0);
+        Theme::named(THEMES[(index + 1) % THEMES.len()]).unwrap()
+    }
+
+    /// Return the theme with the given colors replaced.
+    ///
+    pub fn with_colors(mut self, colors: &ThemeColors) -> Result<Theme> {
+        let fields = [
+            (&mut self.text, &colors.text),
+            (&mut self.border, &colors.border),
+            (&mut self.background, &colors.background),
+            (&mut self.active, &colors.active),
+            (&mut self.secondary, &colors.secondary),
+            (&mut self.warning, &colors.warning),
+            (&mut self.error, &colors.error),
+            (&mut self.success, &colors.success),
+        ];
+        for (color, value) in fields {
+            if let Some(value) = value {
+                *color = parse_color(value)?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Return the theme with its colors approximated by those the terminal
+    /// can display.
+    ///
+    pub fn degrade(&self, support: ColorSupport) -> Theme {
+        let degrade = |color: Color| match (support, color) {
+            (ColorSupport::TrueColor, color) => color,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(ansi256(r, g, b)),
+            (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => ansi16(r, g, b),
+            (ColorSupport::Ansi16, Color::Indexed(_)) => Color::Reset,
+            (_, color) => color,
+        };
+        Theme {
+            name: self.name.to_owned(),
+            text: degrade(self.text),
+            border: degrade(self.border),
+            background: degrade(self.background),
+            active: degrade(self.active),
+            secondary: degrade(self.secondary),
+            warning: degrade(self.warning),
+            error: degrade(self.error),
+            success: degrade(self.success),
+        }
+    }
+
+    fn dark() -> Theme {
+        Theme {
+            name: String::from("dark"),
+            text: Color::White,
+            border: Color::White,
+            background: Color::Black,
+            active: PURPLE,
+            secondary: BLUE,
+            warning: YELLOW,
+            error: PINK,
+            success: GREEN,
+        }
+    }
+
+    fn light() -> Theme {
+        Theme {
+            name: String::from("light"),
+            text: Color::Black,
+            border: Color::DarkGray,
+            background: Color::Reset,
+            active: Color::Rgb(108, 62, 214),
+            secondary: Color::Rgb(0, 116, 166),
+            warning: Color::Rgb(166, 112, 0),
+            error: Color::Rgb(196, 30, 80),
+            success: Color::Rgb(0, 135, 105),
+        }
+    }
+
+    fn high_contrast() -> Theme {
+        Theme {
+            name: String::from("high-contrast"),
+            text: Color::White,
+            border: Color::White,
+            background: Color::Black,
+            active: Color::LightYellow,
+            secondary: Color::LightCyan,
+            warning: Color::Yellow,
+            error: Color::LightRed,
+            success: Color::LightGreen,
+        }
+    }
+
+    fn basic() -> Theme {
+        Theme {
+            name: String::from("basic"),
+            text: Color::Reset,
+            border: Color::Reset,
+            background: Color::Reset,
+            active: Color::Magenta,
+            secondary: Color::Cyan,
+            warning: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+        }
+    }
+}
+
+/// Parse a color given as `#rrggbb`, a 256-color index or a name such as
+/// `lightblue`.
+///
+fn parse_color(value: &str) -> Result<Color> {
+    let value = value.trim().to_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() == 6 {
+            if let Ok(rgb) = u32::from_str_radix(hex, 16) {
+                return Ok(Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8));
+            }
+        }
+        return Err(anyhow!("Failed to parse color '{}'", value));
+    }
+    if let Ok(index) = value.parse::<u8>() {
+        return Ok(Color::Indexed(index));
+    }
+    match value.replace(['-', '_', ' '], "").as_str() {
+        "reset" | "default" => Ok(Color::Reset),
+        "black" => Ok(Color::Black),
+        "red" => Ok(Color::Red),
+        "green" => Ok(Color::Green),
+        "yellow" => Ok(Color::Yellow),
+        "blue" => Ok(Color::Blue),
+        "magenta" => Ok(Color::Magenta),
+        "cyan" => Ok(Color::Cyan),
+        "gray" | "grey" => Ok(Color::Gray),
+        "darkgray" | "darkgrey" => Ok(Color::DarkGray),
+        "lightred" => Ok(Color::LightRed),
+        "lightgreen" => Ok(Color::LightGreen),
+        "lightyellow" => Ok(Color::LightYellow),
+        "lightblue" => Ok(Color::LightBlue),
+        "lightmagenta" => Ok(Color::LightMagenta),
+        "lightcyan" => Ok(Color::LightCyan),
+        "white" => Ok(Color::White),
+        _ => Err(anyhow!("Failed to parse color '{}'", value)),
+    }
+}
+
+/// Return the closest color in the 6x6x6 cube of the 256-color palette.
+///
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| ((v as u16 * 5 + 127) / 255) as u8;
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// Return the closest of the 16 standard terminal colors.
+///
+fn ansi16(r: u8, g: u8, b: u8) -> Color {
+    const PALETTE: [(Color, (i32, i32, i32)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    PALETTE
+        .iter()
+        .min_by_key(|(_, (pr, pg, pb))| (r - pr).pow(2) + (g - pg).pow(2) + (b - pb).pow(2))
+        .map(|(color, _)| *color)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named() {
+        for name in THEMES {
+            assert_eq!(Theme::named(name).unwrap().name, name);
+        }
+        assert!(Theme::named("solarized").is_err());
+    }
+
+    #[test]
+    fn next() {
+        assert_eq!(Theme::default().next().name, "light");
+        assert_eq!(Theme::basic().next().name, "dark");
+    }
+
+    #[test]
+    fn with_colors() {
+        let colors = ThemeColors {
+            active: Some(String::from("#ff0080")),
+            secondary: Some(String::from("light-blue")),
+            warning: Some(String::from("208")),
+            ..ThemeColors::default()
+        };
+        let theme = Theme::dark().with_colors(&colors).unwrap();
+        assert_eq!(theme.active, Color::Rgb(255, 0, 128));
+        assert_eq!(theme.secondary, Color::LightBlue);
+        assert_eq!(theme.warning, Color::Indexed(208));
+        assert_eq!(theme.error, PINK);
+        let invalid = ThemeColors {
+            text: Some(String::from("#ff00")),
+            ..ThemeColors::default()
+        };
+        assert!(Theme::dark().with_colors(&invalid).is_err());
+    }
+
+    #[test]
+    fn degrade() {
+        let theme = Theme::dark();
+        assert_eq!(theme.degrade(ColorSupport::TrueColor), theme);
+        let theme_256 = theme.degrade(ColorSupport::Ansi256);
+        assert_eq!(theme_256.text, Color::White);
+        assert_eq!(theme_256.warning, Color::Indexed(220));
+        let theme_16 = theme.degrade(ColorSupport::Ansi16);
+        assert_eq!(theme_16.warning, Color::LightYellow);
+        assert_eq!(theme_16.secondary, Color::LightCyan);
+        assert_eq!(
+            Theme::dark()
+                .with_colors(&ThemeColors {
+                    active: Some(String::from("208")),
+                    ..ThemeColors::default()
+                })
+                .unwrap()
+                .degrade(ColorSupport::Ansi16)
+                .active,
+            Color::Reset
+        );
+    }
+}